@@ -95,6 +95,105 @@ def test_predict_static():
     return True
 
 
+def test_streaming_ses_threads():
+    """Test concurrent StreamingSes updates from Python threads."""
+    import threading
+    import sa_native
+
+    print("\n--- Testing StreamingSes thread safety ---")
+
+    # A constant stream has the same final level in any interleaving,
+    # so the result is deterministic even though thread order is not
+    smoother = sa_native.StreamingSes(0.3)
+    updates_per_thread = 1000
+
+    def push_values():
+        for _ in range(updates_per_thread):
+            smoother.update(4.0)
+
+    threads = [threading.Thread(target=push_values) for _ in range(2)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+
+    print(f"Level: {smoother.level}, Count: {smoother.count}")
+    assert smoother.count == 2 * updates_per_thread, f"Lost updates: {smoother.count}"
+    assert smoother.level == 4.0, f"Expected level 4.0, got {smoother.level}"
+    assert smoother.forecast(2) == [4.0, 4.0]
+    print("✓ Test passed")
+
+    return True
+
+
+def test_rolling_apply():
+    """Test rolling_apply with a Python callback and its error handling."""
+    import math
+    import sa_native
+
+    print("\n--- Testing rolling_apply ---")
+
+    data = [1.0, 4.0, 2.0, 8.0, 5.0, 5.0]
+    result = sa_native.rolling_apply(data, 3, lambda w: max(w) - min(w))
+    print(f"Input: {data}, Window: 3")
+    print(f"Result: {result}")
+    assert all(math.isnan(v) for v in result[:2]), f"Expected two NaNs, got {result[:2]}"
+    assert result[2:] == [3.0, 6.0, 6.0, 3.0], f"Expected [3.0, 6.0, 6.0, 3.0], got {result[2:]}"
+    print("✓ Test passed")
+
+    # Non-numeric and non-finite returns are rejected
+    for func, error in [(lambda w: "wide", TypeError), (lambda w: math.inf, ValueError)]:
+        try:
+            sa_native.rolling_apply(data, 3, func)
+            print(f"✗ Should have raised {error.__name__}")
+            return False
+        except error as e:
+            print(f"✓ Correctly raised {error.__name__}: {e}")
+
+    # Exceptions from the callback propagate unchanged
+    def failing(window):
+        raise KeyError("boom")
+
+    try:
+        sa_native.rolling_apply(data, 3, failing)
+        print("✗ Should have propagated the callback's KeyError")
+        return False
+    except KeyError as e:
+        print(f"✓ Callback exception propagated: {e!r}")
+
+    return True
+
+
+def test_forecast_iterator():
+    """Test the lazy forecast iterators against the finite forecasts."""
+    import itertools
+    import sa_native
+
+    print("\n--- Testing ForecastIterator ---")
+
+    data = [4.0, 7.0, 5.0, 9.5]
+    static = sa_native.predict_static_iter(data)
+    assert iter(static) is static, "Expected the iterator to be its own iterator"
+    first = list(itertools.islice(static, 5))
+    print(f"Static: {first}")
+    assert first == sa_native.predict_static(data, 5), f"Unexpected static values {first}"
+    assert static.steps_taken == 5, f"Expected 5 steps taken, got {static.steps_taken}"
+
+    drift = list(itertools.islice(sa_native.predict_drift_iter(data), 5))
+    print(f"Drift: {drift}")
+    assert drift == sa_native.predict_drift(data, 5), f"Unexpected drift values {drift}"
+    print("✓ Test passed")
+
+    try:
+        sa_native.predict_static_iter([])
+        print("✗ Should have raised ValueError")
+        return False
+    except ValueError as e:
+        print(f"✓ Correctly raised ValueError: {e}")
+
+    return True
+
+
 def main():
     """Main test runner."""
     print("=" * 60)
@@ -109,6 +208,12 @@ def main():
     try:
         if not test_predict_static():
             return 1
+        if not test_streaming_ses_threads():
+            return 1
+        if not test_rolling_apply():
+            return 1
+        if not test_forecast_iterator():
+            return 1
     except Exception as e:
         print(f"\n✗ Test failed with error: {e}")
         import traceback
@@ -0,0 +1,596 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::methods::{forecast_with_method_impl, min_history_impl};
+use crate::stats::{median_and_mad, student_t_two_sided_p, variance_impl};
+
+/// Family-wise significance level of `detect_level_shift_impl`, after the
+/// Bonferroni correction over candidate splits.
+const LEVEL_SHIFT_SIGNIFICANCE: f64 = 0.01;
+
+/// Check that a z-score threshold is finite and positive.
+fn validate_threshold(threshold: f64) -> Result<(), String> {
+    if !threshold.is_finite() || threshold <= 0.0 {
+        return Err(format!(
+            "Threshold must be a finite value greater than 0, got {}",
+            threshold
+        ));
+    }
+
+    Ok(())
+}
+
+/// Whether `value` lies more than `threshold` scales from `center`. A zero
+/// scale flags any value that differs from the center.
+fn exceeds_threshold(value: f64, center: f64, scale: f64, threshold: f64) -> bool {
+    let deviation = (value - center).abs();
+    if scale > 0.0 {
+        deviation / scale > threshold
+    } else {
+        deviation > 0.0
+    }
+}
+
+/// Sliding-window baseline for streaming anomaly detection.
+///
+/// Mean and sum of squared deviations are maintained with Welford's update
+/// and its inverse, so adding a point and evicting the oldest are O(1). With
+/// `robust`, values are scored against the median and MAD of the window
+/// instead, recomputed in O(window) per push.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct AnomalyState {
+    pub(crate) window: usize,
+    pub(crate) threshold: f64,
+    pub(crate) robust: bool,
+    values: VecDeque<f64>,
+    mean: f64,
+    m2: f64,
+}
+
+impl AnomalyState {
+    pub(crate) fn new(window: usize, threshold: f64, robust: bool) -> Result<Self, String> {
+        if window < 2 {
+            return Err(format!("Window must be at least 2, got {}", window));
+        }
+
+        validate_threshold(threshold)?;
+
+        Ok(AnomalyState {
+            window,
+            threshold,
+            robust,
+            values: VecDeque::with_capacity(window + 1),
+            mean: 0.0,
+            m2: 0.0,
+        })
+    }
+
+    fn add(&mut self, value: f64) {
+        self.values.push_back(value);
+        let delta = value - self.mean;
+        self.mean += delta / self.values.len() as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some(value) = self.values.pop_front() {
+            if self.values.is_empty() {
+                self.mean = 0.0;
+                self.m2 = 0.0;
+                return;
+            }
+            let delta = value - self.mean;
+            self.mean -= delta / self.values.len() as f64;
+            // Rounding can leave a tiny negative remainder
+            self.m2 = (self.m2 - delta * (value - self.mean)).max(0.0);
+        }
+    }
+
+    /// Mean of the baseline window, if it holds any value.
+    pub(crate) fn mean(&self) -> Option<f64> {
+        if self.values.is_empty() {
+            None
+        } else {
+            Some(self.mean)
+        }
+    }
+
+    /// Sample standard deviation of the baseline window, if it holds at
+    /// least two values.
+    pub(crate) fn std(&self) -> Option<f64> {
+        if self.values.len() < 2 {
+            None
+        } else {
+            Some((self.m2 / (self.values.len() - 1) as f64).sqrt())
+        }
+    }
+
+    /// Score `value` against the current baseline, then add it to the window.
+    ///
+    /// Nothing is flagged until the window is full. A zero-scale baseline
+    /// flags any value that differs from it.
+    pub(crate) fn push(&mut self, value: f64) -> Result<bool, String> {
+        if !value.is_finite() {
+            return Err(format!("Observation must be finite, got {}", value));
+        }
+
+        let is_anomaly = self.values.len() == self.window && {
+            let (center, scale) = if self.robust {
+                median_and_mad(self.values.make_contiguous())
+            } else {
+                (self.mean, self.std().unwrap())
+            };
+            exceeds_threshold(value, center, scale, self.threshold)
+        };
+
+        self.add(value);
+        if self.values.len() > self.window {
+            self.evict_oldest();
+        }
+
+        Ok(is_anomaly)
+    }
+}
+
+/// Core batch z-score outlier logic: indices whose distance from the center
+/// exceeds `threshold` times the scale.
+///
+/// The center and scale are the mean and sample standard deviation, or with
+/// `robust` the median and `mad_impl`, scored as in `AnomalyState::push`.
+pub(crate) fn detect_outliers_zscore_impl(
+    data: &[f64],
+    threshold: f64,
+    robust: bool,
+) -> Result<Vec<usize>, String> {
+    if data.len() < 2 {
+        return Err("Outlier detection requires at least two data points".to_string());
+    }
+
+    if data.iter().any(|v| !v.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    validate_threshold(threshold)?;
+
+    let (center, scale) = if robust {
+        median_and_mad(data)
+    } else {
+        (data.iter().sum::<f64>() / data.len() as f64, variance_impl(data).sqrt())
+    };
+
+    Ok(data
+        .iter()
+        .enumerate()
+        .filter(|(_, &value)| exceeds_threshold(value, center, scale, threshold))
+        .map(|(t, _)| t)
+        .collect())
+}
+
+/// Flag outliers in a whole series by z-score.
+///
+/// A point is flagged when `|x - center| / scale > threshold`. By default the
+/// center and scale are the mean and sample standard deviation, but a few
+/// large outliers inflate the standard deviation enough to hide themselves.
+/// With `robust=True` the median and the MAD (see `mad`) are used instead,
+/// which the outliers barely move. A zero scale flags every value that
+/// differs from the center.
+///
+/// # Arguments
+///
+/// * `data` - Series to scan
+/// * `threshold` - Z-score above which a point is flagged (default 3)
+/// * `robust` - Use the median and MAD instead of the mean and std
+///
+/// # Returns
+///
+/// Indices of the flagged points, in increasing order
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * data has fewer than two points or a non-finite value
+/// * threshold is not finite and positive
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.detect_outliers_zscore(sales, robust=True)
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, threshold=3.0, robust=false))]
+pub fn detect_outliers_zscore(
+    data: Vec<f64>,
+    threshold: f64,
+    robust: bool,
+) -> PyResult<Vec<usize>> {
+    detect_outliers_zscore_impl(&data, threshold, robust).map_err(PyValueError::new_err)
+}
+
+/// Core level shift logic: the start of the second segment of the split of
+/// `data` into two segments of at least `min_segment` points whose means
+/// differ most significantly, or `None` if no split is significant.
+///
+/// Each split is scored with the pooled two-sample t statistic, and the best
+/// one is kept if its p-value, multiplied by the number of candidate splits
+/// (a Bonferroni correction for having tried them all), is below
+/// `LEVEL_SHIFT_SIGNIFICANCE`.
+pub(crate) fn detect_level_shift_impl(
+    data: &[f64],
+    min_segment: usize,
+) -> Result<Option<usize>, String> {
+    if min_segment == 0 {
+        return Err("Minimum segment length must be at least 1".to_string());
+    }
+
+    let n = data.len();
+    if n < 3 || min_segment > n / 2 {
+        return Err(format!(
+            "Level shift detection with min_segment {} requires at least {} data points, got {}",
+            min_segment,
+            (2 * min_segment as u128).max(3),
+            n
+        ));
+    }
+
+    if data.iter().any(|v| !v.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    // Prefix sums of centered values and squares give each segment's SSE in
+    // O(1); centering keeps them accurate when the values share an offset
+    let offset = data.iter().sum::<f64>() / n as f64;
+    let mut sums = vec![0.0; n + 1];
+    let mut squares = vec![0.0; n + 1];
+    for (t, value) in data.iter().enumerate() {
+        let centered = value - offset;
+        sums[t + 1] = sums[t] + centered;
+        squares[t + 1] = squares[t] + centered * centered;
+    }
+    let segment = |from: usize, to: usize| {
+        let count = (to - from) as f64;
+        let mean = (sums[to] - sums[from]) / count;
+        let sse = (squares[to] - squares[from] - count * mean * mean).max(0.0);
+        (mean, sse)
+    };
+
+    // The largest |t| has the smallest p-value, without underflowing to 0
+    let df = (n - 2) as f64;
+    let mut best = (0, 0.0);
+    for k in min_segment..=n - min_segment {
+        let (before, before_sse) = segment(0, k);
+        let (after, after_sse) = segment(k, n);
+        let pooled_variance = (before_sse + after_sse) / df;
+        let spread = (pooled_variance * (1.0 / k as f64 + 1.0 / (n - k) as f64)).sqrt();
+        let t = if spread > 0.0 {
+            ((after - before) / spread).abs()
+        } else if after != before {
+            f64::INFINITY
+        } else {
+            0.0
+        };
+        if t > best.1 {
+            best = (k, t);
+        }
+    }
+
+    let (k, t) = best;
+    if t == 0.0 {
+        return Ok(None);
+    }
+    let candidates = (n - 2 * min_segment + 1) as f64;
+    let p_value = if t.is_infinite() { 0.0 } else { student_t_two_sided_p(t, df) };
+    Ok((p_value * candidates < LEVEL_SHIFT_SIGNIFICANCE).then_some(k))
+}
+
+/// Core post-shift forecast logic: `method` fitted on the data from the
+/// detected level shift on, or on all of it when there is none.
+///
+/// `min_segment` defaults to the method's minimum history, so the segment
+/// after a shift is always long enough to fit it.
+pub(crate) fn forecast_after_shift_impl(
+    data: &[f64],
+    horizon: usize,
+    method: &str,
+    min_segment: Option<usize>,
+) -> Result<Vec<f64>, String> {
+    let min_segment = match min_segment {
+        Some(min_segment) => min_segment,
+        None => min_history_impl(method, &HashMap::new())?,
+    };
+
+    let start = detect_level_shift_impl(data, min_segment)?.unwrap_or(0);
+    forecast_with_method_impl(&data[start..], horizon, method)
+}
+
+/// Locate the most significant single shift in the mean level of a series.
+///
+/// Every split into a leading and a trailing segment of at least
+/// `min_segment` points is scored with the pooled two-sample t statistic of
+/// the difference of their means. The best split is reported if it remains
+/// significant at the 1% level after a Bonferroni correction for the number
+/// of splits tried, which keeps pure noise from producing spurious breaks.
+///
+/// # Arguments
+///
+/// * `data` - Series to scan
+/// * `min_segment` - Smallest number of points on either side of the break
+///
+/// # Returns
+///
+/// The index of the first point after the shift, or `None` if no break is
+/// significant
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * min_segment is 0
+/// * data has fewer than `max(2 * min_segment, 3)` points or a non-finite
+///   value
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.detect_level_shift([10.0, 10.2, 9.9, 10.1, 20.0, 20.1, 19.8, 20.2], 2)
+/// # 4
+/// ```
+#[pyfunction]
+pub fn detect_level_shift(data: Vec<f64>, min_segment: usize) -> PyResult<Option<usize>> {
+    detect_level_shift_impl(&data, min_segment).map_err(PyValueError::new_err)
+}
+
+/// Forecast from the history after the last structural break only.
+///
+/// Runs `detect_level_shift` and fits `method` on the data from the shift
+/// on, so the forecast is not pulled toward a level the series has left;
+/// without a significant shift the whole series is used.
+///
+/// # Arguments
+///
+/// * `data` - Historical data
+/// * `horizon` - Number of future values to predict
+/// * `method` - Forecasting method name (e.g. "ses", "linear_trend")
+/// * `min_segment` - Smallest segment on either side of a break (defaults
+///   to the method's minimum history)
+///
+/// # Errors
+///
+/// Returns PyValueError under the conditions of `detect_level_shift`, or if
+/// the method is unknown or fails.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.forecast_after_shift(sales, 6, "mean")
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, horizon, method, min_segment=None))]
+pub fn forecast_after_shift(
+    data: Vec<f64>,
+    horizon: usize,
+    method: String,
+    min_segment: Option<usize>,
+) -> PyResult<Vec<f64>> {
+    forecast_after_shift_impl(&data, horizon, &method, min_segment).map_err(PyValueError::new_err)
+}
+
+/// Streaming anomaly detector with a sliding baseline.
+///
+/// Keeps only the last `window` observations. Each pushed value is compared
+/// with the mean and sample standard deviation of that window before being
+/// added to it, and flagged when its z-score exceeds `threshold`. No value is
+/// flagged until the window has filled. With `robust=True` the median and
+/// MAD of the window are used instead, as in `detect_outliers_zscore`, so a
+/// burst of outliers does not inflate the scale and hide the ones after it.
+///
+/// Like `StreamingSes`, the state is guarded by a mutex so an instance can be
+/// shared between Python threads.
+#[pyclass]
+pub struct StreamingAnomalyDetector {
+    state: Mutex<AnomalyState>,
+}
+
+impl StreamingAnomalyDetector {
+    fn lock(&self) -> MutexGuard<'_, AnomalyState> {
+        // State is only mutated after validation, so it is consistent even
+        // if a previous holder panicked
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[pymethods]
+impl StreamingAnomalyDetector {
+    /// Create a detector over a baseline of `window` points (at least 2)
+    /// flagging z-scores above `threshold` (default 3), robust ones with the
+    /// median and MAD if `robust`.
+    #[new]
+    #[pyo3(signature = (window, threshold=3.0, robust=false))]
+    fn new(window: usize, threshold: f64, robust: bool) -> PyResult<Self> {
+        let state = AnomalyState::new(window, threshold, robust).map_err(PyValueError::new_err)?;
+        Ok(StreamingAnomalyDetector { state: Mutex::new(state) })
+    }
+
+    /// Whether `value` is anomalous relative to the current baseline; the
+    /// value then joins the baseline either way.
+    fn push(&self, value: f64) -> PyResult<bool> {
+        self.lock().push(value).map_err(PyValueError::new_err)
+    }
+
+    /// Baseline mean, or None before the first observation.
+    #[getter]
+    fn mean(&self) -> Option<f64> {
+        self.lock().mean()
+    }
+
+    /// Baseline sample standard deviation, or None with fewer than two points.
+    #[getter]
+    fn std(&self) -> Option<f64> {
+        self.lock().std()
+    }
+
+    /// Number of points in the baseline window.
+    #[getter]
+    fn window(&self) -> usize {
+        self.lock().window
+    }
+
+    /// Z-score above which a point is flagged.
+    #[getter]
+    fn threshold(&self) -> f64 {
+        self.lock().threshold
+    }
+
+    /// Whether points are scored against the median and MAD.
+    #[getter]
+    fn robust(&self) -> bool {
+        self.lock().robust
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_anomaly_state_flags_only_the_spike() {
+        let mut rng = Rng::new(17);
+        let mut state = AnomalyState::new(20, 4.0, false).unwrap();
+        let mut flags = Vec::new();
+        for t in 0..100 {
+            let value = if t == 70 { 25.0 } else { 10.0 + rng.normal() };
+            flags.push(state.push(value).unwrap());
+        }
+
+        let flagged: Vec<usize> = (0..flags.len()).filter(|&t| flags[t]).collect();
+        assert_eq!(flagged, vec![70]);
+    }
+
+    #[test]
+    fn test_anomaly_state_never_flags_before_window_fills() {
+        let mut state = AnomalyState::new(5, 1.0, false).unwrap();
+
+        for value in [1.0, 100.0, -50.0, 1e6, 3.0] {
+            assert!(!state.push(value).unwrap());
+        }
+        assert!(state.push(1e9).unwrap());
+    }
+
+    #[test]
+    fn test_anomaly_state_sliding_stats_match_window() {
+        let mut rng = Rng::new(4);
+        let values: Vec<f64> = (0..200).map(|_| 1e6 + rng.normal()).collect();
+        let mut state = AnomalyState::new(30, 3.0, false).unwrap();
+        for value in &values {
+            state.push(*value).unwrap();
+        }
+
+        let window = &values[170..];
+        let mean = window.iter().sum::<f64>() / 30.0;
+        assert!((state.mean().unwrap() - mean).abs() < 1e-6);
+        assert!((state.std().unwrap() - variance_impl(window).sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_anomaly_state_invalid_window() {
+        let result = AnomalyState::new(1, 3.0, true);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Window must be at least 2, got 1");
+    }
+
+    #[test]
+    fn test_anomaly_state_robust_flags_a_burst() {
+        let mut rng = Rng::new(23);
+        let values: Vec<f64> = (0..80)
+            .map(|t| if (50..54).contains(&t) { 30.0 } else { 10.0 + rng.normal() })
+            .collect();
+        let flagged = |robust: bool| {
+            let mut state = AnomalyState::new(30, 4.0, robust).unwrap();
+            let flags: Vec<bool> = values.iter().map(|&v| state.push(v).unwrap()).collect();
+            (0..flags.len()).filter(|&t| flags[t]).collect::<Vec<usize>>()
+        };
+
+        // Two spikes in the window inflate the standard deviation enough to
+        // hide the rest
+        assert_eq!(flagged(false), vec![50, 51]);
+        assert_eq!(flagged(true), vec![50, 51, 52, 53]);
+    }
+
+    #[test]
+    fn test_detect_outliers_zscore_robust_finds_masked_outliers() {
+        let mut rng = Rng::new(8);
+        let mut data: Vec<f64> = (0..40).map(|_| 10.0 + rng.normal()).collect();
+        let outliers = vec![5, 12, 22, 31, 37];
+        for &t in &outliers {
+            data[t] = 30.0;
+        }
+
+        // Five equal outliers inflate the standard deviation enough to hide
+        assert!(detect_outliers_zscore_impl(&data, 3.0, false).unwrap().is_empty());
+        assert_eq!(detect_outliers_zscore_impl(&data, 3.0, true).unwrap(), outliers);
+    }
+
+    #[test]
+    fn test_detect_outliers_zscore_invalid_threshold() {
+        let result = detect_outliers_zscore_impl(&[1.0, 2.0, 3.0], 0.0, true);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Threshold must be a finite value greater than 0, got 0"
+        );
+    }
+
+    #[test]
+    fn test_detect_level_shift_finds_obvious_break() {
+        let mut rng = Rng::new(6);
+        let data: Vec<f64> = (0..60)
+            .map(|t| if t < 35 { 10.0 } else { 25.0 } + rng.normal())
+            .collect();
+
+        assert_eq!(detect_level_shift_impl(&data, 5).unwrap(), Some(35));
+
+        // The mean of the full series sits between the two levels
+        let full = forecast_with_method_impl(&data, 3, "mean").unwrap();
+        let after = forecast_after_shift_impl(&data, 3, "mean", Some(5)).unwrap();
+        assert!((full[0] - 16.25).abs() < 1.0);
+        assert!((after[0] - 25.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_detect_level_shift_ignores_pure_noise() {
+        let mut rng = Rng::new(2);
+        let data: Vec<f64> = (0..80).map(|_| 10.0 + rng.normal()).collect();
+
+        assert_eq!(detect_level_shift_impl(&data, 5).unwrap(), None);
+        assert_eq!(
+            forecast_after_shift_impl(&data, 2, "mean", Some(5)).unwrap(),
+            forecast_with_method_impl(&data, 2, "mean").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_detect_level_shift_invalid_min_segment() {
+        let result = detect_level_shift_impl(&[1.0, 2.0, 3.0], 0);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Minimum segment length must be at least 1");
+    }
+
+    #[test]
+    fn test_detect_level_shift_huge_min_segment() {
+        let result = detect_level_shift_impl(&[1.0, 2.0, 3.0, 4.0, 5.0], 1usize << 63);
+
+        assert_eq!(
+            result.unwrap_err(),
+            "Level shift detection with min_segment 9223372036854775808 requires at least \
+             18446744073709551616 data points, got 5"
+        );
+    }
+}
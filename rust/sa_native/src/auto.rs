@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use rayon::prelude::*;
+
+use crate::backtest::{backtest_rmse_below_impl, rolling_origins};
+use crate::model::{forecast_impl, Forecast, ForecastOptions};
+use crate::smoothing::fit_ses_impl;
+use crate::stats::series_features_impl;
+
+/// Non-seasonal methods `auto_forecast` always considers.
+const CANDIDATES: &[&str] = &["static", "mean", "linear_trend", "ses", "holt"];
+
+/// Seasonal method considered when the seasonality gate passes.
+const SEASONAL_CANDIDATE: &str = "holt_winters";
+
+/// Candidates `auto_forecast` backtests, each a method and its parameters.
+type Candidates<'a> = [(&'a str, HashMap<String, f64>)];
+
+/// The candidate with the lowest backtest RMSE, first in order on ties.
+///
+/// Candidates run on the rayon thread pool with `parallel`, otherwise in
+/// order. Either way they share the best finite RMSE found so far, and a
+/// backtest is abandoned once its running error strictly exceeds it; such a
+/// candidate could only lose, so the winner does not depend on which
+/// candidates finished first.
+///
+/// Which losers fail, rather than being abandoned first, does depend on that
+/// timing, so failures are not reported when there is a winner. Without one
+/// no bound was ever set and every backtest ran in full, so the error lists
+/// each candidate's failure.
+fn select_candidate<'a>(
+    data: &[f64],
+    initial_train: usize,
+    horizon: usize,
+    candidates: &'a Candidates<'a>,
+    parallel: bool,
+) -> Result<(f64, &'a str, &'a HashMap<String, f64>), String> {
+    // Non-negative floats order like their bit patterns, so the bound can
+    // be lowered with an integer `fetch_min`
+    let bound = AtomicU64::new(f64::INFINITY.to_bits());
+    let backtest = |(method, params): &(&str, HashMap<String, f64>)| {
+        let current = f64::from_bits(bound.load(Ordering::Relaxed));
+        let outcome =
+            backtest_rmse_below_impl(data, initial_train, horizon, 1, method, params, current);
+        if let Ok(Some(rmse)) = outcome {
+            if rmse.is_finite() {
+                bound.fetch_min(rmse.to_bits(), Ordering::Relaxed);
+            }
+        }
+        outcome
+    };
+
+    let outcomes: Vec<Result<Option<f64>, String>> = if parallel {
+        candidates.par_iter().map(backtest).collect()
+    } else {
+        candidates.iter().map(backtest).collect()
+    };
+
+    let mut best: Option<(f64, &str, &HashMap<String, f64>)> = None;
+    let mut failures = Vec::new();
+    for ((method, params), outcome) in candidates.iter().zip(outcomes) {
+        match outcome {
+            Ok(Some(rmse)) if rmse.is_finite() => {
+                let improves = match best {
+                    Some((best_rmse, _, _)) => rmse < best_rmse,
+                    None => true,
+                };
+                if improves {
+                    best = Some((rmse, method, params));
+                }
+            }
+            Ok(Some(_)) => failures.push(format!("{}: backtest RMSE is not finite", method)),
+            Ok(None) => {}
+            Err(e) => failures.push(format!("{}: {}", method, e)),
+        }
+    }
+
+    best.ok_or_else(|| format!("Every candidate failed to backtest: {}", failures.join("; ")))
+}
+
+/// Core automatic model selection logic.
+///
+/// Every candidate is backtested on the same rolling origins (one-step
+/// increments from half of the data, or from two full seasons when the
+/// seasonal candidate is in play) and the lowest RMSE wins. The seasonal
+/// candidate only enters when the seasonal strength from
+/// `series_features_impl` reaches `seasonal_threshold`, so short noisy
+/// series are not steered into a seasonal model by backtest noise.
+/// Candidates are backtested in parallel when the rayon pool has more than
+/// one thread, and those that fall behind the best RMSE so far are
+/// abandoned mid-backtest either way.
+pub(crate) fn auto_forecast_impl(
+    data: &[f64],
+    horizon: usize,
+    season_length: Option<usize>,
+    seasonal_threshold: f64,
+) -> Result<Forecast, String> {
+    if !(0.0..=1.0).contains(&seasonal_threshold) {
+        return Err(format!(
+            "Seasonal threshold must be in [0, 1], got {}",
+            seasonal_threshold
+        ));
+    }
+
+    let n = data.len();
+    let mut initial_train = (n / 2).max(2);
+    rolling_origins(n, initial_train, horizon, 1)?;
+
+    let mut warnings = Vec::new();
+    let mut candidates: Vec<(&str, HashMap<String, f64>)> = CANDIDATES
+        .iter()
+        .map(|&method| (method, HashMap::new()))
+        .collect();
+
+    if let Some(m) = season_length {
+        match series_features_impl(data, m) {
+            Ok((_, strength)) if strength < seasonal_threshold => warnings.push(format!(
+                "skipped {}: seasonal strength {:.3} is below the threshold {}",
+                SEASONAL_CANDIDATE, strength, seasonal_threshold
+            )),
+            Ok(_) if initial_train.max(2 * m) + horizon > n => warnings.push(format!(
+                "skipped {}: not enough data to backtest with two full seasons of history",
+                SEASONAL_CANDIDATE
+            )),
+            Ok(_) => {
+                initial_train = initial_train.max(2 * m);
+                let params = HashMap::from([("season_length".to_string(), m as f64)]);
+                candidates.push((SEASONAL_CANDIDATE, params));
+            }
+            Err(e) => warnings.push(format!("skipped {}: {}", SEASONAL_CANDIDATE, e)),
+        }
+    }
+
+    // A single thread gains nothing from the pool
+    let parallel = rayon::current_num_threads() > 1;
+    let (_, method, params) =
+        select_candidate(data, initial_train, horizon, &candidates, parallel)?;
+
+    // The winner is forecast like any other `forecast` call, so a non-finite
+    // forecast still falls back to static, after the selection warnings
+    let mut result = forecast_impl(data, horizon, method, params, &ForecastOptions::default())?;
+    warnings.append(&mut result.warnings);
+    result.warnings = warnings;
+    Ok(result)
+}
+
+/// Core auto-fitted SES logic: `forecast` with "ses" and the alpha chosen by
+/// `fit_ses_impl`.
+pub(crate) fn predict_ses_auto_impl(data: &[f64], horizon: usize) -> Result<Forecast, String> {
+    let alpha = fit_ses_impl(data)?;
+    let params = HashMap::from([("alpha".to_string(), alpha)]);
+    forecast_impl(data, horizon, "ses", &params, &ForecastOptions::default())
+}
+
+/// Forecast with the method that backtests best on the series.
+///
+/// Candidates are `static`, `mean`, `linear_trend`, `ses` and `holt`, all
+/// with default parameters, scored by RMSE over a rolling-origin backtest.
+/// When `season_length` is given, additive `holt_winters` joins them only if
+/// the seasonal strength from `series_features` is at least
+/// `seasonal_threshold`; structure, not just the noisy backtest, decides
+/// whether a seasonal model is plausible. Candidates skipped before the
+/// backtest are explained in `Forecast.warnings`, and `Forecast.method` names the winner. The
+/// winner is forecast as by `forecast` with its parameters, including the
+/// fallback to `static` if its forecast is not finite.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Number of future values to predict
+/// * `season_length` - Optional season length enabling the seasonal candidate
+/// * `seasonal_threshold` - Minimum seasonal strength in [0, 1] (default 0.5)
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * horizon is 0 or the data is too short to hold out `horizon` points
+/// * seasonal_threshold is outside [0, 1]
+/// * every candidate fails to backtest; the message gives each reason
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// result = sa_native.auto_forecast(sales, 12, season_length=12)
+/// print(result.method, result.values, result.warnings)
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, horizon, season_length=None, seasonal_threshold=0.5))]
+pub fn auto_forecast(
+    data: Vec<f64>,
+    horizon: usize,
+    season_length: Option<usize>,
+    seasonal_threshold: f64,
+) -> PyResult<Forecast> {
+    auto_forecast_impl(&data, horizon, season_length, seasonal_threshold)
+        .map_err(PyValueError::new_err)
+}
+
+/// Forecast with simple exponential smoothing, fitting alpha first.
+///
+/// Alpha is chosen as in `fit_ses`, by minimizing the in-sample one-step
+/// SSE, and the result is the same `Forecast` as `forecast(data, horizon,
+/// "ses", {"alpha": alpha})`. The fitted alpha is recorded in
+/// `Forecast.params`, so the forecast can be reproduced later.
+///
+/// # Errors
+///
+/// Returns PyValueError if data has fewer than two points or horizon is 0.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// result = sa_native.predict_ses_auto(sales, 6)
+/// print(result.values, result.params["alpha"])
+/// ```
+#[pyfunction]
+pub fn predict_ses_auto(data: Vec<f64>, horizon: usize) -> PyResult<Forecast> {
+    predict_ses_auto_impl(&data, horizon).map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    fn seasonal_series(amplitude: f64, noise: f64, seed: u64) -> Vec<f64> {
+        let pattern = [1.0, -0.5, 0.8, -1.3];
+        let mut rng = Rng::new(seed);
+        (0..48)
+            .map(|t| 30.0 + 0.2 * t as f64 + amplitude * pattern[t % 4] + noise * rng.normal())
+            .collect()
+    }
+
+    #[test]
+    fn test_auto_forecast_gate_skips_weak_seasonality() {
+        let data = seasonal_series(0.05, 1.0, 1);
+        let result = auto_forecast_impl(&data, 4, Some(4), 0.5).unwrap();
+
+        assert_ne!(result.method, "holt_winters");
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].starts_with("skipped holt_winters: seasonal strength"));
+    }
+
+    #[test]
+    fn test_auto_forecast_strong_seasonality_selects_holt_winters() {
+        let data = seasonal_series(8.0, 0.3, 2);
+        let result = auto_forecast_impl(&data, 4, Some(4), 0.5).unwrap();
+
+        assert_eq!(result.method, "holt_winters");
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.values.len(), 4);
+    }
+
+    #[test]
+    fn test_auto_forecast_matches_forecast_of_winner() {
+        let data = seasonal_series(8.0, 0.3, 2);
+        let result = auto_forecast_impl(&data, 4, Some(4), 0.5).unwrap();
+        let params = HashMap::from([("season_length".to_string(), 4.0)]);
+        let direct =
+            forecast_impl(&data, 4, "holt_winters", &params, &ForecastOptions::default()).unwrap();
+
+        assert_eq!(result.values, direct.values);
+        assert_eq!(result.params, direct.params);
+        assert_eq!(result.fitted.len(), direct.fitted.len());
+        assert_eq!(result.params["alpha"], 0.3);
+    }
+
+    #[test]
+    fn test_auto_forecast_without_season_picks_trend() {
+        let data: Vec<f64> = (0..30).map(|t| 2.0 * t as f64 + 1.0).collect();
+        let result = auto_forecast_impl(&data, 3, None, 0.5).unwrap();
+
+        assert_eq!(result.method, "linear_trend");
+        assert_eq!(result.values, vec![61.0, 63.0, 65.0]);
+    }
+
+    #[test]
+    fn test_auto_forecast_invalid_threshold() {
+        let result = auto_forecast_impl(&[1.0; 10], 2, Some(2), 1.5);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Seasonal threshold must be in [0, 1], got 1.5");
+    }
+
+    #[test]
+    fn test_select_candidate_early_stop_matches_exhaustive() {
+        let mut rng = Rng::new(9);
+        let data: Vec<f64> = (0..120).map(|t| 5.0 + 0.7 * t as f64 + rng.normal()).collect();
+        // A seasonal model with a long season is slow to fit and clearly worse
+        // here; it comes after the winner so early stopping can abandon it
+        let candidates: Vec<(&str, HashMap<String, f64>)> = vec![
+            ("mean", HashMap::new()),
+            ("linear_trend", HashMap::new()),
+            ("holt_winters", HashMap::from([("season_length".to_string(), 30.0)])),
+            ("static", HashMap::new()),
+            ("holt", HashMap::new()),
+        ];
+
+        let sequential = select_candidate(&data, 60, 3, &candidates, false).unwrap();
+        let parallel = select_candidate(&data, 60, 3, &candidates, true).unwrap();
+
+        assert_eq!(sequential, parallel);
+        assert_eq!(sequential.1, "linear_trend");
+
+        let rmse = sequential.0;
+        let (method, params) = &candidates[2];
+        let abandoned = backtest_rmse_below_impl(&data, 60, 3, 1, method, params, rmse);
+        assert_eq!(abandoned.unwrap(), None);
+    }
+
+    #[test]
+    fn test_auto_forecast_parallel_matches_sequential() {
+        let series = [
+            seasonal_series(8.0, 0.3, 2),
+            seasonal_series(0.05, 1.0, 1),
+            (0..40).map(|t| 100.0 - 1.5 * t as f64).collect(),
+        ];
+        let run = |threads: usize, data: &[f64]| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| auto_forecast_impl(data, 4, Some(4), 0.5).unwrap())
+        };
+
+        for data in &series {
+            let sequential = run(1, data);
+            for threads in [2, 4] {
+                let parallel = run(threads, data);
+                assert_eq!(parallel.method, sequential.method);
+                assert_eq!(parallel.values, sequential.values);
+                assert_eq!(parallel.warnings, sequential.warnings);
+                // Bitwise, as the fitted values start with NaN
+                let bits = |values: &[f64]| values.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+                assert_eq!(bits(&parallel.fitted), bits(&sequential.fitted));
+            }
+        }
+    }
+
+    #[test]
+    fn test_select_candidate_reports_every_failure() {
+        let data: Vec<f64> = (0..20).map(|t| t as f64).collect();
+        let candidates: Vec<(&str, HashMap<String, f64>)> = vec![
+            ("no_such_method", HashMap::new()),
+            ("holt_winters", HashMap::from([("season_length".to_string(), 0.0)])),
+        ];
+
+        for parallel in [false, true] {
+            let err = select_candidate(&data, 10, 2, &candidates, parallel).unwrap_err();
+            assert!(err.starts_with("Every candidate failed to backtest: no_such_method: "));
+            assert!(err.contains("; holt_winters: "));
+        }
+    }
+
+    #[test]
+    fn test_predict_ses_auto_records_fitted_alpha() {
+        let mut rng = Rng::new(29);
+        let data: Vec<f64> = (0..50).map(|t| 30.0 + 0.1 * t as f64 + 2.0 * rng.normal()).collect();
+        let result = predict_ses_auto_impl(&data, 3).unwrap();
+
+        let alpha = fit_ses_impl(&data).unwrap();
+        assert_eq!(result.method, "ses");
+        assert_eq!(result.params, HashMap::from([("alpha".to_string(), alpha)]));
+        let expected = crate::smoothing::predict_ses_impl(&data, 3, alpha).unwrap();
+        assert_eq!(result.values, expected);
+    }
+
+    #[test]
+    fn test_auto_forecast_reports_effective_params() {
+        let data: Vec<f64> = (0..20).map(|t| 2.0 * t as f64 + 1.0).collect();
+        let result = auto_forecast_impl(&data, 3, None, 0.5).unwrap();
+
+        assert_eq!(result.method, "linear_trend");
+        assert!(result.params.is_empty());
+    }
+}
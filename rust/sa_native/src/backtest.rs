@@ -0,0 +1,1172 @@
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyUserWarning, PyValueError};
+
+use crate::intervals::predict_mean_intervals_impl;
+use crate::methods::{
+    check_method_params, fit_method_impl, forecast_with_method_impl, min_history_impl,
+};
+use crate::stats::{quantile_sorted, validate_confidence, variance_impl};
+
+/// Forecast origins of a rolling-origin backtest.
+///
+/// The first origin trains on `initial_train` points and each following origin
+/// moves forward by `step`, as long as `horizon` held-out actuals remain.
+pub(crate) fn rolling_origins(
+    n: usize,
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+) -> Result<Vec<usize>, String> {
+    if initial_train == 0 {
+        return Err("Initial training size must be greater than 0".to_string());
+    }
+
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    if step == 0 {
+        return Err("Step must be greater than 0".to_string());
+    }
+
+    if initial_train.saturating_add(horizon) > n {
+        return Err(format!(
+            "Not enough data for a backtest: initial_train ({}) + horizon ({}) exceeds data length ({})",
+            initial_train, horizon, n
+        ));
+    }
+
+    Ok((initial_train..=n - horizon).step_by(step).collect())
+}
+
+/// Core rolling forecast logic: the forecast produced at each backtest origin.
+pub(crate) fn rolling_forecasts_impl(
+    data: &[f64],
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    method: &str,
+) -> Result<Vec<Vec<f64>>, String> {
+    rolling_origins(data.len(), initial_train, horizon, step)?
+        .into_iter()
+        .map(|origin| forecast_with_method_impl(&data[..origin], horizon, method))
+        .collect()
+}
+
+/// Core backtest logic: RMSE of all rolling forecasts against the held-out actuals.
+pub(crate) fn backtest_rmse_impl(
+    data: &[f64],
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    method: &str,
+) -> Result<f64, String> {
+    backtest_rmse_with_params_impl(data, initial_train, horizon, step, method, &HashMap::new(), 1.0)
+}
+
+/// Backtest RMSE of `method` fitted with `params` at every origin.
+///
+/// Each fold's mean squared error is weighted by `fold_decay^k`, with `k`
+/// the number of folds after it, so the most recent origin has weight 1.
+/// With `fold_decay = 1` every forecast step counts equally.
+pub(crate) fn backtest_rmse_with_params_impl(
+    data: &[f64],
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    method: &str,
+    params: &HashMap<String, f64>,
+    fold_decay: f64,
+) -> Result<f64, String> {
+    if !(fold_decay > 0.0 && fold_decay <= 1.0) {
+        return Err(format!("Fold decay must be in (0, 1], got {}", fold_decay));
+    }
+
+    let origins = rolling_origins(data.len(), initial_train, horizon, step)?;
+    let forecasts = origins
+        .iter()
+        .map(|&origin| fit_method_impl(&data[..origin], method, params)?.forecast(horizon))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut weighted_mse = 0.0;
+    let mut total_weight = 0.0;
+    let mut weight = 1.0;
+    for (origin, forecast) in origins.iter().zip(&forecasts).rev() {
+        let actuals = &data[*origin..origin + horizon];
+        let sum_sq = actuals
+            .iter()
+            .zip(forecast)
+            .map(|(a, f)| (a - f).powi(2))
+            .sum::<f64>();
+        weighted_mse += weight * sum_sq / horizon as f64;
+        total_weight += weight;
+        weight *= fold_decay;
+    }
+
+    Ok((weighted_mse / total_weight).sqrt())
+}
+
+/// Backtest RMSE of `method` fitted with `params`, abandoned once it cannot
+/// come in under `bound`.
+///
+/// Squared errors only accumulate, so as soon as the running total alone
+/// gives an RMSE above `bound`, the final RMSE would too and `None` is
+/// returned without forecasting the remaining origins. An infinite bound
+/// evaluates every origin.
+pub(crate) fn backtest_rmse_below_impl(
+    data: &[f64],
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    method: &str,
+    params: &HashMap<String, f64>,
+    bound: f64,
+) -> Result<Option<f64>, String> {
+    let origins = rolling_origins(data.len(), initial_train, horizon, step)?;
+    let count = (origins.len() * horizon) as f64;
+
+    let mut sum_sq = 0.0;
+    for &origin in &origins {
+        let forecast = fit_method_impl(&data[..origin], method, params)?.forecast(horizon)?;
+        sum_sq += data[origin..origin + horizon]
+            .iter()
+            .zip(&forecast)
+            .map(|(a, f)| (a - f).powi(2))
+            .sum::<f64>();
+
+        if (sum_sq / count).sqrt() > bound {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some((sum_sq / count).sqrt()))
+}
+
+/// Core lead-time error logic: RMSE across origins separately for each
+/// forecast step, entry `h` covering lead time `h + 1`.
+pub(crate) fn backtest_per_step_error_impl(
+    data: &[f64],
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    method: &str,
+) -> Result<Vec<f64>, String> {
+    let origins = rolling_origins(data.len(), initial_train, horizon, step)?;
+
+    let mut sum_sq = vec![0.0; horizon];
+    for &origin in &origins {
+        let forecast = forecast_with_method_impl(&data[..origin], horizon, method)?;
+        let actuals = &data[origin..origin + horizon];
+        for (total, (a, f)) in sum_sq.iter_mut().zip(actuals.iter().zip(&forecast)) {
+            *total += (a - f).powi(2);
+        }
+    }
+
+    let folds = origins.len() as f64;
+    Ok(sum_sq.into_iter().map(|total| (total / folds).sqrt()).collect())
+}
+
+/// Core forecast history logic: the `lead`-step forecast of each point
+/// reachable from the shortest usable history, entry `i` forecasting
+/// `data[min_history + lead - 1 + i]`.
+pub(crate) fn forecast_history_impl(
+    data: &[f64],
+    lead: usize,
+    method: &str,
+) -> Result<Vec<f64>, String> {
+    forecast_history_with_params_impl(data, lead, method, &HashMap::new())
+}
+
+/// Forecast history of `method` fitted with `params` on every window.
+pub(crate) fn forecast_history_with_params_impl(
+    data: &[f64],
+    lead: usize,
+    method: &str,
+    params: &HashMap<String, f64>,
+) -> Result<Vec<f64>, String> {
+    if lead == 0 {
+        return Err("Lead must be greater than 0".to_string());
+    }
+
+    let min_history = min_history_impl(method, params)?;
+    if min_history.saturating_add(lead) > data.len() {
+        return Err(format!(
+            "Not enough data for a forecast history: min_history ({}) + lead ({}) exceeds data length ({})",
+            min_history, lead, data.len()
+        ));
+    }
+
+    // The forecast at `origin` targets `data[origin + lead - 1]`
+    rolling_origins(data.len(), min_history, lead, 1)?
+        .into_iter()
+        .map(|origin| {
+            let forecast = fit_method_impl(&data[..origin], method, params)?.forecast(lead)?;
+            Ok(forecast[lead - 1])
+        })
+        .collect()
+}
+
+/// Core innovation scale logic: the sample standard deviation of the
+/// one-step residuals `data[t] - forecast_history(data, 1)[t]`.
+pub(crate) fn innovation_std_impl(
+    data: &[f64],
+    method: &str,
+    params: &HashMap<String, f64>,
+) -> Result<f64, String> {
+    let history = forecast_history_with_params_impl(data, 1, method, params)?;
+    if history.len() < 2 {
+        return Err(format!(
+            "Innovation std requires at least two one-step residuals, got {}",
+            history.len()
+        ));
+    }
+
+    let targets = &data[data.len() - history.len()..];
+    let residuals: Vec<f64> = targets.iter().zip(&history).map(|(y, f)| y - f).collect();
+    Ok(variance_impl(&residuals).sqrt())
+}
+
+/// Core backtest interval logic: `(lower, upper)` bands around the forecast
+/// on the full data, offset by empirical quantiles of the backtest errors
+/// `actual - forecast` at each lead time.
+pub(crate) fn backtest_intervals_impl(
+    data: &[f64],
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    method: &str,
+    confidence: f64,
+) -> Result<(Vec<f64>, Vec<f64>), String> {
+    validate_confidence(confidence)?;
+
+    let origins = rolling_origins(data.len(), initial_train, horizon, step)?;
+    if origins.len() < 2 {
+        return Err(format!(
+            "Backtest intervals require at least two backtest origins, got {}",
+            origins.len()
+        ));
+    }
+
+    let mut errors = vec![Vec::with_capacity(origins.len()); horizon];
+    for &origin in &origins {
+        let forecast = forecast_with_method_impl(&data[..origin], horizon, method)?;
+        let actuals = &data[origin..origin + horizon];
+        for (lead_errors, (a, f)) in errors.iter_mut().zip(actuals.iter().zip(&forecast)) {
+            lead_errors.push(a - f);
+        }
+    }
+
+    let point = forecast_with_method_impl(data, horizon, method)?;
+    let tail = (1.0 - confidence) / 2.0;
+    let mut lower = Vec::with_capacity(horizon);
+    let mut upper = Vec::with_capacity(horizon);
+    for (mut lead_errors, p) in errors.into_iter().zip(&point) {
+        lead_errors.sort_by(f64::total_cmp);
+        lower.push(p + quantile_sorted(&lead_errors, tail));
+        upper.push(p + quantile_sorted(&lead_errors, 1.0 - tail));
+    }
+
+    Ok((lower, upper))
+}
+
+/// Core interval coverage logic: the fraction of held-out actuals inside the
+/// prediction intervals produced at each rolling origin.
+pub(crate) fn backtest_coverage_impl(
+    data: &[f64],
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    confidence: f64,
+) -> Result<f64, String> {
+    let origins = rolling_origins(data.len(), initial_train, horizon, step)?;
+
+    let mut covered = 0usize;
+    for &origin in &origins {
+        let (_, lower, upper) = predict_mean_intervals_impl(&data[..origin], horizon, confidence)?;
+        let actuals = &data[origin..origin + horizon];
+        covered += (0..horizon)
+            .filter(|&h| lower[h] <= actuals[h] && actuals[h] <= upper[h])
+            .count();
+    }
+
+    Ok(covered as f64 / (origins.len() * horizon) as f64)
+}
+
+/// Core ensemble weighting logic: inverse-RMSE weights from a rolling-origin
+/// backtest of each method, normalized to sum to 1.
+///
+/// The first origin trains on half of the data and origins advance one step
+/// at a time. Methods that fail on some training window get zero weight and a
+/// warning instead of aborting; methods with a perfect backtest (zero RMSE)
+/// share all of the weight.
+///
+/// Returns `(weights, warnings)` with weights aligned to `methods`.
+pub(crate) fn fit_ensemble_weights_impl(
+    data: &[f64],
+    horizon: usize,
+    methods: &[String],
+) -> Result<(Vec<f64>, Vec<String>), String> {
+    if methods.is_empty() {
+        return Err("At least one method is required".to_string());
+    }
+
+    for method in methods {
+        check_method_params(method, &HashMap::new())?;
+    }
+
+    let n = data.len();
+    let initial_train = (n / 2).min(n.saturating_sub(horizon)).max(1);
+    rolling_origins(n, initial_train, horizon, 1)?;
+
+    let mut warnings = Vec::new();
+    let rmses: Vec<Option<f64>> = methods
+        .iter()
+        .map(|method| match backtest_rmse_impl(data, initial_train, horizon, 1, method) {
+            Ok(rmse) if rmse.is_finite() => Some(rmse),
+            Ok(_) => {
+                warnings.push(format!(
+                    "Method '{}' got zero weight: backtest RMSE is not finite",
+                    method
+                ));
+                None
+            }
+            Err(e) => {
+                warnings.push(format!("Method '{}' got zero weight: {}", method, e));
+                None
+            }
+        })
+        .collect();
+
+    let scores: Vec<f64> = if rmses.contains(&Some(0.0)) {
+        rmses.iter().map(|r| if *r == Some(0.0) { 1.0 } else { 0.0 }).collect()
+    } else {
+        rmses.iter().map(|r| r.map_or(0.0, |rmse| 1.0 / rmse)).collect()
+    };
+
+    let total: f64 = scores.iter().sum();
+    if total == 0.0 {
+        return Err("Every method failed to backtest".to_string());
+    }
+
+    Ok((scores.iter().map(|s| s / total).collect(), warnings))
+}
+
+/// Core forecast value add logic: the percentage by which `method` lowers
+/// the backtest RMSE of the naive ("static") forecast over the same origins.
+pub(crate) fn forecast_value_add_impl(
+    data: &[f64],
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    method: &str,
+) -> Result<f64, String> {
+    let method_rmse = backtest_rmse_impl(data, initial_train, horizon, step, method)?;
+    let naive_rmse = backtest_rmse_impl(data, initial_train, horizon, step, "static")?;
+    if naive_rmse == 0.0 {
+        return Err(
+            "Naive backtest RMSE is zero, so forecast value add is undefined".to_string(),
+        );
+    }
+
+    Ok(100.0 * (naive_rmse - method_rmse) / naive_rmse)
+}
+
+/// Core holdout evaluation logic: `method` fitted with `params` on all but
+/// the last `horizon` points and scored against them.
+///
+/// MAPE and sMAPE are in percent. MAPE is NaN when a held-out actual is zero;
+/// an sMAPE term whose actual and forecast are both zero counts as zero.
+pub(crate) fn evaluate_impl(
+    data: &[f64],
+    horizon: usize,
+    method: &str,
+    params: &HashMap<String, f64>,
+) -> Result<HashMap<String, f64>, String> {
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    if data.len() <= horizon {
+        return Err(format!(
+            "Evaluation requires more data points than the horizon ({}), got {}",
+            horizon,
+            data.len()
+        ));
+    }
+
+    let split = data.len() - horizon;
+    let forecast = fit_method_impl(&data[..split], method, params)?.forecast(horizon)?;
+
+    let mut abs_sum = 0.0;
+    let mut sq_sum = 0.0;
+    let mut pct_sum = 0.0;
+    let mut sym_sum = 0.0;
+    for (actual, predicted) in data[split..].iter().zip(&forecast) {
+        let error = (predicted - actual).abs();
+        abs_sum += error;
+        sq_sum += error * error;
+        pct_sum += if *actual == 0.0 { f64::NAN } else { error / actual.abs() };
+        let scale = actual.abs() + predicted.abs();
+        if scale > 0.0 {
+            sym_sum += 2.0 * error / scale;
+        }
+    }
+
+    let n = horizon as f64;
+    Ok(HashMap::from([
+        ("mae".to_string(), abs_sum / n),
+        ("rmse".to_string(), (sq_sum / n).sqrt()),
+        ("mape".to_string(), 100.0 * pct_sum / n),
+        ("smape".to_string(), 100.0 * sym_sum / n),
+    ]))
+}
+
+/// Forecasts produced at each origin of a rolling-origin backtest.
+///
+/// The model is trained on `data[..origin]` for `origin = initial_train,
+/// initial_train + step, ...` while `horizon` actuals remain after the origin,
+/// so the returned forecasts can be scored with any metric.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `initial_train` - Number of observations in the first training window
+/// * `horizon` - Number of values forecast at each origin
+/// * `step` - Distance between consecutive origins
+/// * `method` - Forecasting method name (e.g. "static", "linear_trend")
+///
+/// # Returns
+///
+/// One forecast vector of length `horizon` per origin, in origin order
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * initial_train, horizon or step is 0
+/// * initial_train + horizon exceeds the data length
+/// * the method is unknown or fails on a training window
+#[pyfunction]
+pub fn rolling_forecasts(
+    data: Vec<f64>,
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    method: String,
+) -> PyResult<Vec<Vec<f64>>> {
+    rolling_forecasts_impl(&data, initial_train, horizon, step, &method)
+        .map_err(PyValueError::new_err)
+}
+
+/// Rolling-origin backtest of `predict_static`, returning the RMSE over all
+/// forecast steps of all origins.
+///
+/// With `fold_decay < 1`, recent folds count more: the mean squared error of
+/// each fold is weighted by `fold_decay^k`, where `k` is the number of later
+/// folds, before taking the root. The default of 1 weights every forecast
+/// step equally.
+///
+/// # Errors
+///
+/// Returns PyValueError under the same feasibility conditions as
+/// `rolling_forecasts`, or if fold_decay is outside (0, 1].
+#[pyfunction]
+#[pyo3(signature = (data, initial_train, horizon, step, fold_decay=1.0))]
+pub fn backtest_static(
+    data: Vec<f64>,
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    fold_decay: f64,
+) -> PyResult<f64> {
+    backtest_rmse_with_params_impl(
+        &data,
+        initial_train,
+        horizon,
+        step,
+        "static",
+        &HashMap::new(),
+        fold_decay,
+    )
+    .map_err(PyValueError::new_err)
+}
+
+/// Backtest RMSE broken down by lead time.
+///
+/// Uses the same rolling origins as `rolling_forecasts`. Entry `h` is the
+/// RMSE of the step `h + 1` forecasts across all origins, which shows how
+/// quickly a method degrades at longer horizons.
+///
+/// # Returns
+///
+/// One RMSE per forecast step, of length `horizon`
+///
+/// # Errors
+///
+/// Returns PyValueError under the same conditions as `rolling_forecasts`.
+#[pyfunction]
+pub fn backtest_per_step_error(
+    data: Vec<f64>,
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    method: String,
+) -> PyResult<Vec<f64>> {
+    backtest_per_step_error_impl(&data, initial_train, horizon, step, &method)
+        .map_err(PyValueError::new_err)
+}
+
+/// What `method` would have forecast for each past point from `lead` steps
+/// earlier: the diagonal of the rolling forecast matrix.
+///
+/// The forecast of `data[t]` is made from `data[..t - lead + 1]`, so with
+/// the last observation at `t - lead`. Points are covered from the first one
+/// whose training window holds the method's minimum history, at index
+/// `t0 = min_history + lead - 1`, to the end. With `lead = 1` the history is
+/// the one-step fitted values of the method.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `lead` - Lead time of the forecasts, in steps
+/// * `method` - Forecasting method name (e.g. "ses", "linear_trend")
+///
+/// # Returns
+///
+/// Forecasts of `data[t0..]`, in order
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * lead is 0
+/// * the data holds fewer than `min_history + lead` points
+/// * the method is unknown or fails on a training window
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// history = sa_native.forecast_history(sales, 3, "ses")
+/// # history[i] was forecast 3 steps before sales[i + 3]
+/// ```
+#[pyfunction]
+pub fn forecast_history(data: Vec<f64>, lead: usize, method: String) -> PyResult<Vec<f64>> {
+    forecast_history_impl(&data, lead, &method).map_err(PyValueError::new_err)
+}
+
+/// Estimate the noise level of a series as the spread of one-step errors.
+///
+/// `method` is refitted with `params` on every prefix of the data that is
+/// long enough for it, as in `forecast_history` with `lead = 1`, and the
+/// result is the sample standard deviation of the one-step-ahead residuals.
+/// This approximates the innovation scale that prediction intervals are
+/// built on: near zero for a series the method tracks exactly, and close to
+/// the noise standard deviation when the method captures the signal.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `method` - Forecasting method name (e.g. "ses", "linear_trend")
+/// * `params` - Optional method parameters by name
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * the method or one of the parameter names is unknown
+/// * fewer than two one-step residuals are available
+/// * the method fails on a training window
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.innovation_std(sales, "ses", {"alpha": 0.4})
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, method, params=None))]
+pub fn innovation_std(
+    data: Vec<f64>,
+    method: String,
+    params: Option<HashMap<String, f64>>,
+) -> PyResult<f64> {
+    innovation_std_impl(&data, &method, &params.unwrap_or_default())
+        .map_err(PyValueError::new_err)
+}
+
+/// Empirical coverage of prediction intervals over a rolling-origin backtest.
+///
+/// At each origin, `predict_mean_intervals` is fitted on the history and the
+/// held-out actuals that fall inside its bands are counted. For a
+/// well-calibrated model the result is close to `confidence`.
+///
+/// # Returns
+///
+/// Fraction in [0, 1] of all held-out points covered by their band
+///
+/// # Errors
+///
+/// Returns PyValueError under the same feasibility conditions as
+/// `rolling_forecasts`, if initial_train is less than 2, or if confidence is
+/// outside (0, 1).
+#[pyfunction]
+pub fn backtest_coverage(
+    data: Vec<f64>,
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    confidence: f64,
+) -> PyResult<f64> {
+    backtest_coverage_impl(&data, initial_train, horizon, step, confidence)
+        .map_err(PyValueError::new_err)
+}
+
+/// Prediction intervals calibrated on out-of-sample backtest errors.
+///
+/// Runs the rolling-origin backtest of `rolling_forecasts` and collects the
+/// errors `actual - forecast` separately for each lead time. The bands at
+/// step `h` are the forecast of `method` on the full data plus the
+/// `(1 - confidence) / 2` and `(1 + confidence) / 2` empirical quantiles of
+/// the lead-`h` errors, so they are asymmetric when the errors are skewed
+/// and widen as fast as the method actually degrades with the horizon.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `initial_train` - Training size at the first origin
+/// * `horizon` - Number of steps to forecast
+/// * `step` - Distance between consecutive origins
+/// * `method` - Forecasting method name (e.g. "ses", "linear_trend")
+/// * `confidence` - Nominal coverage of the bands, in `(0, 1)`
+///
+/// # Returns
+///
+/// Tuple of `(lower, upper)` bands, each of length `horizon`
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * confidence is not in `(0, 1)`
+/// * the backtest is infeasible as in `rolling_forecasts`, or has fewer than
+///   two origins
+/// * the method is unknown or fails on a training window
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// lower, upper = sa_native.backtest_intervals(sales, 24, 6, 1, "ses", 0.9)
+/// ```
+#[pyfunction]
+pub fn backtest_intervals(
+    data: Vec<f64>,
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    method: String,
+    confidence: f64,
+) -> PyResult<(Vec<f64>, Vec<f64>)> {
+    backtest_intervals_impl(&data, initial_train, horizon, step, &method, confidence)
+        .map_err(PyValueError::new_err)
+}
+
+/// Ensemble weights derived from backtest accuracy.
+///
+/// Each method is backtested from an origin at half of the data with steps
+/// of 1, and receives a weight proportional to the inverse of its RMSE. The
+/// weights sum to 1 and are aligned with `methods`. A method that fails on
+/// some training window (e.g. `exponential_trend` on non-positive data) gets
+/// zero weight and a `UserWarning` is emitted instead of raising.
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * methods is empty or contains an unknown method
+/// * horizon is 0 or the data is too short to hold out `horizon` points
+/// * every method fails to backtest
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// data = [float(t) for t in range(20)]
+/// sa_native.fit_ensemble_weights(data, 2, ["static", "linear_trend"])
+/// # [0.0, 1.0]: the trend backtests perfectly on a straight line
+/// ```
+#[pyfunction]
+pub fn fit_ensemble_weights(
+    py: Python<'_>,
+    data: Vec<f64>,
+    horizon: usize,
+    methods: Vec<String>,
+) -> PyResult<Vec<f64>> {
+    let (weights, warnings) =
+        fit_ensemble_weights_impl(&data, horizon, &methods).map_err(PyValueError::new_err)?;
+    for warning in &warnings {
+        PyErr::warn(py, py.get_type::<PyUserWarning>(), warning, 1)?;
+    }
+
+    Ok(weights)
+}
+
+/// Forecast value add (FVA) of a method over the naive forecast.
+///
+/// Both `method` and the naive "static" forecast are backtested over the same
+/// rolling origins, as in `rolling_forecasts`, and the result is the
+/// percentage improvement in RMSE, `100 * (naive - method) / naive`.
+/// Positive values mean the method adds value over the naive forecast,
+/// negative values that it does worse; "static" itself scores exactly 0.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `initial_train` - Number of observations in the first training window
+/// * `horizon` - Number of values forecast at each origin
+/// * `step` - Distance between consecutive origins
+/// * `method` - Forecasting method name (e.g. "drift", "ses")
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * initial_train, horizon or step is 0
+/// * initial_train + horizon exceeds the data length
+/// * the method is unknown or fails on a training window
+/// * the naive forecast is perfect, so the improvement is undefined
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.forecast_value_add(sales, 24, 3, 1, "holt")
+/// # e.g. 18.5: Holt's backtest RMSE is 18.5% below the naive one
+/// ```
+#[pyfunction]
+pub fn forecast_value_add(
+    data: Vec<f64>,
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    method: String,
+) -> PyResult<f64> {
+    forecast_value_add_impl(&data, initial_train, horizon, step, &method)
+        .map_err(PyValueError::new_err)
+}
+
+/// Fit, forecast and score a method in one call on a holdout split.
+///
+/// The last `horizon` points are held out, `method` is fitted with `params`
+/// on the rest, and its forecast is scored against the held-out tail. The
+/// returned metrics are `mae` and `rmse`, and `mape` and `smape` in percent,
+/// with sMAPE using `2 |f - a| / (|a| + |f|)`. MAPE is NaN if a held-out
+/// value is zero. For an estimate over several origins, use
+/// `backtest_static` or `rolling_forecasts`.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Number of trailing points held out and forecast
+/// * `method` - Forecasting method name (e.g. "ses", "linear_trend")
+/// * `params` - Optional method parameters by name
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * horizon is 0 or not less than the data length
+/// * the method or one of the parameter names is unknown
+/// * the method fails on the training data
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.evaluate([10.0, 10.0, 10.0, 8.0, 12.0], 2, "static")
+/// # {'mae': 2.0, 'rmse': 2.0, 'mape': 20.8..., 'smape': 20.2...}
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, horizon, method, params=None))]
+pub fn evaluate(
+    data: Vec<f64>,
+    horizon: usize,
+    method: String,
+    params: Option<HashMap<String, f64>>,
+) -> PyResult<HashMap<String, f64>> {
+    evaluate_impl(&data, horizon, &method, &params.unwrap_or_default())
+        .map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_rolling_forecasts_counts_and_lengths() {
+        // Origins 4, 6, 8 leave at least 2 actuals each in 10 points
+        let data: Vec<f64> = (0..10).map(|x| x as f64).collect();
+        let forecasts = rolling_forecasts_impl(&data, 4, 2, 2, "static").unwrap();
+
+        assert_eq!(forecasts.len(), 3);
+        assert!(forecasts.iter().all(|f| f.len() == 2));
+        assert_eq!(forecasts[0], vec![3.0, 3.0]);
+        assert_eq!(forecasts[2], vec![7.0, 7.0]);
+    }
+
+    #[test]
+    fn test_rolling_forecasts_infeasible() {
+        let data = vec![1.0, 2.0, 3.0];
+        let result = rolling_forecasts_impl(&data, 2, 2, 1, "static");
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Not enough data for a backtest: initial_train (2) + horizon (2) exceeds data length (3)"
+        );
+    }
+
+    #[test]
+    fn test_rolling_forecasts_huge_horizon() {
+        let result = rolling_forecasts_impl(&[1.0, 2.0, 3.0], 2, usize::MAX, 1, "static");
+
+        assert_eq!(
+            result.unwrap_err(),
+            format!(
+                "Not enough data for a backtest: initial_train (2) + horizon ({}) exceeds data \
+                 length (3)",
+                usize::MAX
+            )
+        );
+    }
+
+    #[test]
+    fn test_rolling_forecasts_zero_step() {
+        let result = rolling_forecasts_impl(&[1.0, 2.0, 3.0], 1, 1, 0, "static");
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Step must be greater than 0");
+    }
+
+    #[test]
+    fn test_backtest_rmse_static_on_line() {
+        // Carry-forward on a unit-slope line misses by 1 and 2 at each origin
+        let data: Vec<f64> = (0..6).map(|x| x as f64).collect();
+        let rmse = backtest_rmse_impl(&data, 2, 2, 1, "static").unwrap();
+
+        assert!((rmse - 2.5_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_backtest_rmse_linear_trend_on_line() {
+        let data: Vec<f64> = (0..8).map(|x| 2.0 * x as f64).collect();
+        let rmse = backtest_rmse_impl(&data, 3, 2, 1, "linear_trend").unwrap();
+
+        assert!(rmse < 1e-9);
+    }
+
+    #[test]
+    fn test_backtest_fold_decay_favours_improving_accuracy() {
+        // Carry-forward errors shrink as the noise dies down over time
+        let mut rng = Rng::new(11);
+        let data: Vec<f64> = (0..80)
+            .map(|t| 20.0 + 5.0 * (-(t as f64) / 15.0).exp() * rng.normal())
+            .collect();
+        let none = HashMap::new();
+
+        let flat = backtest_rmse_with_params_impl(&data, 10, 3, 1, "static", &none, 1.0);
+        let decayed = backtest_rmse_with_params_impl(&data, 10, 3, 1, "static", &none, 0.9);
+        let (flat, decayed) = (flat.unwrap(), decayed.unwrap());
+
+        assert_eq!(flat, backtest_rmse_impl(&data, 10, 3, 1, "static").unwrap());
+        assert!(decayed < flat, "{} vs {}", decayed, flat);
+    }
+
+    #[test]
+    fn test_backtest_fold_decay_one_is_unweighted() {
+        let data: Vec<f64> = (0..6).map(|x| x as f64).collect();
+        let rmse =
+            backtest_rmse_with_params_impl(&data, 2, 2, 1, "static", &HashMap::new(), 1.0).unwrap();
+
+        assert!((rmse - 2.5_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_backtest_invalid_fold_decay() {
+        let data = [1.0, 2.0, 3.0, 4.0];
+        let none = HashMap::new();
+        for decay in [0.0, 1.5, f64::NAN] {
+            let result = backtest_rmse_with_params_impl(&data, 2, 1, 1, "static", &none, decay);
+            assert!(result.is_err());
+        }
+
+        let result = backtest_rmse_with_params_impl(&data, 2, 1, 1, "static", &none, 0.0);
+        assert_eq!(result.unwrap_err(), "Fold decay must be in (0, 1], got 0");
+    }
+
+    #[test]
+    fn test_backtest_rmse_below_abandons_a_losing_method() {
+        let data: Vec<f64> = (0..40).map(|x| 3.0 * x as f64).collect();
+        let none = HashMap::new();
+
+        let full = backtest_rmse_below_impl(&data, 10, 2, 1, "static", &none, f64::INFINITY);
+        let full = full.unwrap().unwrap();
+        assert!((full - backtest_rmse_impl(&data, 10, 2, 1, "static").unwrap()).abs() < 1e-9);
+
+        let bounded = backtest_rmse_below_impl(&data, 10, 2, 1, "static", &none, 1.0).unwrap();
+        assert_eq!(bounded, None);
+
+        let winner = backtest_rmse_below_impl(&data, 10, 2, 1, "linear_trend", &none, 1.0);
+        assert!(winner.unwrap().unwrap() < 1e-9);
+    }
+
+    #[test]
+    fn test_backtest_per_step_error_static_on_line() {
+        // Carry-forward on a unit-slope line misses by exactly h at lead time h
+        let data: Vec<f64> = (0..10).map(|x| x as f64).collect();
+        let errors = backtest_per_step_error_impl(&data, 3, 4, 2, "static").unwrap();
+
+        assert_eq!(errors, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_backtest_per_step_error_step_one_uses_next_actuals() {
+        // Origins 2 and 3: step-1 errors are data[2] - data[1] and data[3] - data[2]
+        let data = [1.0, 2.0, 5.0, 6.0, 8.0];
+        let errors = backtest_per_step_error_impl(&data, 2, 2, 1, "static").unwrap();
+
+        assert_eq!(errors.len(), 2);
+        assert!((errors[0] - ((9.0 + 1.0) / 2.0_f64).sqrt()).abs() < 1e-12);
+        assert!((errors[1] - ((16.0 + 9.0) / 2.0_f64).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_forecast_history_lead_one_matches_fitted_values() {
+        let data = [3.0, 5.0, 4.0, 6.0, 8.0, 7.0, 9.0];
+        let history = forecast_history_impl(&data, 1, "ses").unwrap();
+        let fitted = crate::methods::fitted_values_impl(&data, "ses", &HashMap::new()).unwrap();
+
+        assert_eq!(history.len(), data.len() - 1);
+        assert_eq!(history, fitted[1..]);
+    }
+
+    #[test]
+    fn test_forecast_history_is_rolling_forecast_diagonal() {
+        let data: Vec<f64> = (0..12).map(|t| (t * t) as f64).collect();
+        let lead = 3;
+        let history = forecast_history_impl(&data, lead, "linear_trend").unwrap();
+        let rolling = rolling_forecasts_impl(&data, 2, lead, 1, "linear_trend").unwrap();
+
+        // linear_trend needs two points, so the first target is data[2 + lead - 1]
+        assert_eq!(history.len(), data.len() - (2 + lead - 1));
+        for (value, forecast) in history.iter().zip(&rolling) {
+            assert_eq!(*value, forecast[lead - 1]);
+        }
+    }
+
+    #[test]
+    fn test_forecast_history_too_short() {
+        assert_eq!(
+            forecast_history_impl(&[1.0; 4], 0, "ses").unwrap_err(),
+            "Lead must be greater than 0"
+        );
+        assert_eq!(
+            forecast_history_impl(&[1.0; 4], 3, "linear_trend").unwrap_err(),
+            "Not enough data for a forecast history: min_history (2) + lead (3) exceeds data length (4)"
+        );
+    }
+
+    #[test]
+    fn test_forecast_history_huge_lead() {
+        assert_eq!(
+            forecast_history_impl(&[1.0; 4], usize::MAX, "linear_trend").unwrap_err(),
+            format!(
+                "Not enough data for a forecast history: min_history (2) + lead ({}) exceeds data \
+                 length (4)",
+                usize::MAX
+            )
+        );
+    }
+
+    #[test]
+    fn test_innovation_std_deterministic_series_is_near_zero() {
+        let data: Vec<f64> = (0..30).map(|t| 3.0 * t as f64 - 7.0).collect();
+        let std = innovation_std_impl(&data, "linear_trend", &HashMap::new()).unwrap();
+
+        assert!(std < 1e-9);
+    }
+
+    #[test]
+    fn test_innovation_std_recovers_noise_scale() {
+        let mut rng = Rng::new(12);
+        let data: Vec<f64> =
+            (0..400).map(|t| 20.0 + 0.1 * t as f64 + 2.0 * rng.normal()).collect();
+        let std = innovation_std_impl(&data, "linear_trend", &HashMap::new()).unwrap();
+
+        // Early windows estimate the line poorly, inflating the errors a little
+        assert!((std - 2.0).abs() < 0.3);
+    }
+
+    #[test]
+    fn test_innovation_std_needs_two_residuals() {
+        let result = innovation_std_impl(&[1.0, 2.0], "static", &HashMap::new());
+
+        assert_eq!(
+            result.unwrap_err(),
+            "Innovation std requires at least two one-step residuals, got 1"
+        );
+    }
+
+    #[test]
+    fn test_backtest_intervals_widen_with_lead_time() {
+        // Random walk errors of the static forecast grow like sqrt(lead)
+        let mut rng = Rng::new(21);
+        let mut level = 100.0;
+        let data: Vec<f64> = (0..300)
+            .map(|_| {
+                level += rng.normal();
+                level
+            })
+            .collect();
+        let (lower, upper) = backtest_intervals_impl(&data, 50, 8, 1, "static", 0.8).unwrap();
+
+        let point = data[data.len() - 1];
+        let widths: Vec<f64> = lower.iter().zip(&upper).map(|(l, u)| u - l).collect();
+        assert!(lower.iter().zip(&upper).all(|(l, u)| *l < point && point < *u));
+        assert!(widths[7] > 2.0 * widths[0]);
+        assert!(widths[3] > widths[0]);
+    }
+
+    #[test]
+    fn test_backtest_intervals_need_two_origins() {
+        let data: Vec<f64> = (0..10).map(|t| t as f64).collect();
+        let result = backtest_intervals_impl(&data, 8, 2, 1, "static", 0.9);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Backtest intervals require at least two backtest origins, got 1"
+        );
+    }
+
+    #[test]
+    fn test_backtest_coverage_stationary_noise() {
+        let mut rng = Rng::new(7);
+        let data: Vec<f64> = (0..600).map(|_| 50.0 + 2.0 * rng.normal()).collect();
+        let coverage = backtest_coverage_impl(&data, 100, 5, 5, 0.9).unwrap();
+
+        assert!(coverage > 0.85 && coverage < 0.95, "coverage {}", coverage);
+    }
+
+    #[test]
+    fn test_backtest_coverage_wider_bands_cover_more() {
+        let mut rng = Rng::new(11);
+        let data: Vec<f64> = (0..300).map(|_| rng.normal()).collect();
+        let narrow = backtest_coverage_impl(&data, 50, 3, 3, 0.5).unwrap();
+        let wide = backtest_coverage_impl(&data, 50, 3, 3, 0.99).unwrap();
+
+        assert!(narrow < wide);
+    }
+
+    fn names(methods: &[&str]) -> Vec<String> {
+        methods.iter().map(|m| m.to_string()).collect()
+    }
+
+    #[test]
+    fn test_fit_ensemble_weights_favors_trend_on_trending_data() {
+        let mut rng = Rng::new(7);
+        let data: Vec<f64> = (0..40).map(|t| 2.0 * t as f64 + rng.normal()).collect();
+        let (weights, warnings) =
+            fit_ensemble_weights_impl(&data, 3, &names(&["static", "mean", "linear_trend"]))
+                .unwrap();
+
+        assert!(warnings.is_empty());
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(weights[2] > weights[0] && weights[2] > weights[1]);
+    }
+
+    #[test]
+    fn test_fit_ensemble_weights_failing_method_gets_zero_weight() {
+        let data = [3.0, -1.0, 4.0, 1.0, -5.0, 9.0, 2.0, 6.0];
+        let (weights, warnings) =
+            fit_ensemble_weights_impl(&data, 2, &names(&["mean", "exponential_trend"])).unwrap();
+
+        assert_eq!(weights, vec![1.0, 0.0]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("Method 'exponential_trend' got zero weight"));
+    }
+
+    #[test]
+    fn test_fit_ensemble_weights_unknown_method() {
+        let result = fit_ensemble_weights_impl(&[1.0, 2.0, 3.0], 1, &names(&["magic"]));
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().starts_with("Unknown method 'magic'"));
+    }
+
+    #[test]
+    fn test_forecast_value_add_drift_on_trend() {
+        let mut rng = Rng::new(23);
+        let data: Vec<f64> = (0..60).map(|t| 20.0 + 0.8 * t as f64 + rng.normal()).collect();
+
+        let drift = forecast_value_add_impl(&data, 30, 3, 1, "drift").unwrap();
+        assert!(drift > 20.0);
+        assert_eq!(forecast_value_add_impl(&data, 30, 3, 1, "static").unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_forecast_value_add_perfect_naive() {
+        let result = forecast_value_add_impl(&[5.0; 10], 5, 2, 1, "mean");
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Naive backtest RMSE is zero, so forecast value add is undefined"
+        );
+    }
+
+    #[test]
+    fn test_evaluate_matches_manual_metrics() {
+        let data = [3.0, 5.0, 4.0, 6.0, 8.0, 7.0, 10.0, 9.0];
+        let params = HashMap::from([("alpha".to_string(), 0.5)]);
+        let metrics = evaluate_impl(&data, 3, "ses", &params).unwrap();
+
+        let forecast = fit_method_impl(&data[..5], "ses", &params).unwrap().forecast(3).unwrap();
+        let errors: Vec<f64> = data[5..].iter().zip(&forecast).map(|(a, f)| f - a).collect();
+        let mae = errors.iter().map(|e| e.abs()).sum::<f64>() / 3.0;
+        let rmse = (errors.iter().map(|e| e * e).sum::<f64>() / 3.0).sqrt();
+        let mape = data[5..].iter().zip(&errors).map(|(a, e)| (e / a).abs()).sum::<f64>() / 0.03;
+        let smape = data[5..]
+            .iter()
+            .zip(&forecast)
+            .map(|(a, f)| 2.0 * (f - a).abs() / (a.abs() + f.abs()))
+            .sum::<f64>()
+            / 0.03;
+
+        assert_eq!(metrics.len(), 4);
+        assert!((metrics["mae"] - mae).abs() < 1e-12);
+        assert!((metrics["rmse"] - rmse).abs() < 1e-12);
+        assert!((metrics["mape"] - mape).abs() < 1e-9);
+        assert!((metrics["smape"] - smape).abs() < 1e-9);
+
+        // The SES level after 3, 5, 4, 6, 8 is 6.5, missing by 0.5, 3.5 and 2.5
+        assert!((metrics["mae"] - 13.0 / 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_evaluate_zero_actual_and_perfect_forecast() {
+        let metrics = evaluate_impl(&[0.0, 0.0, 0.0], 1, "static", &HashMap::new()).unwrap();
+
+        assert_eq!(metrics["mae"], 0.0);
+        assert!(metrics["mape"].is_nan());
+        assert_eq!(metrics["smape"], 0.0);
+    }
+
+    #[test]
+    fn test_evaluate_horizon_too_long() {
+        let result = evaluate_impl(&[1.0, 2.0, 3.0], 3, "static", &HashMap::new());
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Evaluation requires more data points than the horizon (3), got 3"
+        );
+    }
+}
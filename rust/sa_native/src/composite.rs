@@ -0,0 +1,1493 @@
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::linalg::solve;
+use crate::methods::{fit_method_impl, forecast_with_method_impl, is_recursive_method};
+use crate::seasonal::{
+    centered_moving_average, classical_decomposition, fit_seasonal_dummy_impl, validate_seasons,
+};
+use crate::trend::{extrapolate_line, fit_linear_trend_impl};
+
+/// Trend and season component names accepted by `predict_composite`.
+const TREND_MODELS: &[&str] = &["linear", "mean", "none"];
+const SEASON_MODELS: &[&str] = &["naive", "dummy"];
+
+/// Smallest count magnitude that does not fit in an `i64`, 2^63.
+const COUNT_LIMIT: f64 = 9_223_372_036_854_775_808.0;
+
+/// Core ratio forecast logic.
+pub(crate) fn predict_ratio_impl(
+    numerator: &[f64],
+    denominator: &[f64],
+    horizon: usize,
+    method: &str,
+) -> Result<Vec<f64>, String> {
+    if numerator.len() != denominator.len() {
+        return Err(format!(
+            "Numerator length ({}) must match denominator length ({})",
+            numerator.len(),
+            denominator.len()
+        ));
+    }
+
+    let num_forecast = forecast_with_method_impl(numerator, horizon, method)?;
+    let den_forecast = forecast_with_method_impl(denominator, horizon, method)?;
+
+    num_forecast
+        .iter()
+        .zip(&den_forecast)
+        .enumerate()
+        .map(|(h, (num, den))| {
+            if *den == 0.0 {
+                Err(format!("Forecasted denominator is zero at step {}", h))
+            } else {
+                Ok(num / den)
+            }
+        })
+        .collect()
+}
+
+/// Core horizon blend logic.
+///
+/// Steps `1..=crossover` use the near forecast; after that the weight of the
+/// far forecast grows linearly, reaching 1 at the last step.
+pub(crate) fn predict_horizon_blend_impl(
+    data: &[f64],
+    horizon: usize,
+    near_method: &str,
+    far_method: &str,
+    crossover: usize,
+) -> Result<Vec<f64>, String> {
+    if crossover > horizon {
+        return Err(format!(
+            "Crossover ({}) cannot exceed horizon ({})",
+            crossover, horizon
+        ));
+    }
+
+    let near = forecast_with_method_impl(data, horizon, near_method)?;
+    let far = forecast_with_method_impl(data, horizon, far_method)?;
+
+    let ramp = (horizon - crossover) as f64;
+    Ok(near
+        .iter()
+        .zip(&far)
+        .enumerate()
+        .map(|(i, (n, f))| {
+            let step = i + 1;
+            if step <= crossover {
+                *n
+            } else {
+                let weight = (step - crossover) as f64 / ramp;
+                n + weight * (f - n)
+            }
+        })
+        .collect())
+}
+
+/// Zero-mean seasonal component per phase (phase 0 is the first observation).
+///
+/// "naive" takes the last full season of the data detrended by a centered
+/// moving average; "dummy" takes the offsets of the seasonal dummy
+/// regression.
+fn seasonal_component(data: &[f64], model: &str, season_length: usize) -> Result<Vec<f64>, String> {
+    let mut component = match model {
+        "naive" => {
+            let trend = centered_moving_average(data, season_length);
+            let last = trend.iter().rposition(|v| !v.is_nan()).unwrap();
+            let mut component = vec![0.0; season_length];
+            for t in last + 1 - season_length..=last {
+                component[t % season_length] = data[t] - trend[t];
+            }
+            component
+        }
+        "dummy" => fit_seasonal_dummy_impl(data, season_length)?.offsets,
+        _ => unreachable!("season models are validated by predict_composite_impl"),
+    };
+
+    let mean = component.iter().sum::<f64>() / season_length as f64;
+    for value in component.iter_mut() {
+        *value -= mean;
+    }
+
+    Ok(component)
+}
+
+/// Core composite forecast logic: trend on the deseasonalized data plus the
+/// seasonal component carried forward.
+pub(crate) fn predict_composite_impl(
+    data: &[f64],
+    horizon: usize,
+    trend: &str,
+    season: Option<(&str, usize)>,
+) -> Result<Vec<f64>, String> {
+    let trend_method = match trend {
+        "linear" => "linear_trend",
+        "mean" => "mean",
+        "none" => "static",
+        _ => {
+            return Err(format!(
+                "Unknown trend model '{}', expected one of: {}",
+                trend,
+                TREND_MODELS.join(", ")
+            ))
+        }
+    };
+
+    let (model, season_length) = match season {
+        Some(season) => season,
+        None => return forecast_with_method_impl(data, horizon, trend_method),
+    };
+
+    if !SEASON_MODELS.contains(&model) {
+        return Err(format!(
+            "Unknown season model '{}', expected one of: {}",
+            model,
+            SEASON_MODELS.join(", ")
+        ));
+    }
+
+    validate_seasons(data.len(), season_length)?;
+
+    // A non-finite value would leave no defined moving-average window
+    if data.iter().any(|y| !y.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    let component = seasonal_component(data, model, season_length)?;
+    let adjusted: Vec<f64> = data
+        .iter()
+        .enumerate()
+        .map(|(t, y)| y - component[t % season_length])
+        .collect();
+
+    let n = data.len();
+    Ok(forecast_with_method_impl(&adjusted, horizon, trend_method)?
+        .into_iter()
+        .enumerate()
+        .map(|(h, value)| value + component[(n + h) % season_length])
+        .collect())
+}
+
+/// Core differenced forecast logic: forecast `y[t] - y[t - lag]` with
+/// `method`, then integrate back onto the original scale.
+pub(crate) fn predict_on_difference_impl(
+    data: &[f64],
+    horizon: usize,
+    method: &str,
+    lag: usize,
+) -> Result<Vec<f64>, String> {
+    if lag == 0 {
+        return Err("Lag must be greater than 0".to_string());
+    }
+
+    if data.len() <= lag {
+        return Err(format!(
+            "Differencing at lag {} requires more than {} data points, got {}",
+            lag,
+            lag,
+            data.len()
+        ));
+    }
+
+    let differences: Vec<f64> = data.windows(lag + 1).map(|w| w[lag] - w[0]).collect();
+    let forecast = forecast_with_method_impl(&differences, horizon, method)?;
+
+    // Each step adds its difference to the value one lag earlier, taken from
+    // the data for the first `lag` steps and from the forecast after that
+    let mut levels = data[data.len() - lag..].to_vec();
+    for (h, difference) in forecast.iter().enumerate() {
+        levels.push(levels[h] + difference);
+    }
+
+    Ok(levels.split_off(lag))
+}
+
+/// Core inverse-variance pooling logic: each forecast weighted by
+/// `1 / variance`, normalized to sum to 1.
+pub(crate) fn combine_inverse_variance_impl(
+    forecasts: &[Vec<f64>],
+    variances: &[f64],
+) -> Result<Vec<f64>, String> {
+    validate_forecast_set(forecasts)?;
+
+    if variances.len() != forecasts.len() {
+        return Err(format!(
+            "Variance count ({}) must match forecast count ({})",
+            variances.len(),
+            forecasts.len()
+        ));
+    }
+
+    if let Some(v) = variances.iter().find(|v| !v.is_finite() || **v <= 0.0) {
+        return Err(format!("Variances must be finite and positive, got {}", v));
+    }
+
+    let precisions: Vec<f64> = variances.iter().map(|v| 1.0 / v).collect();
+    let total: f64 = precisions.iter().sum();
+    let weights: Vec<f64> = precisions.iter().map(|p| p / total).collect();
+
+    Ok(weighted_sum(forecasts, &weights))
+}
+
+/// Core minimum-variance combination logic: weights `S^-1 1 / (1' S^-1 1)`
+/// for the error covariance `S`, applied at every step.
+pub(crate) fn combine_optimal_impl(
+    forecasts: &[Vec<f64>],
+    error_covariance: &[Vec<f64>],
+) -> Result<Vec<f64>, String> {
+    validate_forecast_set(forecasts)?;
+
+    let k = forecasts.len();
+    if error_covariance.len() != k {
+        return Err(format!(
+            "Error covariance has {} rows, expected {} to match the forecast count",
+            error_covariance.len(),
+            k
+        ));
+    }
+
+    if let Some(i) = error_covariance.iter().position(|row| row.len() != k) {
+        return Err(format!(
+            "Error covariance row {} has {} columns, expected {}",
+            i,
+            error_covariance[i].len(),
+            k
+        ));
+    }
+
+    if error_covariance.iter().flatten().any(|v| !v.is_finite()) {
+        return Err("Error covariance must be finite".to_string());
+    }
+
+    let precision_sums = solve(error_covariance.to_vec(), vec![1.0; k]).map_err(|_| {
+        "Error covariance is singular or nearly singular; drop or merge forecasts whose errors \
+         are (almost) linearly dependent"
+            .to_string()
+    })?;
+    let total: f64 = precision_sums.iter().sum();
+    if !total.is_finite() || total <= 0.0 {
+        return Err("Error covariance must be positive definite".to_string());
+    }
+    let weights: Vec<f64> = precision_sums.iter().map(|p| p / total).collect();
+
+    Ok(weighted_sum(forecasts, &weights))
+}
+
+/// Check that there is at least one forecast and that all share the same,
+/// non-zero horizon.
+fn validate_forecast_set(forecasts: &[Vec<f64>]) -> Result<(), String> {
+    let first = match forecasts.first() {
+        Some(first) => first,
+        None => return Err("At least one forecast is required".to_string()),
+    };
+
+    if first.is_empty() {
+        return Err("Forecasts cannot be empty".to_string());
+    }
+
+    if let Some(i) = forecasts.iter().position(|f| f.len() != first.len()) {
+        return Err(format!(
+            "All forecasts must have the same horizon: forecast {} has {} values, expected {}",
+            i,
+            forecasts[i].len(),
+            first.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Step-wise weighted sum of equally long forecasts, one weight each.
+fn weighted_sum(forecasts: &[Vec<f64>], weights: &[f64]) -> Vec<f64> {
+    let mut pooled = vec![0.0; forecasts[0].len()];
+    for (forecast, weight) in forecasts.iter().zip(weights) {
+        for (sum, value) in pooled.iter_mut().zip(forecast) {
+            *sum += weight * value;
+        }
+    }
+
+    pooled
+}
+
+/// Core override logic: replace the forecast values at the given steps.
+pub(crate) fn apply_overrides_impl(
+    forecast: &[f64],
+    overrides: &HashMap<usize, f64>,
+) -> Result<Vec<f64>, String> {
+    // Report the smallest offending step so the error does not depend on
+    // hash order
+    if let Some(step) = overrides.keys().filter(|&&step| step >= forecast.len()).min() {
+        return Err(format!(
+            "Override step {} is out of range for a forecast of length {}",
+            step,
+            forecast.len()
+        ));
+    }
+
+    let mut result = forecast.to_vec();
+    for (&step, &value) in overrides {
+        result[step] = value;
+    }
+
+    Ok(result)
+}
+
+/// Core bounded forecast logic: forecast `ln((y - lower) / (upper - y))`
+/// with `method` and map back with the scaled logistic function.
+pub(crate) fn predict_bounded_impl(
+    data: &[f64],
+    horizon: usize,
+    method: &str,
+    lower: f64,
+    upper: f64,
+) -> Result<Vec<f64>, String> {
+    if !(lower.is_finite() && upper.is_finite() && lower < upper) {
+        return Err(format!(
+            "Bounds must be finite with lower < upper, got ({}, {})",
+            lower, upper
+        ));
+    }
+
+    if let Some(y) = data.iter().find(|y| !(lower < **y && **y < upper)) {
+        return Err(format!(
+            "Data must lie strictly inside ({}, {}), got {}",
+            lower, upper, y
+        ));
+    }
+
+    let logits: Vec<f64> = data.iter().map(|y| ((y - lower) / (upper - y)).ln()).collect();
+    let range = upper - lower;
+    // Large logits round onto a bound; the data proves a float lies strictly
+    // between them, so the clamp range is not empty
+    let (inside_lower, inside_upper) = (lower.next_up(), upper.next_down());
+    Ok(forecast_with_method_impl(&logits, horizon, method)?
+        .into_iter()
+        .map(|z| (lower + range / (1.0 + (-z).exp())).clamp(inside_lower, inside_upper))
+        .collect())
+}
+
+/// Core per-step bounds logic: the `method` forecast with step `h` clamped
+/// to `[lower[h], upper[h]]`.
+pub(crate) fn predict_with_bounds_impl(
+    data: &[f64],
+    horizon: usize,
+    method: &str,
+    lower: &[f64],
+    upper: &[f64],
+) -> Result<Vec<f64>, String> {
+    for (name, bounds) in [("Lower", lower), ("Upper", upper)] {
+        if bounds.len() != horizon {
+            return Err(format!(
+                "{} bounds have {} values, expected one per step of the horizon ({})",
+                name,
+                bounds.len(),
+                horizon
+            ));
+        }
+    }
+
+    let invalid = |h: &usize| lower[*h].is_nan() || upper[*h].is_nan() || lower[*h] > upper[*h];
+    if let Some(h) = (0..horizon).find(invalid) {
+        return Err(format!(
+            "Bounds at step {} must satisfy lower <= upper, got ({}, {})",
+            h, lower[h], upper[h]
+        ));
+    }
+
+    Ok(forecast_with_method_impl(data, horizon, method)?
+        .into_iter()
+        .zip(lower.iter().zip(upper))
+        .map(|(value, (lo, hi))| value.clamp(*lo, *hi))
+        .collect())
+}
+
+/// Round non-negative values to integers by the largest-remainder method:
+/// every value is floored, then the units still missing from the rounded
+/// total go to the values with the largest fractional parts, earlier steps
+/// first on ties.
+pub(crate) fn round_largest_remainder(values: &[f64]) -> Result<Vec<i64>, String> {
+    let total: f64 = values.iter().sum();
+    if total >= COUNT_LIMIT {
+        return Err(format!("Count forecast total {:e} is too large to represent", total));
+    }
+
+    let mut counts: Vec<i64> = values.iter().map(|v| v.floor() as i64).collect();
+    let floored = counts
+        .iter()
+        .try_fold(0i64, |sum, &count| sum.checked_add(count))
+        .ok_or_else(|| "Count forecast total is too large to represent".to_string())?;
+    let missing = total.round() as i64 - floored;
+
+    let remainders: Vec<f64> = values.iter().map(|v| v - v.floor()).collect();
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    // Stable sort keeps earlier steps first among equal remainders
+    order.sort_by(|&a, &b| remainders[b].total_cmp(&remainders[a]));
+    for &step in order.iter().take(missing.max(0) as usize) {
+        counts[step] += 1;
+    }
+
+    Ok(counts)
+}
+
+/// Core count forecast logic: the `method` forecast, floored at zero and
+/// rounded with `round_largest_remainder`.
+pub(crate) fn predict_counts_impl(
+    data: &[f64],
+    horizon: usize,
+    method: &str,
+) -> Result<Vec<i64>, String> {
+    if let Some(y) = data.iter().find(|y| !y.is_finite() || **y < 0.0) {
+        return Err(format!("Count data must be finite and non-negative, got {}", y));
+    }
+
+    let forecast = forecast_with_method_impl(data, horizon, method)?;
+    if forecast.iter().any(|v| !v.is_finite()) {
+        return Err(format!("Method '{}' produced a non-finite forecast", method));
+    }
+
+    if let Some(step) = forecast.iter().position(|&v| v >= COUNT_LIMIT) {
+        return Err(format!(
+            "Method '{}' produced a forecast too large to count, got {:e} at step {}",
+            method, forecast[step], step
+        ));
+    }
+
+    let clipped: Vec<f64> = forecast.into_iter().map(|v| v.max(0.0)).collect();
+    round_largest_remainder(&clipped)
+}
+
+/// Core anchored forecast logic: the `method` forecast with the anchored
+/// steps replaced by their known values.
+///
+/// For recursive methods (see `is_recursive_method`) the forecast between
+/// anchors is rebuilt: after each anchor the method is refitted on the data
+/// extended with every step up to and including it. Since the method's own
+/// forecasts leave its recursion unchanged, this is the same as feeding the
+/// anchored value into the recursion in place of an observation.
+pub(crate) fn predict_anchored_impl(
+    data: &[f64],
+    horizon: usize,
+    method: &str,
+    anchors: &HashMap<usize, f64>,
+    params: &HashMap<String, f64>,
+) -> Result<Vec<f64>, String> {
+    let mut steps: Vec<usize> = anchors.keys().copied().collect();
+    steps.sort_unstable();
+
+    if let Some(&step) = steps.iter().find(|&&step| step >= horizon) {
+        return Err(format!(
+            "Anchor step {} is out of range for a horizon of {}",
+            step, horizon
+        ));
+    }
+
+    if let Some(&step) = steps.iter().find(|step| !anchors[step].is_finite()) {
+        return Err(format!(
+            "Anchor values must be finite, got {} at step {}",
+            anchors[&step], step
+        ));
+    }
+
+    let mut state = fit_method_impl(data, method, params)?;
+    if !is_recursive_method(method) {
+        let mut forecast = state.forecast(horizon)?;
+        for step in steps {
+            forecast[step] = anchors[&step];
+        }
+        return Ok(forecast);
+    }
+
+    let mut path = data.to_vec();
+    let mut start = 0;
+    for step in steps {
+        if step > start {
+            path.extend(state.forecast(step - start)?);
+        }
+        path.push(anchors[&step]);
+        state = fit_method_impl(&path, method, params)?;
+        start = step + 1;
+    }
+    if start < horizon {
+        path.extend(state.forecast(horizon - start)?);
+    }
+
+    Ok(path.split_off(data.len()))
+}
+
+/// Core decomposition forecast logic.
+///
+/// Runs the classical additive decomposition, extends a least squares line
+/// through the moving-average trend and adds the seasonal component of the
+/// matching phase, i.e. its seasonal-naive continuation.
+pub(crate) fn predict_decomposition_impl(
+    data: &[f64],
+    horizon: usize,
+    season_length: usize,
+) -> Result<Vec<f64>, String> {
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    validate_seasons(data.len(), season_length)?;
+
+    if data.iter().any(|y| !y.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    let (trend, seasonal) = classical_decomposition(data, season_length);
+    // The moving average is defined on one contiguous block after the edges
+    let first = trend.iter().position(|v| !v.is_nan()).unwrap();
+    let last = trend.iter().rposition(|v| !v.is_nan()).unwrap();
+    let (slope, intercept) = fit_linear_trend_impl(&trend[first..=last])?;
+
+    let n = data.len();
+    Ok(extrapolate_line(slope, intercept, n - first, horizon)
+        .into_iter()
+        .enumerate()
+        .map(|(h, level)| level + seasonal[(n + h) % season_length])
+        .collect())
+}
+
+/// Forecast a ratio of two series, such as a conversion rate.
+///
+/// Numerator and denominator are forecast separately with the same method and
+/// the element-wise ratio of the two forecasts is returned.
+///
+/// # Arguments
+///
+/// * `numerator` - Historical numerator values
+/// * `denominator` - Historical denominator values, same length
+/// * `horizon` - Number of future values to predict
+/// * `method` - Forecasting method name (e.g. "static", "linear_trend")
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * the histories differ in length
+/// * either forecast fails
+/// * a forecasted denominator is zero
+#[pyfunction]
+pub fn predict_ratio(
+    numerator: Vec<f64>,
+    denominator: Vec<f64>,
+    horizon: usize,
+    method: String,
+) -> PyResult<Vec<f64>> {
+    predict_ratio_impl(&numerator, &denominator, horizon, &method).map_err(PyValueError::new_err)
+}
+
+/// Blend two methods across the horizon: one for near steps, one for far.
+///
+/// Steps up to `crossover` are the `near_method` forecast. Beyond it the
+/// forecast moves linearly from the near to the far forecast, so the last
+/// step equals the `far_method` forecast. With `crossover == horizon` the
+/// result is the near forecast alone.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Number of future values to predict
+/// * `near_method` - Method used for the first `crossover` steps
+/// * `far_method` - Method the blend converges to at the last step
+/// * `crossover` - Last step forecast purely by `near_method`
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * crossover exceeds horizon
+/// * either method is unknown or fails on the data
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// data = [1.0, 2.0, 3.0, 4.0]
+/// sa_native.predict_horizon_blend(data, 4, "linear_trend", "static", 2)
+/// # [5.0, 6.0, 5.5, 4.0]
+/// ```
+#[pyfunction]
+pub fn predict_horizon_blend(
+    data: Vec<f64>,
+    horizon: usize,
+    near_method: String,
+    far_method: String,
+    crossover: usize,
+) -> PyResult<Vec<f64>> {
+    predict_horizon_blend_impl(&data, horizon, &near_method, &far_method, crossover)
+        .map_err(PyValueError::new_err)
+}
+
+/// Forecast by composing a trend model with an optional seasonal model.
+///
+/// With a season, the seasonal component is estimated, removed from the
+/// data, the trend model is fitted to the adjusted series, and the
+/// component is added back to its forecast, as in classical decomposition.
+///
+/// Trend models: `"linear"` (least squares line), `"mean"` (historical
+/// mean) and `"none"` (last adjusted value, no trend). Season models:
+/// `"naive"` (last full season, detrended by a centered moving average) and
+/// `"dummy"` (seasonal dummy regression offsets). Components are centered to
+/// average zero over a season.
+///
+/// # Arguments
+///
+/// * `data` - Historical data, phase 0 being the first observation
+/// * `horizon` - Number of future values to predict
+/// * `trend` - Trend model name
+/// * `season` - Optional `(season model, season length)`
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * the trend or season model name is unknown
+/// * season length is less than 2 or data holds fewer than two full seasons
+/// * a season is given and data has a non-finite value
+/// * the trend model fails on the adjusted data or horizon is 0
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// data = [10.0, 14.0, 12.0, 16.0, 14.0, 18.0]
+/// sa_native.predict_composite(data, 2, "linear", ("naive", 2))
+/// # [16.0, 20.0]
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, horizon, trend, season=None))]
+pub fn predict_composite(
+    data: Vec<f64>,
+    horizon: usize,
+    trend: String,
+    season: Option<(String, usize)>,
+) -> PyResult<Vec<f64>> {
+    let season = season.as_ref().map(|(model, length)| (model.as_str(), *length));
+    predict_composite_impl(&data, horizon, &trend, season).map_err(PyValueError::new_err)
+}
+
+/// Forecast the lag-`lag` differences of a series and integrate the result.
+///
+/// The series `y[t] - y[t - lag]` is forecast with `method`, and each
+/// forecast difference is added back to the value one lag earlier, starting
+/// from the last `lag` observations. `lag = 1` removes a trend; a lag of one
+/// season removes a stable seasonal pattern.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Number of future values to predict
+/// * `method` - Forecasting method applied to the differences
+/// * `lag` - Differencing lag
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * lag is 0 or data has no more points than the lag
+/// * the method is unknown or fails on the differences
+/// * horizon is 0
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.predict_on_difference([1.0, 3.0, 5.0, 7.0], 3, "mean", 1)
+/// # [9.0, 11.0, 13.0]
+/// ```
+#[pyfunction]
+pub fn predict_on_difference(
+    data: Vec<f64>,
+    horizon: usize,
+    method: String,
+    lag: usize,
+) -> PyResult<Vec<f64>> {
+    predict_on_difference_impl(&data, horizon, &method, lag).map_err(PyValueError::new_err)
+}
+
+/// Pool independent forecasts weighted by the inverse of their error
+/// variances.
+///
+/// Forecast `i` gets weight `(1 / v_i) / sum_j (1 / v_j)` at every step,
+/// which minimizes the variance of the pooled forecast when the errors are
+/// independent and unbiased. Equal variances give the plain average.
+///
+/// # Arguments
+///
+/// * `forecasts` - Forecasts to pool, all with the same horizon
+/// * `variances` - Error variance of each forecast
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * there are no forecasts, or they are empty or differ in horizon
+/// * the variance count differs from the forecast count
+/// * a variance is not finite and positive
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.combine_inverse_variance([[10.0, 12.0], [16.0, 18.0]], [1.0, 2.0])
+/// # [12.0, 14.0]
+/// ```
+#[pyfunction]
+pub fn combine_inverse_variance(
+    forecasts: Vec<Vec<f64>>,
+    variances: Vec<f64>,
+) -> PyResult<Vec<f64>> {
+    combine_inverse_variance_impl(&forecasts, &variances).map_err(PyValueError::new_err)
+}
+
+/// Pool correlated forecasts with the minimum-variance combination weights.
+///
+/// Inverse-variance pooling assumes independent errors; when forecasts share
+/// information their errors are correlated and those weights are no longer
+/// optimal. Given the error covariance matrix `S`, forecast `i` gets weight
+/// `w_i` from `w = S^-1 1 / (1' S^-1 1)` at every step, the weights that
+/// minimize the variance of the pooled error subject to summing to 1. A
+/// diagonal `S` reproduces `combine_inverse_variance`. Weights can be
+/// negative when forecasts are strongly correlated.
+///
+/// # Arguments
+///
+/// * `forecasts` - Forecasts to pool, all with the same horizon
+/// * `error_covariance` - Square, symmetric covariance matrix of the forecast
+///   errors, one row and column per forecast
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * there are no forecasts, or they are empty or differ in horizon
+/// * the covariance is not square with one row per forecast, or not finite
+/// * the covariance is singular or nearly singular, or not positive definite
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.combine_optimal([[10.0, 12.0], [16.0, 18.0]], [[1.0, 0.5], [0.5, 2.0]])
+/// # [11.5, 13.5]: weights 0.75 and 0.25
+/// ```
+#[pyfunction]
+pub fn combine_optimal(
+    forecasts: Vec<Vec<f64>>,
+    error_covariance: Vec<Vec<f64>>,
+) -> PyResult<Vec<f64>> {
+    combine_optimal_impl(&forecasts, &error_covariance).map_err(PyValueError::new_err)
+}
+
+/// Replace forecast values at chosen horizon steps with analyst overrides.
+///
+/// Step `i` is the forecast `i + 1` periods ahead, i.e. index `i` of
+/// `forecast`. Steps without an override are returned unchanged.
+///
+/// # Arguments
+///
+/// * `forecast` - Forecast values to adjust
+/// * `overrides` - Mapping from step index to its replacement value
+///
+/// # Errors
+///
+/// Returns PyValueError naming the offending step if an index is beyond the
+/// end of the forecast.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.apply_overrides([10.0, 11.0, 12.0, 13.0], {0: 9.5, 2: 15.0})
+/// # [9.5, 11.0, 15.0, 13.0]
+/// ```
+#[pyfunction]
+pub fn apply_overrides(forecast: Vec<f64>, overrides: HashMap<usize, f64>) -> PyResult<Vec<f64>> {
+    apply_overrides_impl(&forecast, &overrides).map_err(PyValueError::new_err)
+}
+
+/// Forecast a series confined to `(lower, upper)`, such as a proportion.
+///
+/// The data is mapped to an unbounded scale with the logit
+/// `ln((y - lower) / (upper - y))`, forecast there with `method`, and mapped
+/// back with `lower + (upper - lower) / (1 + exp(-z))`. The forecasts
+/// therefore stay strictly inside the bounds however far the method
+/// extrapolates; logits beyond about ±37, which would round onto a bound in
+/// floating point, give the nearest float inside it instead.
+///
+/// # Arguments
+///
+/// * `data` - Historical data, strictly inside the bounds
+/// * `horizon` - Number of future values to predict
+/// * `method` - Forecasting method applied on the logit scale
+/// * `lower` - Lower bound
+/// * `upper` - Upper bound
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * the bounds are not finite or lower >= upper
+/// * a data value is not strictly inside the bounds
+/// * the method is unknown or fails, or horizon is 0
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// share = [0.50, 0.60, 0.70, 0.80, 0.90]
+/// sa_native.predict_bounded(share, 3, "linear_trend", 0.0, 1.0)
+/// # approaches 1.0 without reaching it
+/// ```
+#[pyfunction]
+pub fn predict_bounded(
+    data: Vec<f64>,
+    horizon: usize,
+    method: String,
+    lower: f64,
+    upper: f64,
+) -> PyResult<Vec<f64>> {
+    predict_bounded_impl(&data, horizon, &method, lower, upper).map_err(PyValueError::new_err)
+}
+
+/// Forecast with hard bounds that vary over the horizon, e.g. a capacity
+/// ramping up over the planning period.
+///
+/// The forecast of `method` is computed as usual and each step `h` is then
+/// clamped to `[lower[h], upper[h]]`. Unlike `predict_bounded`, the bounds
+/// are applied after the fact and forecasts can sit exactly on them; use
+/// `-inf` or `inf` to leave a side of some step open.
+///
+/// # Arguments
+///
+/// * `data` - Historical data
+/// * `horizon` - Number of future values to predict
+/// * `method` - Forecasting method name (e.g. "ses", "linear_trend")
+/// * `lower` - Lower bound of each step, of length `horizon`
+/// * `upper` - Upper bound of each step, of length `horizon`
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * either bound vector does not have `horizon` values
+/// * `lower[h] > upper[h]` or a bound is NaN at some step
+/// * the method is unknown or fails, or horizon is 0
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// data = [10.0, 12.0, 14.0, 16.0]
+/// sa_native.predict_with_bounds(data, 3, "linear_trend", [0.0] * 3, [17.0, 19.0, 19.0])
+/// # [17.0, 19.0, 19.0]
+/// ```
+#[pyfunction]
+pub fn predict_with_bounds(
+    data: Vec<f64>,
+    horizon: usize,
+    method: String,
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+) -> PyResult<Vec<f64>> {
+    predict_with_bounds_impl(&data, horizon, &method, &lower, &upper)
+        .map_err(PyValueError::new_err)
+}
+
+/// Forecast with some future values already known.
+///
+/// `anchors` maps horizon steps (0 being one step ahead) to known values,
+/// such as a scheduled promotion. Anchored steps take their known value. For
+/// recursive methods ("static", "pct_change", "drift", "ses", "holt" and
+/// "holt_winters"), the anchors are also fed back into the recursion as if
+/// they had been observed, so the steps after an anchor start from it; for
+/// example, an SES forecast moves toward an anchored value and stays there
+/// until the next anchor. Global fits ("mean", "linear_trend",
+/// "exponential_trend") do not depend on the order of the data in that way,
+/// so their unanchored steps are left unchanged.
+///
+/// # Arguments
+///
+/// * `data` - Historical data
+/// * `horizon` - Number of future values to predict
+/// * `method` - Forecasting method name (e.g. "ses", "linear_trend")
+/// * `anchors` - Known values by horizon step
+/// * `params` - Optional method parameters by name, used for every refit
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * an anchor step is not less than horizon, or an anchor value is not
+///   finite
+/// * the method is unknown or fails, or horizon is 0
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.predict_anchored([10.0, 12.0, 14.0], 4, "static", {1: 30.0})
+/// # [14.0, 30.0, 30.0, 30.0]
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, horizon, method, anchors, params=None))]
+pub fn predict_anchored(
+    data: Vec<f64>,
+    horizon: usize,
+    method: String,
+    anchors: HashMap<usize, f64>,
+    params: Option<HashMap<String, f64>>,
+) -> PyResult<Vec<f64>> {
+    predict_anchored_impl(&data, horizon, &method, &anchors, &params.unwrap_or_default())
+        .map_err(PyValueError::new_err)
+}
+
+/// Forecast whole-unit counts whose total matches the continuous forecast.
+///
+/// The forecast of `method` is made in continuous space, negative values are
+/// floored at zero, and the result is rounded with the largest-remainder
+/// method: every step is rounded down, then the units still needed to reach
+/// the rounded continuous total are added to the steps with the largest
+/// fractional parts. Rounding each step on its own can bias the total by up
+/// to half a unit per step (e.g. a flat 2.4 rounds to 2 every time); here
+/// the rounded total stays within half a unit of the continuous one, and each
+/// step within one unit of its continuous value.
+///
+/// # Arguments
+///
+/// * `data` - Historical counts, finite and non-negative
+/// * `horizon` - Number of future values to predict
+/// * `method` - Forecasting method name (e.g. "ses", "linear_trend")
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * a data value is negative or not finite
+/// * the method is unknown, fails or gives a non-finite forecast, or horizon
+///   is 0
+/// * a forecast, or their total, is too large for a 64-bit integer count
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.predict_counts([2.4, 2.4, 2.4], 5, "static")
+/// # [3, 3, 2, 2, 2]: 12 units, as 5 * 2.4 = 12.0
+/// ```
+#[pyfunction]
+pub fn predict_counts(data: Vec<f64>, horizon: usize, method: String) -> PyResult<Vec<i64>> {
+    predict_counts_impl(&data, horizon, &method).map_err(PyValueError::new_err)
+}
+
+/// Forecast by classical additive decomposition and recomposition.
+///
+/// The series is split into a centered moving-average trend and a zero-mean
+/// seasonal component per phase. The trend is extrapolated with a least
+/// squares line and the seasonal component is carried forward
+/// seasonal-naively, then the two are added. The moving average is undefined
+/// for the last half season, so the line is fitted through the trend up to
+/// that point and extended over the gap and the horizon.
+///
+/// # Arguments
+///
+/// * `data` - Historical data, phase 0 being the first observation
+/// * `horizon` - Number of future values to predict
+/// * `season_length` - Number of observations per season
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * season length is less than 2 or data holds fewer than two full seasons
+/// * data has a non-finite value
+/// * horizon is 0
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// data = [10.0, 14.0, 12.0, 16.0, 14.0, 18.0]
+/// sa_native.predict_decomposition(data, 2, 2)
+/// # [16.0, 20.0]
+/// ```
+#[pyfunction]
+pub fn predict_decomposition(
+    data: Vec<f64>,
+    horizon: usize,
+    season_length: usize,
+) -> PyResult<Vec<f64>> {
+    predict_decomposition_impl(&data, horizon, season_length).map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_predict_ratio_flat_forecasts() {
+        let numerator = vec![5.0, 8.0, 6.0];
+        let denominator = vec![100.0, 90.0, 120.0];
+        let result = predict_ratio_impl(&numerator, &denominator, 3, "static").unwrap();
+
+        assert_eq!(result, vec![0.05; 3]);
+    }
+
+    #[test]
+    fn test_predict_ratio_length_mismatch() {
+        let result = predict_ratio_impl(&[1.0, 2.0], &[1.0], 2, "static");
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Numerator length (2) must match denominator length (1)"
+        );
+    }
+
+    #[test]
+    fn test_predict_ratio_zero_denominator() {
+        // The denominator trend crosses zero at the second step
+        let result = predict_ratio_impl(&[1.0, 1.0, 1.0], &[4.0, 3.0, 2.0], 3, "linear_trend");
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Forecasted denominator is zero at step 1");
+    }
+
+    #[test]
+    fn test_predict_horizon_blend_near_then_far() {
+        let data = [1.0, 2.0, 3.0, 4.0];
+        let near = forecast_with_method_impl(&data, 8, "linear_trend").unwrap();
+        let far = forecast_with_method_impl(&data, 8, "mean").unwrap();
+        let result = predict_horizon_blend_impl(&data, 8, "linear_trend", "mean", 3).unwrap();
+
+        assert_eq!(&result[..3], &near[..3]);
+        assert!((result[7] - far[7]).abs() < 1e-12);
+        // Strictly between the two in the transition
+        for h in 3..7 {
+            assert!(far[h] < result[h] && result[h] < near[h]);
+        }
+    }
+
+    #[test]
+    fn test_predict_horizon_blend_example() {
+        let result =
+            predict_horizon_blend_impl(&[1.0, 2.0, 3.0, 4.0], 4, "linear_trend", "static", 2)
+                .unwrap();
+
+        assert_eq!(result, vec![5.0, 6.0, 5.5, 4.0]);
+    }
+
+    #[test]
+    fn test_predict_horizon_blend_crossover_too_large() {
+        let result = predict_horizon_blend_impl(&[1.0, 2.0], 2, "static", "mean", 3);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Crossover (3) cannot exceed horizon (2)");
+    }
+
+    #[test]
+    fn test_predict_composite_linear_trend_naive_season() {
+        let pattern = [6.0, -2.0, 1.0, -5.0];
+        let series = |t: usize| 20.0 + 1.5 * t as f64 + pattern[t % 4];
+        let data: Vec<f64> = (0..16).map(series).collect();
+        let result = predict_composite_impl(&data, 6, "linear", Some(("naive", 4))).unwrap();
+
+        for (h, value) in result.iter().enumerate() {
+            assert!((value - series(16 + h)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_predict_composite_dummy_matches_naive_on_exact_pattern() {
+        let pattern = [1.0, 4.0, -5.0];
+        let data: Vec<f64> = (0..12).map(|t| 3.0 - 0.5 * t as f64 + pattern[t % 3]).collect();
+        let naive = predict_composite_impl(&data, 4, "linear", Some(("naive", 3))).unwrap();
+        let dummy = predict_composite_impl(&data, 4, "linear", Some(("dummy", 3))).unwrap();
+
+        for (a, b) in naive.iter().zip(&dummy) {
+            assert!((a - b).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_predict_composite_without_season_is_trend_method() {
+        let data = [1.0, 2.0, 4.0];
+        let result = predict_composite_impl(&data, 2, "none", None).unwrap();
+
+        assert_eq!(result, vec![4.0, 4.0]);
+    }
+
+    #[test]
+    fn test_predict_composite_invalid_names() {
+        assert_eq!(
+            predict_composite_impl(&[1.0; 8], 2, "cubic", None).unwrap_err(),
+            "Unknown trend model 'cubic', expected one of: linear, mean, none"
+        );
+        assert_eq!(
+            predict_composite_impl(&[1.0; 8], 2, "linear", Some(("fourier", 4))).unwrap_err(),
+            "Unknown season model 'fourier', expected one of: naive, dummy"
+        );
+    }
+
+    #[test]
+    fn test_predict_composite_rejects_non_finite_data() {
+        let data = [1.0, 1.0, f64::NAN, 1.0];
+        for &model in SEASON_MODELS {
+            let result = predict_composite_impl(&data, 1, "linear", Some((model, 2)));
+
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), "Input data must be finite");
+        }
+    }
+
+    #[test]
+    fn test_predict_decomposition_tracks_trend_plus_season() {
+        let pattern = [3.0, -1.0, -4.0, 2.0];
+        let signal = |t: usize| 10.0 + 0.5 * t as f64 + pattern[t % 4];
+        let data: Vec<f64> = (0..24).map(signal).collect();
+
+        let result = predict_decomposition_impl(&data, 8, 4).unwrap();
+        for (h, value) in result.iter().enumerate() {
+            assert!((value - signal(24 + h)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_predict_decomposition_noisy_signal_stays_close() {
+        let mut rng = Rng::new(23);
+        let signal = |t: usize| 50.0 + 0.8 * t as f64 + 6.0 * (t as f64 * PI / 6.0).sin();
+        let data: Vec<f64> = (0..72).map(|t| signal(t) + 0.3 * rng.normal()).collect();
+
+        let result = predict_decomposition_impl(&data, 12, 12).unwrap();
+        for (h, value) in result.iter().enumerate() {
+            assert!((value - signal(72 + h)).abs() < 1.0, "step {}: {}", h, value);
+        }
+    }
+
+    #[test]
+    fn test_predict_decomposition_requires_two_seasons() {
+        let result = predict_decomposition_impl(&[1.0, 2.0, 3.0, 4.0, 5.0], 2, 3);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "At least two full seasons (6 points) are required, got 5"
+        );
+    }
+
+    #[test]
+    fn test_predict_decomposition_rejects_non_finite_data() {
+        let result = predict_decomposition_impl(&[1.0, 1.0, f64::NAN, 1.0], 1, 2);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Input data must be finite");
+    }
+
+    #[test]
+    fn test_predict_on_difference_mean_continues_line() {
+        let data: Vec<f64> = (0..10).map(|t| 4.0 + 1.5 * t as f64).collect();
+        let result = predict_on_difference_impl(&data, 4, "mean", 1).unwrap();
+
+        for (h, value) in result.iter().enumerate() {
+            assert!((value - (4.0 + 1.5 * (10 + h) as f64)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_predict_on_difference_seasonal_lag_repeats_pattern() {
+        let data = [5.0, 1.0, 3.0, 6.0, 2.0, 4.0];
+        let result = predict_on_difference_impl(&data, 5, "mean", 3).unwrap();
+
+        assert_eq!(result, vec![7.0, 3.0, 5.0, 8.0, 4.0]);
+    }
+
+    #[test]
+    fn test_predict_on_difference_lag_too_long() {
+        let result = predict_on_difference_impl(&[1.0, 2.0], 1, "mean", 2);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Differencing at lag 2 requires more than 2 data points, got 2"
+        );
+    }
+
+    #[test]
+    fn test_combine_inverse_variance_equal_variances_average() {
+        let forecasts = vec![vec![10.0, 20.0], vec![14.0, 30.0]];
+        let result = combine_inverse_variance_impl(&forecasts, &[2.5, 2.5]).unwrap();
+
+        assert_eq!(result, vec![12.0, 25.0]);
+    }
+
+    #[test]
+    fn test_combine_inverse_variance_favours_lower_variance() {
+        let forecasts = vec![vec![10.0, 12.0], vec![16.0, 18.0]];
+        let result = combine_inverse_variance_impl(&forecasts, &[1.0, 2.0]).unwrap();
+
+        // Weights 2/3 and 1/3
+        assert!((result[0] - 12.0).abs() < 1e-12);
+        assert!((result[1] - 14.0).abs() < 1e-12);
+        assert!(result[0] < 13.0);
+    }
+
+    #[test]
+    fn test_combine_inverse_variance_validation() {
+        let forecasts = vec![vec![1.0, 2.0], vec![3.0]];
+        assert_eq!(
+            combine_inverse_variance_impl(&forecasts, &[1.0, 1.0]).unwrap_err(),
+            "All forecasts must have the same horizon: forecast 1 has 1 values, expected 2"
+        );
+
+        let forecasts = vec![vec![1.0], vec![3.0]];
+        assert_eq!(
+            combine_inverse_variance_impl(&forecasts, &[1.0]).unwrap_err(),
+            "Variance count (1) must match forecast count (2)"
+        );
+        assert_eq!(
+            combine_inverse_variance_impl(&forecasts, &[1.0, 0.0]).unwrap_err(),
+            "Variances must be finite and positive, got 0"
+        );
+    }
+
+    #[test]
+    fn test_combine_optimal_diagonal_matches_inverse_variance() {
+        let forecasts = vec![vec![10.0, 12.0, 9.0], vec![16.0, 18.0, 11.0], vec![13.0, 7.0, 8.0]];
+        let variances = [1.0, 2.0, 4.0];
+        let covariance = vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 2.0, 0.0],
+            vec![0.0, 0.0, 4.0],
+        ];
+        let optimal = combine_optimal_impl(&forecasts, &covariance).unwrap();
+        let inverse_variance = combine_inverse_variance_impl(&forecasts, &variances).unwrap();
+
+        for (o, iv) in optimal.iter().zip(&inverse_variance) {
+            assert!((o - iv).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_combine_optimal_correlated_errors() {
+        // S^-1 1 = (1.5, 0.5) / 1.75, normalized to weights (0.75, 0.25)
+        let forecasts = vec![vec![10.0, 12.0], vec![16.0, 18.0]];
+        let covariance = vec![vec![1.0, 0.5], vec![0.5, 2.0]];
+        let result = combine_optimal_impl(&forecasts, &covariance).unwrap();
+
+        assert!((result[0] - 11.5).abs() < 1e-12);
+        assert!((result[1] - 13.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_combine_optimal_validation() {
+        let forecasts = vec![vec![1.0], vec![3.0]];
+        assert_eq!(
+            combine_optimal_impl(&forecasts, &[vec![1.0, 0.0]]).unwrap_err(),
+            "Error covariance has 1 rows, expected 2 to match the forecast count"
+        );
+        assert_eq!(
+            combine_optimal_impl(&forecasts, &[vec![1.0, 0.0], vec![0.0]]).unwrap_err(),
+            "Error covariance row 1 has 1 columns, expected 2"
+        );
+
+        // Perfectly correlated errors make the covariance singular
+        let result = combine_optimal_impl(&forecasts, &[vec![1.0, 2.0], vec![2.0, 4.0]]);
+        assert!(result.unwrap_err().starts_with("Error covariance is singular"));
+    }
+
+    #[test]
+    fn test_apply_overrides_replaces_only_given_steps() {
+        let forecast = [10.0, 11.0, 12.0, 13.0];
+        let overrides = HashMap::from([(0, 9.5), (2, 15.0)]);
+        let result = apply_overrides_impl(&forecast, &overrides).unwrap();
+
+        assert_eq!(result, vec![9.5, 11.0, 15.0, 13.0]);
+    }
+
+    #[test]
+    fn test_apply_overrides_step_out_of_range() {
+        let overrides = HashMap::from([(1, 0.0), (7, 0.0), (4, 0.0)]);
+        let result = apply_overrides_impl(&[1.0, 2.0, 3.0, 4.0], &overrides);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Override step 4 is out of range for a forecast of length 4"
+        );
+    }
+
+    #[test]
+    fn test_predict_bounded_stays_below_upper() {
+        let data = [0.5, 0.6, 0.7, 0.8, 0.9];
+        let unbounded = forecast_with_method_impl(&data, 5, "linear_trend").unwrap();
+        assert!(unbounded[0] > 0.99 && unbounded[4] > 1.0);
+
+        let bounded = predict_bounded_impl(&data, 5, "linear_trend", 0.0, 1.0).unwrap();
+        assert!(bounded.iter().all(|&v| 0.9 < v && v < 1.0));
+        assert!(bounded.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_predict_bounded_round_trips_static() {
+        let data = [12.0, 15.0, 17.5];
+        let result = predict_bounded_impl(&data, 2, "static", 10.0, 20.0).unwrap();
+
+        assert!((result[0] - 17.5).abs() < 1e-12);
+        assert!((result[1] - 17.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_predict_bounded_data_outside_bounds() {
+        let result = predict_bounded_impl(&[0.2, 1.0], 1, "static", 0.0, 1.0);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Data must lie strictly inside (0, 1), got 1");
+    }
+
+    #[test]
+    fn test_predict_bounded_saturated_forecast_stays_inside() {
+        let data = [0.5, 0.6, 0.7, 0.8, 0.9, 0.99, 0.999, 0.9999];
+        let result = predict_bounded_impl(&data, 60, "linear_trend", 0.0, 1.0).unwrap();
+
+        assert!(result.iter().all(|&v| 0.0 < v && v < 1.0));
+        assert_eq!(result[59], 1.0f64.next_down());
+    }
+
+    #[test]
+    fn test_predict_with_bounds_clamps_into_tightening_bounds() {
+        // The trend forecasts 18, 20, 22, 24 while the band narrows to 19
+        let data = [10.0, 12.0, 14.0, 16.0];
+        let lower = [0.0, 19.5, 19.0, 19.0];
+        let upper = [30.0, 25.0, 21.0, 19.0];
+        let result = predict_with_bounds_impl(&data, 4, "linear_trend", &lower, &upper).unwrap();
+
+        let expected = [18.0, 20.0, 21.0, 19.0];
+        for (value, e) in result.iter().zip(expected) {
+            assert!((value - e).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_predict_with_bounds_validation() {
+        let data = [1.0, 2.0];
+        assert_eq!(
+            predict_with_bounds_impl(&data, 2, "static", &[0.0], &[5.0, 5.0]).unwrap_err(),
+            "Lower bounds have 1 values, expected one per step of the horizon (2)"
+        );
+        assert_eq!(
+            predict_with_bounds_impl(&data, 2, "static", &[0.0, 3.0], &[5.0, 2.0]).unwrap_err(),
+            "Bounds at step 1 must satisfy lower <= upper, got (3, 2)"
+        );
+    }
+
+    #[test]
+    fn test_predict_counts_preserves_total() {
+        let data = [3.0, 2.0, 4.0, 1.0, 3.0, 5.0, 2.0];
+        for method in ["static", "mean", "linear_trend", "ses"] {
+            let continuous = forecast_with_method_impl(&data, 9, method).unwrap();
+            let counts = predict_counts_impl(&data, 9, method).unwrap();
+
+            let continuous_total: f64 = continuous.iter().map(|v| v.max(0.0)).sum();
+            let total = counts.iter().sum::<i64>() as f64;
+            assert!((total - continuous_total).abs() <= 1.0);
+            for (count, value) in counts.iter().zip(&continuous) {
+                assert!((*count as f64 - value.max(0.0)).abs() < 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_largest_remainder_avoids_rounding_bias() {
+        // Rounding each 2.4 on its own gives 10 instead of 12
+        assert_eq!(round_largest_remainder(&[2.4; 5]).unwrap(), vec![3, 3, 2, 2, 2]);
+        assert_eq!(round_largest_remainder(&[0.2, 1.7, 0.6]).unwrap(), vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn test_predict_counts_rejects_negative_history() {
+        let result = predict_counts_impl(&[1.0, -2.0], 1, "static");
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Count data must be finite and non-negative, got -2");
+    }
+
+    #[test]
+    fn test_predict_counts_rejects_unrepresentable_counts() {
+        let result = predict_counts_impl(&[1e300, 1e300], 2, "static");
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Method 'static' produced a forecast too large to count, got 1e300 at step 0"
+        );
+
+        let result = predict_counts_impl(&[0.0, 5e18, 1e19], 2, "linear_trend");
+        assert!(result.is_err());
+
+        // Each step fits in an i64 but their total does not
+        let result = predict_counts_impl(&[6e18, 6e18], 2, "static");
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Count forecast total 1.2e19 is too large to represent"
+        );
+    }
+
+    #[test]
+    fn test_predict_anchored_feeds_anchor_into_recursion() {
+        let data = [10.0, 11.0, 9.0, 10.0, 12.0, 10.0];
+        let plain = forecast_with_method_impl(&data, 5, "ses").unwrap();
+        let anchors = HashMap::from([(2, 40.0)]);
+        let anchored = predict_anchored_impl(&data, 5, "ses", &anchors, &HashMap::new()).unwrap();
+
+        // Steps before the anchor are untouched, later ones follow the level
+        // updated with the anchor: level + 0.3 * (40 - level)
+        assert_eq!(&anchored[..2], &plain[..2]);
+        assert_eq!(anchored[2], 40.0);
+        let level = plain[0] + 0.3 * (40.0 - plain[0]);
+        for value in &anchored[3..] {
+            assert!((value - level).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_predict_anchored_without_anchors_matches_forecast() {
+        let data = [3.0, 5.0, 4.0, 7.0, 9.0, 8.0];
+        for method in ["holt", "pct_change", "linear_trend"] {
+            let plain = forecast_with_method_impl(&data, 4, method).unwrap();
+            let anchored =
+                predict_anchored_impl(&data, 4, method, &HashMap::new(), &HashMap::new()).unwrap();
+            for (a, p) in anchored.iter().zip(&plain) {
+                assert!((a - p).abs() < 1e-9);
+            }
+        }
+
+        // A global fit only has the anchored step replaced
+        let anchors = HashMap::from([(1, 0.0)]);
+        let trend =
+            predict_anchored_impl(&data, 4, "linear_trend", &anchors, &HashMap::new()).unwrap();
+        let plain = forecast_with_method_impl(&data, 4, "linear_trend").unwrap();
+        assert_eq!(trend, vec![plain[0], 0.0, plain[2], plain[3]]);
+    }
+
+    #[test]
+    fn test_predict_anchored_step_out_of_range() {
+        let anchors = HashMap::from([(3, 1.0)]);
+        let result = predict_anchored_impl(&[1.0, 2.0], 3, "static", &anchors, &HashMap::new());
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Anchor step 3 is out of range for a horizon of 3");
+    }
+
+    #[test]
+    fn test_predict_anchored_passes_params_to_every_fit() {
+        let data: Vec<f64> = (0..16).map(|t| [10.0, 14.0, 12.0, 8.0][t % 4] + t as f64).collect();
+        let params = HashMap::from([("season_length".to_string(), 4.0)]);
+        let plain = fit_method_impl(&data, "holt_winters", &params).unwrap().forecast(6).unwrap();
+
+        let none = predict_anchored_impl(&data, 6, "holt_winters", &HashMap::new(), &params);
+        assert_eq!(none.unwrap(), plain);
+
+        let anchors = HashMap::from([(1, plain[1] + 5.0)]);
+        let anchored =
+            predict_anchored_impl(&data, 6, "holt_winters", &anchors, &params).unwrap();
+        assert_eq!(anchored[0], plain[0]);
+        assert_eq!(anchored[1], plain[1] + 5.0);
+        assert!(anchored[2..].iter().zip(&plain[2..]).all(|(a, p)| a != p));
+    }
+}
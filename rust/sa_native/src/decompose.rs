@@ -0,0 +1,131 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::seasonal::{centered_moving_average, validate_seasons};
+
+/// Trend, seasonal and residual components, one entry per observation each.
+type Components = (Vec<f64>, Vec<f64>, Vec<f64>);
+
+/// Core multiplicative decomposition logic: `(trend, seasonal, residual)`,
+/// one entry per observation, with `y = T * S * R`.
+///
+/// The trend is the centered moving average over one season; the seasonal
+/// factor of each phase is the average ratio `y / T` over that phase, scaled
+/// so the factors average 1. Trend and residual are NaN where the moving
+/// average has no full window.
+pub(crate) fn decompose_multiplicative_impl(
+    data: &[f64],
+    season_length: usize,
+) -> Result<Components, String> {
+    validate_seasons(data.len(), season_length)?;
+
+    if let Some(y) = data.iter().find(|y| !y.is_finite() || **y <= 0.0) {
+        return Err(format!(
+            "Multiplicative decomposition requires finite, strictly positive data, got {}",
+            y
+        ));
+    }
+
+    let trend = centered_moving_average(data, season_length);
+
+    let mut sums = vec![0.0; season_length];
+    let mut counts = vec![0usize; season_length];
+    for (t, (y, level)) in data.iter().zip(&trend).enumerate() {
+        if !level.is_nan() {
+            sums[t % season_length] += y / level;
+            counts[t % season_length] += 1;
+        }
+    }
+    let mut factors: Vec<f64> = sums.iter().zip(&counts).map(|(s, c)| s / *c as f64).collect();
+    let factor_mean = factors.iter().sum::<f64>() / season_length as f64;
+    for factor in factors.iter_mut() {
+        *factor /= factor_mean;
+    }
+
+    let seasonal: Vec<f64> = (0..data.len()).map(|t| factors[t % season_length]).collect();
+    let residual = data
+        .iter()
+        .zip(&trend)
+        .zip(&seasonal)
+        .map(|((y, level), factor)| y / (level * factor))
+        .collect();
+
+    Ok((trend, seasonal, residual))
+}
+
+/// Classical multiplicative decomposition `y = trend * seasonal * residual`.
+///
+/// Suited to series whose seasonal swings grow with their level. The trend
+/// is the centered moving average over one season (the `2 x m` average for
+/// even `m`), the seasonal factors are the per-phase average ratios of the
+/// data to the trend, centered so they average 1.0, and the residual is what
+/// remains. The model is undefined for zero or negative values.
+///
+/// # Arguments
+///
+/// * `data` - Strictly positive historical data, phase 0 being the first
+///   observation
+/// * `season_length` - Number of observations per season
+///
+/// # Returns
+///
+/// Tuple `(trend, seasonal, residual)`, each as long as `data`. The first and
+/// last `season_length / 2` entries of trend and residual are NaN, where the
+/// moving average has no full window.
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * season_length is less than 2 or data holds fewer than two full seasons
+/// * a data value is zero, negative or not finite
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// trend, seasonal, residual = sa_native.decompose_multiplicative(sales, 12)
+/// # sales[t] == trend[t] * seasonal[t] * residual[t] wherever trend[t] is defined
+/// ```
+#[pyfunction]
+pub fn decompose_multiplicative(data: Vec<f64>, season_length: usize) -> PyResult<Components> {
+    decompose_multiplicative_impl(&data, season_length).map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_decompose_multiplicative_components_multiply_back() {
+        let pattern = [1.3, 0.8, 1.1, 0.8];
+        let mut rng = Rng::new(5);
+        let data: Vec<f64> = (0..32)
+            .map(|t| (50.0 + 2.0 * t as f64) * pattern[t % 4] * (1.0 + 0.02 * rng.normal()))
+            .collect();
+        let (trend, seasonal, residual) = decompose_multiplicative_impl(&data, 4).unwrap();
+
+        assert!(trend[..2].iter().chain(&trend[30..]).all(|v| v.is_nan()));
+        for (t, y) in data.iter().enumerate().skip(2).take(28) {
+            assert!((trend[t] * seasonal[t] * residual[t] - y).abs() < 1e-9);
+        }
+        let factor_mean = seasonal[..4].iter().sum::<f64>() / 4.0;
+        assert!((factor_mean - 1.0).abs() < 1e-12);
+        for (factor, expected) in seasonal.iter().zip(pattern) {
+            assert!((factor - expected).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn test_decompose_multiplicative_rejects_non_positive_data() {
+        let mut data = vec![10.0; 8];
+        data[5] = -1.0;
+        let result = decompose_multiplicative_impl(&data, 4);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Multiplicative decomposition requires finite, strictly positive data, got -1"
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt::Write;
+
+use pyo3::prelude::*;
+
+/// A CSV cell for `value`: Rust's shortest round-trip formatting, or an empty
+/// cell for NaN and infinities.
+fn csv_cell(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        String::new()
+    }
+}
+
+/// Core CSV export logic: an `index,value` header, then one row per value
+/// with indices counting up from `start_index`.
+pub(crate) fn forecast_to_csv_impl(values: &[f64], start_index: usize) -> String {
+    let mut csv = String::from("index,value\n");
+    for (offset, value) in values.iter().enumerate() {
+        // Writing to a String cannot fail
+        writeln!(csv, "{},{}", start_index + offset, csv_cell(*value)).unwrap();
+    }
+
+    csv
+}
+
+/// Core batch CSV export logic, in long format: a `series,index,value`
+/// header, then one row per value of each series in turn.
+pub(crate) fn forecast_batch_to_csv_impl(series: &[Vec<f64>], start_index: usize) -> String {
+    let mut csv = String::from("series,index,value\n");
+    for (i, values) in series.iter().enumerate() {
+        for (offset, value) in values.iter().enumerate() {
+            writeln!(csv, "{},{},{}", i, start_index + offset, csv_cell(*value)).unwrap();
+        }
+    }
+
+    csv
+}
+
+/// Format a forecast as CSV text.
+///
+/// The output has an `index,value` header and one row per value, indices
+/// counting up from `start_index` (e.g. the length of the history, so they
+/// continue its positions). Values use the shortest representation that
+/// parses back to the same float; NaN and infinite values are written as
+/// empty cells. Rows end with `\n`.
+///
+/// # Arguments
+///
+/// * `values` - Forecast values
+/// * `start_index` - Index of the first row
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.forecast_to_csv([1.5, float("nan")], 10)
+/// # 'index,value\n10,1.5\n11,\n'
+/// ```
+#[pyfunction]
+pub fn forecast_to_csv(values: Vec<f64>, start_index: usize) -> PyResult<String> {
+    Ok(forecast_to_csv_impl(&values, start_index))
+}
+
+/// Format several forecasts as one CSV table in long format.
+///
+/// Long format keeps one value per row, so series of different lengths need
+/// no padding: the header is `series,index,value`, where `series` is the
+/// position of the forecast in `series` and `index` counts up from
+/// `start_index` within each one. Cells are formatted as in
+/// `forecast_to_csv`.
+///
+/// # Arguments
+///
+/// * `series` - One forecast per series
+/// * `start_index` - Index of the first row of every series
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.forecast_batch_to_csv([[1.0, 2.0], [3.0]], 0)
+/// # 'series,index,value\n0,0,1\n0,1,2\n1,0,3\n'
+/// ```
+#[pyfunction]
+pub fn forecast_batch_to_csv(series: Vec<Vec<f64>>, start_index: usize) -> PyResult<String> {
+    Ok(forecast_batch_to_csv_impl(&series, start_index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parse the data rows of emitted CSV, empty cells as NaN.
+    fn parse_rows(csv: &str) -> Vec<Vec<f64>> {
+        csv.lines()
+            .skip(1)
+            .map(|line| {
+                line.split(',')
+                    .map(|cell| if cell.is_empty() { f64::NAN } else { cell.parse().unwrap() })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_forecast_to_csv_round_trips_values() {
+        let values = [1.0, -0.1, 1.0 / 3.0, 1e-12, 6.02e23, f64::NAN, f64::INFINITY];
+        let csv = forecast_to_csv_impl(&values, 5);
+
+        assert!(csv.starts_with("index,value\n5,1\n6,-0.1\n"));
+        let rows = parse_rows(&csv);
+        assert_eq!(rows.len(), values.len());
+        for (t, (row, value)) in rows.iter().zip(&values).enumerate() {
+            assert_eq!(row[0], (5 + t) as f64);
+            if value.is_finite() {
+                assert_eq!(row[1], *value);
+            } else {
+                assert!(row[1].is_nan());
+            }
+        }
+    }
+
+    #[test]
+    fn test_forecast_batch_to_csv_long_format() {
+        let series = vec![vec![1.5, f64::NAN], vec![], vec![-2.25]];
+        let csv = forecast_batch_to_csv_impl(&series, 3);
+
+        assert_eq!(csv, "series,index,value\n0,3,1.5\n0,4,\n2,3,-2.25\n");
+        let rows = parse_rows(&csv);
+        assert_eq!(rows[2], vec![2.0, 3.0, -2.25]);
+    }
+}
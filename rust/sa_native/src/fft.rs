@@ -0,0 +1,200 @@
+use std::f64::consts::PI;
+use std::ops::{Add, Mul, Sub};
+
+/// Minimal complex number for the FFT.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Complex {
+    pub(crate) re: f64,
+    pub(crate) im: f64,
+}
+
+impl Complex {
+    pub(crate) fn new(re: f64, im: f64) -> Self {
+        Complex { re, im }
+    }
+
+    /// `exp(i * angle)`
+    fn from_angle(angle: f64) -> Self {
+        Complex::new(angle.cos(), angle.sin())
+    }
+
+    fn conj(self) -> Self {
+        Complex::new(self.re, -self.im)
+    }
+
+    pub(crate) fn norm_sqr(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+/// In-place iterative radix-2 FFT; `values.len()` must be a power of two.
+fn fft_radix2(values: &mut [Complex], inverse: bool) {
+    let n = values.len();
+
+    // Bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let step = Complex::from_angle(sign * 2.0 * PI / len as f64);
+        for start in (0..n).step_by(len) {
+            let mut w = Complex::new(1.0, 0.0);
+            for k in 0..len / 2 {
+                let even = values[start + k];
+                let odd = values[start + k + len / 2] * w;
+                values[start + k] = even + odd;
+                values[start + k + len / 2] = even - odd;
+                w = w * step;
+            }
+        }
+        len <<= 1;
+    }
+
+    if inverse {
+        let scale = 1.0 / n as f64;
+        for value in values.iter_mut() {
+            *value = Complex::new(value.re * scale, value.im * scale);
+        }
+    }
+}
+
+/// Discrete Fourier transform of a real series of any length.
+///
+/// Power-of-two lengths use radix-2 directly; other lengths go through
+/// Bluestein's algorithm, which rewrites the DFT as a convolution computed
+/// with power-of-two FFTs. Both are O(n log n).
+pub(crate) fn dft(data: &[f64]) -> Vec<Complex> {
+    let n = data.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    if n.is_power_of_two() {
+        let mut values: Vec<Complex> = data.iter().map(|&x| Complex::new(x, 0.0)).collect();
+        fft_radix2(&mut values, false);
+        return values;
+    }
+
+    // Chirp w_k = exp(-i*pi*k^2/n); k^2 is reduced mod 2n to keep angles small
+    let chirp: Vec<Complex> = (0..n)
+        .map(|k| {
+            let k2 = (k as u128 * k as u128 % (2 * n as u128)) as f64;
+            Complex::from_angle(-PI * k2 / n as f64)
+        })
+        .collect();
+
+    let m = (2 * n - 1).next_power_of_two();
+    let mut a = vec![Complex::new(0.0, 0.0); m];
+    for k in 0..n {
+        a[k] = Complex::new(data[k], 0.0) * chirp[k];
+    }
+    let mut b = vec![Complex::new(0.0, 0.0); m];
+    b[0] = chirp[0].conj();
+    for k in 1..n {
+        b[k] = chirp[k].conj();
+        b[m - k] = chirp[k].conj();
+    }
+
+    fft_radix2(&mut a, false);
+    fft_radix2(&mut b, false);
+    for (x, y) in a.iter_mut().zip(&b) {
+        *x = *x * *y;
+    }
+    fft_radix2(&mut a, true);
+
+    (0..n).map(|k| a[k] * chirp[k]).collect()
+}
+
+/// Periodogram of the demeaned series: power `|X_k|^2 / n` at frequencies
+/// `k / n` for `k = 0..=n/2`.
+pub(crate) fn periodogram(data: &[f64]) -> Vec<f64> {
+    let n = data.len();
+    let mean = data.iter().sum::<f64>() / n as f64;
+    let centered: Vec<f64> = data.iter().map(|x| x - mean).collect();
+
+    dft(&centered)
+        .into_iter()
+        .take(n / 2 + 1)
+        .map(|c| c.norm_sqr() / n as f64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_dft(data: &[f64]) -> Vec<Complex> {
+        let n = data.len();
+        (0..n)
+            .map(|k| {
+                data.iter().enumerate().fold(Complex::new(0.0, 0.0), |acc, (t, &x)| {
+                    let angle = -2.0 * PI * (k * t) as f64 / n as f64;
+                    acc + Complex::from_angle(angle) * Complex::new(x, 0.0)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dft_matches_naive_for_any_length() {
+        for n in [1, 2, 7, 8, 12, 31, 64, 100] {
+            let data: Vec<f64> = (0..n).map(|t| ((t * 7 + 3) % 11) as f64 - 5.0).collect();
+            let fast = dft(&data);
+            let slow = naive_dft(&data);
+
+            for (f, s) in fast.iter().zip(&slow) {
+                assert!((f.re - s.re).abs() < 1e-8, "n = {}", n);
+                assert!((f.im - s.im).abs() < 1e-8, "n = {}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_periodogram_peak_at_sinusoid_frequency() {
+        let data: Vec<f64> = (0..60)
+            .map(|t| (2.0 * PI * t as f64 / 10.0).sin())
+            .collect();
+        let power = periodogram(&data);
+        let peak = (0..power.len())
+            .max_by(|&i, &j| power[i].total_cmp(&power[j]))
+            .unwrap();
+
+        assert_eq!(peak, 6);
+    }
+}
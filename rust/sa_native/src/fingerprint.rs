@@ -0,0 +1,208 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a hasher. Unlike `std::collections::hash_map::DefaultHasher`,
+/// its output is fixed by the algorithm and does not change between runs or
+/// Rust versions.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Fnv1a(FNV_OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+/// Canonical form of a float for hashing: `-0.0` hashes like `0.0` and every
+/// NaN payload hashes like the standard NaN.
+fn canonical(value: f64) -> f64 {
+    if value.is_nan() {
+        f64::NAN
+    } else if value == 0.0 {
+        0.0
+    } else {
+        value
+    }
+}
+
+/// Byte order used to serialize numbers before hashing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ByteOrder {
+    Little,
+    Big,
+}
+
+impl ByteOrder {
+    /// Parse the `"le"` / `"be"` names used by the Python API.
+    pub(crate) fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "le" => Ok(ByteOrder::Little),
+            "be" => Ok(ByteOrder::Big),
+            _ => Err(format!("Byte order must be 'le' or 'be', got '{}'", name)),
+        }
+    }
+
+    fn u64_bytes(self, value: u64) -> [u8; 8] {
+        match self {
+            ByteOrder::Little => value.to_le_bytes(),
+            ByteOrder::Big => value.to_be_bytes(),
+        }
+    }
+
+    fn f64_bytes(self, value: f64) -> [u8; 8] {
+        self.u64_bytes(value.to_bits())
+    }
+}
+
+/// Core fingerprint logic.
+///
+/// Numbers are serialized in the given byte order rather than the host's, so
+/// a digest depends only on the inputs and `order`, never on the platform.
+pub(crate) fn fingerprint_impl(
+    data: &[f64],
+    horizon: usize,
+    method: &str,
+    order: ByteOrder,
+) -> String {
+    let mut hasher = Fnv1a::new();
+
+    // Length prefixes keep the boundary between fields unambiguous
+    hasher.write(&order.u64_bytes(data.len() as u64));
+    for value in data {
+        hasher.write(&order.f64_bytes(canonical(*value)));
+    }
+    hasher.write(&order.u64_bytes(horizon as u64));
+    hasher.write(&order.u64_bytes(method.len() as u64));
+    hasher.write(method.as_bytes());
+
+    format!("{:016x}", hasher.0)
+}
+
+/// Compute a stable hex digest of a forecast request, for use as a cache key.
+///
+/// The digest is a 64-bit FNV-1a hash over the data values, horizon and method
+/// name. `-0.0` and `0.0` give the same digest, as do all NaN payloads.
+///
+/// Numbers are serialized in an explicit byte order before hashing, so the
+/// same inputs produce the same digest on little- and big-endian hosts alike.
+/// `byte_order` selects `"le"` (the default) or `"be"`; the two orders give
+/// different digests, so every node sharing a cache must use the same one.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Forecast horizon
+/// * `method` - Forecasting method name
+/// * `byte_order` - `"le"` (default) or `"be"`
+///
+/// # Returns
+///
+/// A 16-character lowercase hex string
+///
+/// # Errors
+///
+/// Returns PyValueError if byte_order is not `"le"` or `"be"`.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// key = sa_native.fingerprint([1.0, 2.0, 3.0], 3, "static")
+/// assert key == sa_native.fingerprint([1.0, 2.0, 3.0], 3, "static", "le")
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, horizon, method, byte_order="le"))]
+pub fn fingerprint(
+    data: Vec<f64>,
+    horizon: usize,
+    method: String,
+    byte_order: &str,
+) -> PyResult<String> {
+    let order = ByteOrder::parse(byte_order).map_err(PyValueError::new_err)?;
+    Ok(fingerprint_impl(&data, horizon, &method, order))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LE: ByteOrder = ByteOrder::Little;
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        let data = vec![1.0, 2.5, -3.0];
+        let first = fingerprint_impl(&data, 3, "static", LE);
+        let second = fingerprint_impl(&data.clone(), 3, "static", LE);
+
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 16);
+        assert!(first.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn test_fnv1a_reference_vector() {
+        // Pins the hash algorithm so cached fingerprints stay valid
+        let mut hasher = Fnv1a::new();
+        hasher.write(b"a");
+
+        assert_eq!(hasher.0, 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_fingerprint_differs_for_different_inputs() {
+        let base = fingerprint_impl(&[1.0, 2.0, 3.0], 3, "static", LE);
+
+        assert_ne!(base, fingerprint_impl(&[1.0, 2.0, 3.5], 3, "static", LE));
+        assert_ne!(base, fingerprint_impl(&[1.0, 2.0, 3.0], 4, "static", LE));
+        assert_ne!(base, fingerprint_impl(&[1.0, 2.0, 3.0], 3, "linear_trend", LE));
+        assert_ne!(base, fingerprint_impl(&[1.0, 2.0], 3, "static", LE));
+    }
+
+    #[test]
+    fn test_fingerprint_canonicalizes_zero_and_nan() {
+        assert_eq!(
+            fingerprint_impl(&[0.0, f64::NAN], 1, "static", LE),
+            fingerprint_impl(&[-0.0, -f64::NAN], 1, "static", LE)
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_byte_orders_are_stable_and_distinct() {
+        let data = [1.0, 2.5, -3.0];
+        let le = fingerprint_impl(&data, 3, "static", ByteOrder::Little);
+        let be = fingerprint_impl(&data, 3, "static", ByteOrder::Big);
+
+        assert_eq!(le, fingerprint_impl(&data, 3, "static", ByteOrder::Little));
+        assert_eq!(be, fingerprint_impl(&data, 3, "static", ByteOrder::Big));
+        assert_ne!(le, be);
+    }
+
+    #[test]
+    fn test_fingerprint_reference_digest() {
+        // Pins the serialization so digests match on every host
+        assert_eq!(fingerprint_impl(&[1.0, 2.0], 3, "static", LE), "5ed6b198722ba559");
+        assert_eq!(
+            fingerprint_impl(&[1.0, 2.0], 3, "static", ByteOrder::Big),
+            "3ca1394c97dd4dc5"
+        );
+    }
+
+    #[test]
+    fn test_byte_order_parse() {
+        assert_eq!(ByteOrder::parse("le").unwrap(), ByteOrder::Little);
+        assert_eq!(ByteOrder::parse("be").unwrap(), ByteOrder::Big);
+        assert_eq!(
+            ByteOrder::parse("native").unwrap_err(),
+            "Byte order must be 'le' or 'be', got 'native'"
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::linalg::least_squares;
+
+/// Tolerance on the sum of top-down proportions.
+const PROPORTION_TOLERANCE: f64 = 1e-9;
+
+/// Core bottom-up reconciliation: element-wise sum of the regional forecasts.
+pub(crate) fn reconcile_bottom_up_impl(regional_forecasts: &[Vec<f64>]) -> Result<Vec<f64>, String> {
+    let first = match regional_forecasts.first() {
+        Some(first) => first,
+        None => return Err("At least one regional forecast is required".to_string()),
+    };
+
+    if let Some((i, region)) = regional_forecasts
+        .iter()
+        .enumerate()
+        .find(|(_, region)| region.len() != first.len())
+    {
+        return Err(format!(
+            "Regional forecast {} has horizon {}, expected {}",
+            i,
+            region.len(),
+            first.len()
+        ));
+    }
+
+    let mut total = vec![0.0; first.len()];
+    for region in regional_forecasts {
+        for (sum, value) in total.iter_mut().zip(region) {
+            *sum += value;
+        }
+    }
+
+    Ok(total)
+}
+
+/// Core top-down reconciliation: split a total forecast by fixed proportions.
+pub(crate) fn reconcile_top_down_impl(
+    total_forecast: &[f64],
+    proportions: &[f64],
+) -> Result<Vec<Vec<f64>>, String> {
+    if proportions.is_empty() {
+        return Err("At least one proportion is required".to_string());
+    }
+
+    if proportions.iter().any(|p| !p.is_finite() || *p < 0.0) {
+        return Err("Proportions must be finite and non-negative".to_string());
+    }
+
+    let sum: f64 = proportions.iter().sum();
+    if (sum - 1.0).abs() > PROPORTION_TOLERANCE {
+        return Err(format!("Proportions must sum to 1, got {}", sum));
+    }
+
+    Ok(proportions
+        .iter()
+        .map(|p| total_forecast.iter().map(|value| value * p).collect())
+        .collect())
+}
+
+/// Core OLS reconciliation: at each step, the projection `S (S'S)^-1 S' y` of
+/// the stacked base forecasts onto the column space of the summing matrix.
+pub(crate) fn reconcile_ols_impl(
+    base_forecasts: &[Vec<f64>],
+    summing_matrix: &[Vec<f64>],
+) -> Result<Vec<Vec<f64>>, String> {
+    let bottom_count = match summing_matrix.first() {
+        Some(row) if !row.is_empty() => row.len(),
+        _ => return Err("Summing matrix must have at least one row and column".to_string()),
+    };
+
+    if let Some(i) = summing_matrix.iter().position(|row| row.len() != bottom_count) {
+        return Err(format!(
+            "Summing matrix row {} has {} columns, expected {}",
+            i,
+            summing_matrix[i].len(),
+            bottom_count
+        ));
+    }
+
+    if base_forecasts.len() != summing_matrix.len() {
+        return Err(format!(
+            "Base forecast count ({}) must match summing matrix rows ({})",
+            base_forecasts.len(),
+            summing_matrix.len()
+        ));
+    }
+
+    let horizon = base_forecasts[0].len();
+    if horizon == 0 {
+        return Err("Base forecasts cannot be empty".to_string());
+    }
+
+    if let Some(i) = base_forecasts.iter().position(|f| f.len() != horizon) {
+        return Err(format!(
+            "Base forecast {} has horizon {}, expected {}",
+            i,
+            base_forecasts[i].len(),
+            horizon
+        ));
+    }
+
+    let mut reconciled = vec![Vec::with_capacity(horizon); base_forecasts.len()];
+    for h in 0..horizon {
+        let step: Vec<f64> = base_forecasts.iter().map(|f| f[h]).collect();
+        let bottom = least_squares(summing_matrix, &step).map_err(|_| {
+            "Summing matrix columns must be linearly independent".to_string()
+        })?;
+        for (node, row) in reconciled.iter_mut().zip(summing_matrix) {
+            node.push(row.iter().zip(&bottom).map(|(s, b)| s * b).sum());
+        }
+    }
+
+    Ok(reconciled)
+}
+
+/// Reconcile regional forecasts bottom-up into an aggregate forecast.
+///
+/// # Arguments
+///
+/// * `regional_forecasts` - One forecast per region, all of the same horizon
+///
+/// # Returns
+///
+/// The element-wise sum of the regional forecasts
+///
+/// # Errors
+///
+/// Returns PyValueError if no regions are given or their horizons differ.
+#[pyfunction]
+pub fn reconcile_bottom_up(regional_forecasts: Vec<Vec<f64>>) -> PyResult<Vec<f64>> {
+    reconcile_bottom_up_impl(&regional_forecasts).map_err(PyValueError::new_err)
+}
+
+/// Reconcile a total forecast top-down into regional forecasts.
+///
+/// # Arguments
+///
+/// * `total_forecast` - Forecast of the aggregate series
+/// * `proportions` - Share of the total assigned to each region, summing to 1
+///
+/// # Returns
+///
+/// One forecast per region, each the total scaled by its proportion
+///
+/// # Errors
+///
+/// Returns PyValueError if proportions are empty, negative, non-finite or do
+/// not sum to 1.
+#[pyfunction]
+pub fn reconcile_top_down(
+    total_forecast: Vec<f64>,
+    proportions: Vec<f64>,
+) -> PyResult<Vec<Vec<f64>>> {
+    reconcile_top_down_impl(&total_forecast, &proportions).map_err(PyValueError::new_err)
+}
+
+/// Reconcile forecasts of every node of a hierarchy by OLS projection.
+///
+/// The summing matrix `S` has one row per node and one column per bottom
+/// series: row `i` marks the bottom series whose sum is node `i`, so the
+/// bottom series' own rows form an identity block. At each horizon step the
+/// base forecasts `y` are replaced by `S (S'S)^-1 S' y`, the coherent
+/// forecasts closest to them in least squares. Every aggregate of the result
+/// then equals the sum of its bottom series.
+///
+/// # Arguments
+///
+/// * `base_forecasts` - One forecast per node, in summing matrix row order
+/// * `summing_matrix` - Node-by-bottom-series 0/1 aggregation matrix
+///
+/// # Returns
+///
+/// The reconciled forecast of each node, in the same order
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * the summing matrix is empty or ragged
+/// * the forecast count differs from its row count, or horizons differ
+/// * its columns are linearly dependent
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// S = [[1.0, 1.0], [1.0, 0.0], [0.0, 1.0]]  # total, A, B
+/// sa_native.reconcile_ols([[10.0], [4.0], [5.0]], S)
+/// # [[9.666...], [4.333...], [5.333...]]: the gap of 1 is shared out
+/// ```
+#[pyfunction]
+pub fn reconcile_ols(
+    base_forecasts: Vec<Vec<f64>>,
+    summing_matrix: Vec<Vec<f64>>,
+) -> PyResult<Vec<Vec<f64>>> {
+    reconcile_ols_impl(&base_forecasts, &summing_matrix).map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconcile_bottom_up_sums_regions() {
+        let regions = vec![vec![1.0, 2.0, 3.0], vec![10.0, 20.0, 30.0], vec![0.5, 0.5, 0.5]];
+        let total = reconcile_bottom_up_impl(&regions).unwrap();
+
+        assert_eq!(total, vec![11.5, 22.5, 33.5]);
+    }
+
+    #[test]
+    fn test_reconcile_bottom_up_mismatched_horizons() {
+        let regions = vec![vec![1.0, 2.0], vec![1.0]];
+        let result = reconcile_bottom_up_impl(&regions);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Regional forecast 1 has horizon 1, expected 2");
+    }
+
+    #[test]
+    fn test_reconcile_top_down_reconstructs_total() {
+        let total = vec![100.0, 200.0, 300.0];
+        let regions = reconcile_top_down_impl(&total, &[0.5, 0.3, 0.2]).unwrap();
+
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions[0], vec![50.0, 100.0, 150.0]);
+        let rebuilt = reconcile_bottom_up_impl(&regions).unwrap();
+        for (a, b) in rebuilt.iter().zip(&total) {
+            assert!((a - b).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_reconcile_top_down_proportions_must_sum_to_one() {
+        let result = reconcile_top_down_impl(&[1.0], &[0.5, 0.4]);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().starts_with("Proportions must sum to 1"));
+    }
+
+    #[test]
+    fn test_reconcile_ols_two_level_hierarchy_is_coherent() {
+        let summing = vec![vec![1.0, 1.0], vec![1.0, 0.0], vec![0.0, 1.0]];
+        let base = vec![vec![10.0, 12.0, 20.0], vec![4.0, 5.0, 9.0], vec![5.0, 6.0, 11.0]];
+        let reconciled = reconcile_ols_impl(&base, &summing).unwrap();
+
+        let bottom_sum = reconcile_bottom_up_impl(&reconciled[1..]).unwrap();
+        for (total, sum) in reconciled[0].iter().zip(&bottom_sum) {
+            assert!((total - sum).abs() < 1e-12);
+        }
+        // The gap between the total and its parts is shared equally by the
+        // three nodes; the coherent last step is left alone
+        let third = 1.0 / 3.0;
+        let expected = [
+            [10.0 - third, 12.0 - third, 20.0],
+            [4.0 + third, 5.0 + third, 9.0],
+            [5.0 + third, 6.0 + third, 11.0],
+        ];
+        for (node, values) in reconciled.iter().zip(&expected) {
+            for (value, target) in node.iter().zip(values) {
+                assert!((value - target).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reconcile_ols_dimension_mismatch() {
+        let summing = vec![vec![1.0, 1.0], vec![1.0, 0.0], vec![0.0, 1.0]];
+        let result = reconcile_ols_impl(&[vec![1.0], vec![1.0]], &summing);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Base forecast count (2) must match summing matrix rows (3)"
+        );
+    }
+}
@@ -0,0 +1,879 @@
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::methods::{forecast_with_method_impl, min_history_impl};
+use crate::predict_mean_impl;
+use crate::rng::Rng;
+use crate::stats::{quantile_sorted, validate_confidence, variance_impl, z_score_impl};
+
+/// Point forecast with lower and upper prediction bands, `(point, lower, upper)`.
+pub(crate) type Bands = (Vec<f64>, Vec<f64>, Vec<f64>);
+
+/// Core mean-forecast interval logic, returning `(point, lower, upper)`.
+///
+/// Treats the data as i.i.d. normal noise around its mean, so the prediction
+/// interval half-width is `z * s * sqrt(1 + 1/n)` at every step, with `s` the
+/// sample standard deviation.
+pub(crate) fn predict_mean_intervals_impl(
+    data: &[f64],
+    horizon: usize,
+    confidence: f64,
+) -> Result<Bands, String> {
+    if data.len() < 2 {
+        return Err("Prediction intervals require at least two data points".to_string());
+    }
+
+    let z = z_score_impl(confidence)?;
+    let point = predict_mean_impl(data, horizon)?;
+
+    let n = data.len() as f64;
+    let std = variance_impl(data).sqrt();
+    let half_width = z * std * (1.0 + 1.0 / n).sqrt();
+
+    let lower = point.iter().map(|p| p - half_width).collect();
+    let upper = point.iter().map(|p| p + half_width).collect();
+    Ok((point, lower, upper))
+}
+
+/// Core asymmetric interval logic, returning `(point, lower, upper)`.
+///
+/// The point forecast is the mean; each band is offset from it by the
+/// empirical `lower_q` or `upper_q` quantile of the in-sample residuals
+/// `y - mean`, so skewed residuals give bands of different widths.
+pub(crate) fn predict_asymmetric_intervals_impl(
+    data: &[f64],
+    horizon: usize,
+    lower_q: f64,
+    upper_q: f64,
+) -> Result<Bands, String> {
+    if data.len() < 2 {
+        return Err("Prediction intervals require at least two data points".to_string());
+    }
+
+    if !(0.0 < lower_q && lower_q < upper_q && upper_q < 1.0) {
+        return Err(format!(
+            "Quantiles must satisfy 0 < lower_q < upper_q < 1, got ({}, {})",
+            lower_q, upper_q
+        ));
+    }
+
+    let point = predict_mean_impl(data, horizon)?;
+
+    let mut residuals: Vec<f64> = data.iter().map(|y| y - point[0]).collect();
+    residuals.sort_by(f64::total_cmp);
+    let lower_offset = quantile_sorted(&residuals, lower_q);
+    let upper_offset = quantile_sorted(&residuals, upper_q);
+
+    let lower = point.iter().map(|p| p + lower_offset).collect();
+    let upper = point.iter().map(|p| p + upper_offset).collect();
+    Ok((point, lower, upper))
+}
+
+/// One-step changes `y[t] - y[t - 1]`, the in-sample errors of the static
+/// (carry-forward) forecast.
+fn naive_errors(data: &[f64]) -> Result<Vec<f64>, String> {
+    if data.len() < 2 {
+        return Err("Simulation requires at least two data points".to_string());
+    }
+
+    if data.iter().any(|v| !v.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    Ok(data.windows(2).map(|w| w[1] - w[0]).collect())
+}
+
+/// Random-walk paths starting at the last value, each step adding the error
+/// produced by `draw`.
+fn random_walk_paths(
+    last: f64,
+    horizon: usize,
+    n_paths: usize,
+    mut draw: impl FnMut() -> f64,
+) -> Vec<Vec<f64>> {
+    (0..n_paths)
+        .map(|_| {
+            let mut level = last;
+            (0..horizon)
+                .map(|_| {
+                    level += draw();
+                    level
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Core path simulation logic: Gaussian random walks from the last value,
+/// with the step size the RMS of the one-step changes.
+pub(crate) fn simulate_paths_impl(
+    data: &[f64],
+    horizon: usize,
+    n_paths: usize,
+    seed: Option<u64>,
+) -> Result<Vec<Vec<f64>>, String> {
+    let errors = naive_errors(data)?;
+
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    if n_paths == 0 {
+        return Err("Number of paths must be greater than 0".to_string());
+    }
+
+    let scale = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+    let mut rng = Rng::from_seed(seed);
+    let last = data[data.len() - 1];
+    Ok(random_walk_paths(last, horizon, n_paths, || scale * rng.normal()))
+}
+
+/// Core bootstrap interval logic, returning `(point, lower, upper)`.
+///
+/// The point forecast carries the last value forward. Each bootstrap path
+/// accumulates one-step changes resampled with replacement from the
+/// history, and the bands are the central `confidence` quantiles of the
+/// paths at every step.
+pub(crate) fn predict_bootstrap_intervals_impl(
+    data: &[f64],
+    horizon: usize,
+    confidence: f64,
+    n_boot: usize,
+    seed: Option<u64>,
+) -> Result<Bands, String> {
+    let errors = naive_errors(data)?;
+    validate_confidence(confidence)?;
+
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    if n_boot == 0 {
+        return Err("Number of bootstrap samples must be greater than 0".to_string());
+    }
+
+    let mut rng = Rng::from_seed(seed);
+    let last = data[data.len() - 1];
+    let paths = random_walk_paths(last, horizon, n_boot, || errors[rng.index(errors.len())]);
+
+    let tail = (1.0 - confidence) / 2.0;
+    let mut bands = forecast_quantiles_impl(&paths, &[tail, 1.0 - tail])?;
+    let upper = bands.pop().unwrap();
+    let lower = bands.pop().unwrap();
+    Ok((vec![last; horizon], lower, upper))
+}
+
+/// Core batch bootstrap logic: `predict_bootstrap_intervals_impl` for each
+/// series, series `i` drawing from stream `i` of `seed`.
+pub(crate) fn predict_bootstrap_batch_impl(
+    series: &[Vec<f64>],
+    horizon: usize,
+    n_samples: usize,
+    confidence: f64,
+    seed: u64,
+) -> Result<Vec<Bands>, String> {
+    if series.is_empty() {
+        return Err("Batch cannot be empty".to_string());
+    }
+
+    series
+        .iter()
+        .enumerate()
+        .map(|(i, data)| {
+            let sub_seed = Rng::for_stream(seed, i as u64).next_u64();
+            predict_bootstrap_intervals_impl(data, horizon, confidence, n_samples, Some(sub_seed))
+                .map_err(|e| format!("Series {}: {}", i, e))
+        })
+        .collect()
+}
+
+/// Rank of the split-conformal quantile among `m` calibration scores:
+/// `ceil((m + 1) * confidence)`, which may exceed `m` for small sets.
+fn conformal_rank(m: usize, confidence: f64) -> usize {
+    ((m + 1) as f64 * confidence).ceil() as usize
+}
+
+/// Smallest calibration set whose `conformal_rank` fits inside it.
+///
+/// Exactly, `(m + 1) * c <= m` holds from `m = ceil(c / (1 - c))`. Rounding
+/// in `conformal_rank` can move that threshold far when `c` is close to 1,
+/// so the estimate only brackets it and the threshold is found by bisection.
+fn min_calibration_len(confidence: f64) -> usize {
+    let fits = |m: usize| conformal_rank(m, confidence) <= m;
+    let mut high = ((confidence / (1.0 - confidence)).ceil() as usize).max(1);
+    while !fits(high) {
+        high *= 2;
+    }
+
+    // No set of size 0 fits, as the rank is always at least 1
+    let mut low = 0;
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if fits(mid) {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    high
+}
+
+/// Core split-conformal interval logic, returning `(point, lower, upper)`.
+///
+/// `method` is fitted on the first `n - floor(calibration_fraction * n)`
+/// points and forecasts the rest; the absolute errors of those forecasts are
+/// the nonconformity scores. The bands are the full-data forecast plus or
+/// minus the `conformal_rank`-th smallest score.
+pub(crate) fn predict_conformal_impl(
+    data: &[f64],
+    horizon: usize,
+    method: &str,
+    confidence: f64,
+    calibration_fraction: f64,
+) -> Result<Bands, String> {
+    validate_confidence(confidence)?;
+
+    if !(calibration_fraction > 0.0 && calibration_fraction < 1.0) {
+        return Err(format!(
+            "Calibration fraction must be in (0, 1), got {}",
+            calibration_fraction
+        ));
+    }
+
+    let n = data.len();
+    let calibration_len = (calibration_fraction * n as f64).floor() as usize;
+    let train_len = n - calibration_len;
+    let min_history = min_history_impl(method, &HashMap::new())?;
+    if train_len < min_history {
+        return Err(format!(
+            "Calibration fraction {} leaves {} training points, but method '{}' needs {}",
+            calibration_fraction, train_len, method, min_history
+        ));
+    }
+
+    if calibration_len == 0 || conformal_rank(calibration_len, confidence) > calibration_len {
+        return Err(format!(
+            "Calibration set of {} points is too small for confidence {}: at least {} are needed",
+            calibration_len,
+            confidence,
+            min_calibration_len(confidence)
+        ));
+    }
+
+    let calibration = forecast_with_method_impl(&data[..train_len], calibration_len, method)?;
+    let mut scores: Vec<f64> = data[train_len..]
+        .iter()
+        .zip(&calibration)
+        .map(|(y, f)| (y - f).abs())
+        .collect();
+    scores.sort_by(f64::total_cmp);
+    let half_width = scores[conformal_rank(calibration_len, confidence) - 1];
+
+    let point = forecast_with_method_impl(data, horizon, method)?;
+    let lower = point.iter().map(|p| p - half_width).collect();
+    let upper = point.iter().map(|p| p + half_width).collect();
+    Ok((point, lower, upper))
+}
+
+/// Core simulation quantile logic: for each quantile, the interpolated
+/// empirical quantile across paths at every horizon step.
+pub(crate) fn forecast_quantiles_impl(
+    paths: &[Vec<f64>],
+    quantiles: &[f64],
+) -> Result<Vec<Vec<f64>>, String> {
+    if paths.is_empty() {
+        return Err("At least one path is required".to_string());
+    }
+
+    let horizon = paths[0].len();
+    if horizon == 0 {
+        return Err("Paths cannot be empty".to_string());
+    }
+
+    if let Some(i) = paths.iter().position(|path| path.len() != horizon) {
+        return Err(format!(
+            "All paths must have the same length: path {} has {} values, expected {}",
+            i,
+            paths[i].len(),
+            horizon
+        ));
+    }
+
+    // Interpolating toward an infinite order statistic can give NaN
+    if paths.iter().flatten().any(|v| !v.is_finite()) {
+        return Err("Paths must be finite".to_string());
+    }
+
+    if let Some(q) = quantiles.iter().find(|q| !(0.0..=1.0).contains(*q)) {
+        return Err(format!("Quantiles must be in [0, 1], got {}", q));
+    }
+
+    // Sort once per step, then read every quantile from the sorted column
+    let columns: Vec<Vec<f64>> = (0..horizon)
+        .map(|h| {
+            let mut column: Vec<f64> = paths.iter().map(|path| path[h]).collect();
+            column.sort_by(f64::total_cmp);
+            column
+        })
+        .collect();
+
+    Ok(quantiles
+        .iter()
+        .map(|&q| columns.iter().map(|column| quantile_sorted(column, q)).collect())
+        .collect())
+}
+
+/// Forecast the historical mean with normal prediction intervals.
+///
+/// Assumes the series is stationary noise around a constant level. The bands
+/// are `mean ± z * s * sqrt(1 + 1/n)`, where `s` is the sample standard
+/// deviation and `z` the two-sided normal critical value for `confidence`.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Number of future values to predict
+/// * `confidence` - Nominal coverage of the bands, in (0, 1)
+///
+/// # Returns
+///
+/// Tuple `(point, lower, upper)` of horizon-length vectors
+///
+/// # Errors
+///
+/// Returns PyValueError if data has fewer than two points, horizon is 0 or
+/// confidence is outside (0, 1).
+#[pyfunction]
+pub fn predict_mean_intervals(
+    data: Vec<f64>,
+    horizon: usize,
+    confidence: f64,
+) -> PyResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    predict_mean_intervals_impl(&data, horizon, confidence).map_err(PyValueError::new_err)
+}
+
+/// Forecast the historical mean with bands from empirical residual quantiles.
+///
+/// Unlike `predict_mean_intervals`, no symmetry or normality is assumed: the
+/// lower band is the mean plus the `lower_q` quantile of the in-sample
+/// residuals `y - mean`, and the upper band the mean plus their `upper_q`
+/// quantile. Right-skewed errors give a wider upper band, and vice versa.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Number of future values to predict
+/// * `lower_q` - Residual quantile for the lower band
+/// * `upper_q` - Residual quantile for the upper band
+///
+/// # Returns
+///
+/// Tuple `(point, lower, upper)` of horizon-length vectors
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * data has fewer than two points
+/// * the quantiles do not satisfy `0 < lower_q < upper_q < 1`
+/// * horizon is 0
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// point, lower, upper = sa_native.predict_asymmetric_intervals(sales, 6, 0.05, 0.95)
+/// ```
+#[pyfunction]
+pub fn predict_asymmetric_intervals(
+    data: Vec<f64>,
+    horizon: usize,
+    lower_q: f64,
+    upper_q: f64,
+) -> PyResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    predict_asymmetric_intervals_impl(&data, horizon, lower_q, upper_q)
+        .map_err(PyValueError::new_err)
+}
+
+/// Quantile bands from simulated forecast trajectories.
+///
+/// At each horizon step the values of all paths are sorted and each
+/// requested quantile is read by linear interpolation between order
+/// statistics (NumPy's default method). Because the same sorted values are
+/// used for every quantile, bands are monotone: a lower quantile is never
+/// above a higher one at any step.
+///
+/// # Arguments
+///
+/// * `paths` - Simulated trajectories, all of the same horizon length
+/// * `quantiles` - Quantile levels in `[0, 1]`, e.g. `[0.05, 0.5, 0.95]`
+///
+/// # Returns
+///
+/// One horizon-length vector per requested quantile, in request order
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * paths is empty, or the paths are empty or of different lengths
+/// * a path contains a non-finite value
+/// * a quantile is outside `[0, 1]`
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// paths = [[1.0, 2.0], [3.0, 6.0], [2.0, 4.0]]
+/// sa_native.forecast_quantiles(paths, [0.5, 1.0])
+/// # [[2.0, 4.0], [3.0, 6.0]]
+/// ```
+#[pyfunction]
+pub fn forecast_quantiles(paths: Vec<Vec<f64>>, quantiles: Vec<f64>) -> PyResult<Vec<Vec<f64>>> {
+    forecast_quantiles_impl(&paths, &quantiles).map_err(PyValueError::new_err)
+}
+
+/// Simulate future trajectories as Gaussian random walks.
+///
+/// Each path starts from the last observation and adds normal steps whose
+/// standard deviation is the root mean square of the historical one-step
+/// changes, the in-sample error of `predict_static`. Feed the paths to
+/// `forecast_quantiles` for simulation-based bands.
+///
+/// Like every stochastic function in this module, the same `seed` with the
+/// same inputs gives identical paths; without a seed each call draws fresh
+/// randomness.
+///
+/// # Arguments
+///
+/// * `data` - Historical data, at least two finite points
+/// * `horizon` - Number of future values per path
+/// * `n_paths` - Number of paths
+/// * `seed` - Optional seed for reproducible output
+///
+/// # Returns
+///
+/// `n_paths` vectors of length `horizon`
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * data has fewer than two points or a non-finite value
+/// * horizon or n_paths is 0
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// paths = sa_native.simulate_paths(sales, 12, 1000, seed=7)
+/// bands = sa_native.forecast_quantiles(paths, [0.1, 0.5, 0.9])
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, horizon, n_paths, seed=None))]
+pub fn simulate_paths(
+    data: Vec<f64>,
+    horizon: usize,
+    n_paths: usize,
+    seed: Option<u64>,
+) -> PyResult<Vec<Vec<f64>>> {
+    simulate_paths_impl(&data, horizon, n_paths, seed).map_err(PyValueError::new_err)
+}
+
+/// Carry-forward forecast with bootstrap prediction intervals.
+///
+/// Each of `n_boot` paths accumulates historical one-step changes resampled
+/// with replacement, starting from the last observation. The bands are the
+/// `(1 - confidence) / 2` and `(1 + confidence) / 2` quantiles of the paths
+/// at every step, so they need no normality assumption and widen with the
+/// horizon.
+///
+/// The same `seed` with the same inputs gives identical bands; without a
+/// seed each call resamples afresh.
+///
+/// # Arguments
+///
+/// * `data` - Historical data, at least two finite points
+/// * `horizon` - Number of future values to predict
+/// * `confidence` - Nominal coverage of the bands, in (0, 1)
+/// * `n_boot` - Number of bootstrap paths (default 1000)
+/// * `seed` - Optional seed for reproducible output
+///
+/// # Returns
+///
+/// Tuple `(point, lower, upper)` of horizon-length vectors
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * data has fewer than two points or a non-finite value
+/// * confidence is outside (0, 1)
+/// * horizon or n_boot is 0
+#[pyfunction]
+#[pyo3(signature = (data, horizon, confidence, n_boot=1000, seed=None))]
+pub fn predict_bootstrap_intervals(
+    data: Vec<f64>,
+    horizon: usize,
+    confidence: f64,
+    n_boot: usize,
+    seed: Option<u64>,
+) -> PyResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    predict_bootstrap_intervals_impl(&data, horizon, confidence, n_boot, seed)
+        .map_err(PyValueError::new_err)
+}
+
+/// Bootstrap prediction intervals for many series, reproducibly.
+///
+/// Each series gets the bands of `predict_bootstrap_intervals` with
+/// `n_samples` paths. Series `i` is resampled with its own sub-seed derived
+/// deterministically from `seed` and `i`, so the whole batch is reproducible
+/// from one seed, the draws of different series are independent, and a
+/// series' bands do not depend on the other series in the batch, only on its
+/// position.
+///
+/// # Arguments
+///
+/// * `series` - Historical data of each series, at least two finite points
+/// * `horizon` - Number of future values to predict
+/// * `n_samples` - Number of bootstrap paths per series
+/// * `confidence` - Nominal coverage of the bands, in (0, 1)
+/// * `seed` - Base seed of the batch
+///
+/// # Returns
+///
+/// One `(point, lower, upper)` tuple per series
+///
+/// # Errors
+///
+/// Returns PyValueError if the batch is empty, or naming the first series
+/// that `predict_bootstrap_intervals` rejects.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// bands = sa_native.predict_bootstrap_batch([sales, returns], 6, 1000, 0.9, 7)
+/// point, lower, upper = bands[0]
+/// ```
+#[pyfunction]
+pub fn predict_bootstrap_batch(
+    series: Vec<Vec<f64>>,
+    horizon: usize,
+    n_samples: usize,
+    confidence: f64,
+    seed: u64,
+) -> PyResult<Vec<Bands>> {
+    predict_bootstrap_batch_impl(&series, horizon, n_samples, confidence, seed)
+        .map_err(PyValueError::new_err)
+}
+
+/// Forecast with split-conformal prediction intervals.
+///
+/// The data is split in time: `method` is fitted on the leading training
+/// portion and forecasts the trailing `calibration_fraction` of the series,
+/// and the absolute errors of those forecasts are the nonconformity scores.
+/// With `m` scores, the band half-width is the `ceil((m + 1) * confidence)`-th
+/// smallest one, which covers a new point with probability at least
+/// `confidence` whenever the errors are exchangeable, without assuming any
+/// distribution. The point forecast refits `method` on all the data. The
+/// scores come from forecasts up to `m` steps ahead, so for methods whose
+/// error grows with the lead time the bands are conservative at short
+/// horizons.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Number of future values to predict
+/// * `method` - Forecasting method name (e.g. "mean", "ses")
+/// * `confidence` - Target coverage of the bands, in (0, 1)
+/// * `calibration_fraction` - Share of the data held out for calibration,
+///   in (0, 1)
+///
+/// # Returns
+///
+/// Tuple `(point, lower, upper)` of horizon-length vectors
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * confidence or calibration_fraction is outside (0, 1)
+/// * the training portion is too short for the method
+/// * the calibration portion is too small to reach `confidence`, i.e.
+///   `ceil((m + 1) * confidence) > m`
+/// * the method is unknown or fails, or horizon is 0
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// point, lower, upper = sa_native.predict_conformal(sales, 6, "ses", 0.9, 0.3)
+/// ```
+#[pyfunction]
+pub fn predict_conformal(
+    data: Vec<f64>,
+    horizon: usize,
+    method: String,
+    confidence: f64,
+    calibration_fraction: f64,
+) -> PyResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    predict_conformal_impl(&data, horizon, &method, confidence, calibration_fraction)
+        .map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_predict_mean_intervals_symmetric_around_mean() {
+        let data = vec![1.0, 3.0, 2.0, 4.0, 5.0];
+        let (point, lower, upper) = predict_mean_intervals_impl(&data, 3, 0.9).unwrap();
+
+        assert_eq!(point, vec![3.0; 3]);
+        for h in 0..3 {
+            assert!(lower[h] < point[h] && point[h] < upper[h]);
+            assert!(((upper[h] - point[h]) - (point[h] - lower[h])).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_predict_mean_intervals_invalid_confidence() {
+        let result = predict_mean_intervals_impl(&[1.0, 2.0], 2, 1.0);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Confidence must be in (0, 1), got 1");
+    }
+
+    #[test]
+    fn test_forecast_quantiles_bands_are_ordered() {
+        let mut rng = Rng::new(11);
+        let paths: Vec<Vec<f64>> = (0..200)
+            .map(|_| {
+                let mut value = 10.0;
+                (0..12)
+                    .map(|_| {
+                        value += rng.normal();
+                        value
+                    })
+                    .collect()
+            })
+            .collect();
+        let bands = forecast_quantiles_impl(&paths, &[0.05, 0.5, 0.95]).unwrap();
+
+        assert_eq!(bands.len(), 3);
+        for ((low, mid), high) in bands[0].iter().zip(&bands[1]).zip(&bands[2]) {
+            assert!(low < mid && mid < high);
+        }
+    }
+
+    #[test]
+    fn test_forecast_quantiles_interpolates() {
+        let paths = [vec![1.0], vec![4.0], vec![2.0], vec![3.0]];
+        let bands = forecast_quantiles_impl(&paths, &[0.0, 0.5, 1.0]).unwrap();
+
+        assert_eq!(bands, vec![vec![1.0], vec![2.5], vec![4.0]]);
+    }
+
+    #[test]
+    fn test_forecast_quantiles_ragged_paths() {
+        let result = forecast_quantiles_impl(&[vec![1.0, 2.0], vec![1.0]], &[0.5]);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "All paths must have the same length: path 1 has 1 values, expected 2"
+        );
+    }
+
+    #[test]
+    fn test_forecast_quantiles_out_of_range() {
+        let result = forecast_quantiles_impl(&[vec![1.0]], &[0.5, 1.5]);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Quantiles must be in [0, 1], got 1.5");
+    }
+
+    #[test]
+    fn test_forecast_quantiles_rejects_infinite_paths() {
+        let paths = [vec![1.0], vec![f64::INFINITY], vec![f64::INFINITY]];
+        let result = forecast_quantiles_impl(&paths, &[0.5, 1.0]);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Paths must be finite");
+    }
+
+    #[test]
+    fn test_predict_asymmetric_intervals_skewed_residuals() {
+        // Exponential noise: a long right tail above a floor just below the mean
+        let mut rng = Rng::new(5);
+        let data: Vec<f64> = (0..400).map(|_| 10.0 - 2.0 * (1.0 - rng.uniform()).ln()).collect();
+        let (point, lower, upper) =
+            predict_asymmetric_intervals_impl(&data, 2, 0.05, 0.95).unwrap();
+
+        let below = point[0] - lower[0];
+        let above = upper[0] - point[0];
+        assert!(below > 0.0 && above > 0.0);
+        assert!(above > 2.0 * below, "below {} above {}", below, above);
+        assert_eq!(lower[0], lower[1]);
+        assert_eq!(upper[0], upper[1]);
+    }
+
+    #[test]
+    fn test_predict_asymmetric_intervals_invalid_quantiles() {
+        let data = [1.0, 2.0, 3.0];
+        for (lower_q, upper_q) in [(0.0, 0.9), (0.5, 0.5), (0.9, 0.1), (0.1, 1.0)] {
+            assert!(predict_asymmetric_intervals_impl(&data, 1, lower_q, upper_q).is_err());
+        }
+
+        let result = predict_asymmetric_intervals_impl(&data, 1, 0.9, 0.1);
+        assert_eq!(
+            result.unwrap_err(),
+            "Quantiles must satisfy 0 < lower_q < upper_q < 1, got (0.9, 0.1)"
+        );
+    }
+
+    #[test]
+    fn test_stochastic_functions_reproducible_with_seed() {
+        // Continuous steps, so independent resamples cannot tie by chance
+        let mut rng = Rng::new(21);
+        let data: Vec<f64> = (0..50).map(|t| t as f64 + rng.normal()).collect();
+
+        let a = simulate_paths_impl(&data, 5, 50, Some(3)).unwrap();
+        assert_eq!(a, simulate_paths_impl(&data, 5, 50, Some(3)).unwrap());
+        assert_ne!(a, simulate_paths_impl(&data, 5, 50, None).unwrap());
+        assert_ne!(
+            simulate_paths_impl(&data, 5, 50, None).unwrap(),
+            simulate_paths_impl(&data, 5, 50, None).unwrap()
+        );
+
+        let b = predict_bootstrap_intervals_impl(&data, 5, 0.8, 200, Some(3)).unwrap();
+        assert_eq!(b, predict_bootstrap_intervals_impl(&data, 5, 0.8, 200, Some(3)).unwrap());
+        assert_ne!(
+            predict_bootstrap_intervals_impl(&data, 5, 0.8, 200, None).unwrap(),
+            predict_bootstrap_intervals_impl(&data, 5, 0.8, 200, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_simulate_paths_spread_grows_like_random_walk() {
+        let data = [0.0, 1.0, 0.0, 1.0, 0.0];
+        let paths = simulate_paths_impl(&data, 4, 4000, Some(11)).unwrap();
+
+        assert_eq!(paths.len(), 4000);
+        assert!(paths.iter().all(|path| path.len() == 4));
+        // Unit steps, so the variance after h steps is close to h
+        for h in [0, 3] {
+            let column: Vec<f64> = paths.iter().map(|path| path[h]).collect();
+            let variance = variance_impl(&column);
+            assert!((variance - (h + 1) as f64).abs() < 0.15 * (h + 1) as f64, "{}", variance);
+        }
+    }
+
+    #[test]
+    fn test_predict_bootstrap_intervals_widen_with_horizon() {
+        let mut rng = Rng::new(8);
+        let mut level = 100.0;
+        let data: Vec<f64> = (0..200)
+            .map(|_| {
+                level += rng.normal();
+                level
+            })
+            .collect();
+        let (point, lower, upper) =
+            predict_bootstrap_intervals_impl(&data, 6, 0.9, 2000, Some(1)).unwrap();
+
+        assert_eq!(point, vec![data[199]; 6]);
+        for h in 0..6 {
+            assert!(lower[h] < point[h] && point[h] < upper[h]);
+        }
+        assert!(upper[5] - lower[5] > 1.5 * (upper[0] - lower[0]));
+    }
+
+    #[test]
+    fn test_predict_bootstrap_batch_reproducible_and_distinct() {
+        let mut rng = Rng::new(8);
+        let a: Vec<f64> = (0..60).map(|_| rng.normal()).collect();
+        let b: Vec<f64> = (0..60).map(|_| 5.0 * rng.normal()).collect();
+        let series = vec![a.clone(), b, a];
+
+        let first = predict_bootstrap_batch_impl(&series, 4, 300, 0.9, 11).unwrap();
+        assert_eq!(first, predict_bootstrap_batch_impl(&series, 4, 300, 0.9, 11).unwrap());
+        assert_ne!(first[0], first[1]);
+        // Identical data still gets its own draws at another position
+        assert_ne!(first[0].1, first[2].1);
+        assert_ne!(first, predict_bootstrap_batch_impl(&series, 4, 300, 0.9, 12).unwrap());
+    }
+
+    #[test]
+    fn test_predict_bootstrap_batch_names_failing_series() {
+        let series = vec![vec![1.0, 2.0], vec![1.0]];
+        let result = predict_bootstrap_batch_impl(&series, 2, 10, 0.9, 0);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().starts_with("Series 1: "));
+    }
+
+    #[test]
+    fn test_predict_bootstrap_intervals_zero_samples() {
+        let result = predict_bootstrap_intervals_impl(&[1.0, 2.0], 1, 0.9, 0, None);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Number of bootstrap samples must be greater than 0");
+    }
+
+    #[test]
+    fn test_predict_conformal_widens_with_confidence() {
+        let mut rng = Rng::new(30);
+        let data: Vec<f64> = (0..200).map(|_| 10.0 + rng.normal()).collect();
+        let width = |confidence| {
+            let (_, lower, upper) =
+                predict_conformal_impl(&data, 1, "mean", confidence, 0.5).unwrap();
+            upper[0] - lower[0]
+        };
+
+        assert!(width(0.5) < width(0.8));
+        assert!(width(0.8) < width(0.95));
+    }
+
+    #[test]
+    fn test_predict_conformal_covers_held_out_data() {
+        let mut rng = Rng::new(31);
+        let series: Vec<f64> = (0..2200).map(|_| 5.0 + 2.0 * rng.normal()).collect();
+        let (history, future) = series.split_at(200);
+        let (_, lower, upper) =
+            predict_conformal_impl(history, future.len(), "mean", 0.9, 0.5).unwrap();
+
+        let covered = future
+            .iter()
+            .zip(lower.iter().zip(&upper))
+            .filter(|(y, (lo, hi))| lo <= y && y <= hi)
+            .count();
+        let coverage = covered as f64 / future.len() as f64;
+        assert!((coverage - 0.9).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_predict_conformal_calibration_too_small() {
+        let data: Vec<f64> = (0..20).map(|t| t as f64).collect();
+        let result = predict_conformal_impl(&data, 1, "mean", 0.95, 0.5);
+
+        assert_eq!(
+            result.unwrap_err(),
+            "Calibration set of 10 points is too small for confidence 0.95: at least 19 are needed"
+        );
+    }
+
+    #[test]
+    fn test_min_calibration_len_matches_search() {
+        for confidence in [1e-9, 0.1, 0.5, 0.8, 0.9, 0.95, 0.99, 0.995, 0.999] {
+            let searched = (1..).find(|&m| conformal_rank(m, confidence) <= m).unwrap();
+            assert_eq!(min_calibration_len(confidence), searched);
+        }
+
+        // Near-certain confidence needs a huge set, found without a search
+        let needed = min_calibration_len(1.0 - 1e-12);
+        assert!(conformal_rank(needed, 1.0 - 1e-12) <= needed);
+        assert!(conformal_rank(needed - 1, 1.0 - 1e-12) > needed - 1);
+        assert!(needed > 999_000_000_000);
+    }
+}
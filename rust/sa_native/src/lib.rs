@@ -1,8 +1,39 @@
+use numpy::PyArray1;
 use pyo3::prelude::*;
-use pyo3::exceptions::PyValueError;
+use pyo3::exceptions::{PyUserWarning, PyValueError};
+
+mod anomaly;
+mod auto;
+mod backtest;
+mod composite;
+mod decompose;
+mod export;
+mod fft;
+mod fingerprint;
+mod hierarchy;
+mod intervals;
+mod linalg;
+mod methods;
+mod metrics;
+mod model;
+mod optimize;
+mod preprocessing;
+mod rng;
+mod seasonal;
+mod smoothing;
+mod stats;
+mod streaming;
+mod transform;
+mod trend;
 
 /// Core prediction logic (can be tested without Python runtime)
-fn predict_static_impl(data: &[f64], horizon: usize) -> Result<Vec<f64>, String> {
+pub(crate) fn predict_static_impl(data: &[f64], horizon: usize) -> Result<Vec<f64>, String> {
+    carry_forward_impl(data, horizon)
+}
+
+/// Carry-forward shared by the float and integer APIs, so both validate
+/// identically.
+fn carry_forward_impl<T: Copy>(data: &[T], horizon: usize) -> Result<Vec<T>, String> {
     // Validate input
     if data.is_empty() {
         return Err("Input data cannot be empty".to_string());
@@ -51,7 +82,366 @@ fn predict_static_impl(data: &[f64], horizon: usize) -> Result<Vec<f64>, String>
 #[pyfunction]
 fn predict_static(data: Vec<f64>, horizon: usize) -> PyResult<Vec<f64>> {
     predict_static_impl(&data, horizon)
-        .map_err(|e| PyValueError::new_err(e))
+        .map_err(PyValueError::new_err)
+}
+
+/// Core in-place carry-forward logic: fills a caller-owned buffer of length
+/// `horizon` instead of allocating the forecast.
+pub(crate) fn predict_static_into_impl(
+    data: &[f64],
+    out: &mut [f64],
+    horizon: usize,
+) -> Result<(), String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    if out.len() != horizon {
+        return Err(format!(
+            "Output length ({}) must match horizon ({})",
+            out.len(),
+            horizon
+        ));
+    }
+
+    out.fill(data[data.len() - 1]);
+    Ok(())
+}
+
+/// Write the `predict_static` forecast into a preallocated NumPy array.
+///
+/// For services that forecast the same horizon repeatedly, reusing one
+/// output array avoids allocating a new list on every call. The array is
+/// written in place and must be contiguous with exactly `horizon` elements.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `out` - Writable contiguous `float64` array of length `horizon`
+/// * `horizon` - Number of future values to predict
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * data is empty
+/// * horizon is 0
+/// * the array length differs from horizon
+/// * the array is not contiguous, not writeable or already borrowed
+///
+/// # Examples
+///
+/// ```python
+/// import numpy as np
+/// import sa_native
+/// out = np.empty(3)
+/// sa_native.predict_static_into_np([1.0, 2.0, 3.0], out, 3)
+/// # out is now array([3., 3., 3.])
+/// ```
+#[pyfunction]
+fn predict_static_into_np(data: Vec<f64>, out: &PyArray1<f64>, horizon: usize) -> PyResult<()> {
+    let mut out = out
+        .try_readwrite()
+        .map_err(|e| PyValueError::new_err(format!("Output array cannot be written: {}", e)))?;
+    let out = out
+        .as_slice_mut()
+        .map_err(|_| PyValueError::new_err("Output array must be contiguous"))?;
+
+    predict_static_into_impl(&data, out, horizon)
+        .map_err(PyValueError::new_err)
+}
+
+/// Integer carry-forward logic, exact for any `i64` value.
+pub(crate) fn predict_static_i64_impl(data: &[i64], horizon: usize) -> Result<Vec<i64>, String> {
+    carry_forward_impl(data, horizon)
+}
+
+/// Core mean prediction logic: the historical mean repeated for the horizon.
+pub(crate) fn predict_mean_impl(data: &[f64], horizon: usize) -> Result<Vec<f64>, String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let mean = data.iter().sum::<f64>() / data.len() as f64;
+    Ok(vec![mean; horizon])
+}
+
+/// What a batch does with a series that fails to forecast.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum OnError {
+    /// Abort the whole batch with the series' error.
+    Raise,
+    /// Leave the series out of the result.
+    Skip,
+    /// Return a NaN-filled forecast in its place.
+    Nan,
+}
+
+impl OnError {
+    pub(crate) fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "raise" => Ok(OnError::Raise),
+            "skip" => Ok(OnError::Skip),
+            "nan" => Ok(OnError::Nan),
+            _ => Err(format!(
+                "Unknown on_error mode '{}', expected one of: raise, skip, nan",
+                name
+            )),
+        }
+    }
+}
+
+/// Core batch prediction logic: every series goes through `predict_static_impl`
+/// so validation and error messages match the single-series path exactly.
+pub(crate) fn predict_static_batch_impl(
+    series: &[Vec<f64>],
+    horizon: usize,
+) -> Result<Vec<Vec<f64>>, String> {
+    Ok(predict_static_batch_on_error_impl(series, horizon, OnError::Raise)?.0)
+}
+
+/// Batch forecasts with the index and error of each failed series.
+type BatchOutcome = (Vec<Vec<f64>>, Vec<(usize, String)>);
+
+/// Core batch loop: the forecasts, plus the index and error of each failed
+/// series under `Skip` or `Nan`.
+fn predict_static_batch_failures_impl(
+    series: &[Vec<f64>],
+    horizon: usize,
+    on_error: OnError,
+) -> Result<BatchOutcome, String> {
+    if series.is_empty() {
+        return Err("Batch cannot be empty".to_string());
+    }
+
+    // A zero horizon fails every series, so it is not a per-series problem
+    if on_error != OnError::Raise && horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let mut forecasts = Vec::with_capacity(series.len());
+    let mut failures = Vec::new();
+    for (i, data) in series.iter().enumerate() {
+        match (predict_static_impl(data, horizon), on_error) {
+            (Ok(forecast), _) => forecasts.push(forecast),
+            (Err(e), OnError::Raise) => return Err(e),
+            (Err(e), OnError::Skip) => failures.push((i, e)),
+            (Err(e), OnError::Nan) => {
+                failures.push((i, e));
+                forecasts.push(vec![f64::NAN; horizon]);
+            }
+        }
+    }
+
+    Ok((forecasts, failures))
+}
+
+/// Core batch prediction logic with per-series error handling: the
+/// forecasts, plus one warning naming each failed series under `Skip` or
+/// `Nan`.
+pub(crate) fn predict_static_batch_on_error_impl(
+    series: &[Vec<f64>],
+    horizon: usize,
+    on_error: OnError,
+) -> Result<(Vec<Vec<f64>>, Vec<String>), String> {
+    let (forecasts, failures) = predict_static_batch_failures_impl(series, horizon, on_error)?;
+    let warnings = failures
+        .into_iter()
+        .map(|(i, e)| match on_error {
+            OnError::Nan => format!("series {} forecast as NaN: {}", i, e),
+            _ => format!("skipped series {}: {}", i, e),
+        })
+        .collect();
+
+    Ok((forecasts, warnings))
+}
+
+/// Core checked batch logic: the forecasts of the series that succeed, and
+/// the indices of those skipped.
+pub(crate) fn predict_static_batch_checked_impl(
+    series: &[Vec<f64>],
+    horizon: usize,
+) -> Result<(Vec<Vec<f64>>, Vec<usize>), String> {
+    let (forecasts, failures) = predict_static_batch_failures_impl(series, horizon, OnError::Skip)?;
+    Ok((forecasts, failures.into_iter().map(|(i, _)| i).collect()))
+}
+
+/// Core flat batch logic: the batch forecasts concatenated in row-major order.
+pub(crate) fn predict_static_batch_flat_impl(
+    series: &[Vec<f64>],
+    horizon: usize,
+) -> Result<(Vec<f64>, usize), String> {
+    let forecasts = predict_static_batch_impl(series, horizon)?;
+    Ok((forecasts.concat(), horizon))
+}
+
+/// Predict future values for several series at once (mock implementation).
+///
+/// Each series is forecast exactly as `predict_static` would forecast it, and
+/// the same `horizon` applies to all series in the batch.
+///
+/// `on_error` decides what happens when a series is rejected: "raise" (the
+/// default) aborts the batch, "skip" leaves the series out of the result and
+/// "nan" returns a NaN-filled forecast in its place. With "skip" and "nan" a
+/// `UserWarning` naming the series index and its error is emitted for each
+/// failure; `predict_static_batch_checked` returns the skipped indices
+/// instead.
+///
+/// # Arguments
+///
+/// * `series` - Historical data for each series
+/// * `horizon` - Number of future values to predict for every series
+/// * `on_error` - "raise", "skip" or "nan" (default "raise")
+///
+/// # Returns
+///
+/// One vector of predicted values per input series, in input order, without
+/// the skipped ones
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * the batch is empty or on_error is unknown
+/// * any series is rejected by `predict_static` (same message) and on_error
+///   is "raise"
+/// * horizon is 0
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// result = sa_native.predict_static_batch([[1.0, 2.0], [5.0]], 2)
+/// # Returns [[2.0, 2.0], [5.0, 5.0]]
+/// ```
+#[pyfunction]
+#[pyo3(signature = (series, horizon, on_error="raise"))]
+fn predict_static_batch(
+    py: Python<'_>,
+    series: Vec<Vec<f64>>,
+    horizon: usize,
+    on_error: &str,
+) -> PyResult<Vec<Vec<f64>>> {
+    let on_error = OnError::parse(on_error).map_err(PyValueError::new_err)?;
+    let (forecasts, warnings) = predict_static_batch_on_error_impl(&series, horizon, on_error)
+        .map_err(PyValueError::new_err)?;
+    for warning in &warnings {
+        PyErr::warn(py, py.get_type::<PyUserWarning>(), warning, 1)?;
+    }
+
+    Ok(forecasts)
+}
+
+/// Batch forecast that skips rejected series and reports which ones.
+///
+/// Like `predict_static_batch` with `on_error="skip"`, but the skipped
+/// series come back as data rather than as warnings, so a caller can tell
+/// which forecasts belong to which input without parsing warning messages.
+///
+/// # Arguments
+///
+/// * `series` - Historical data for each series
+/// * `horizon` - Number of future values to predict for every series
+///
+/// # Returns
+///
+/// Tuple `(forecasts, skipped)`: the forecasts of the accepted series in
+/// input order, and the indices of the rejected ones in increasing order
+///
+/// # Errors
+///
+/// Returns PyValueError if the batch is empty or horizon is 0.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// forecasts, skipped = sa_native.predict_static_batch_checked([[1.0, 2.0], [], [5.0]], 2)
+/// # forecasts == [[2.0, 2.0], [5.0, 5.0]], skipped == [1]
+/// ```
+#[pyfunction]
+fn predict_static_batch_checked(
+    series: Vec<Vec<f64>>,
+    horizon: usize,
+) -> PyResult<(Vec<Vec<f64>>, Vec<usize>)> {
+    predict_static_batch_checked_impl(&series, horizon).map_err(PyValueError::new_err)
+}
+
+/// Batch forecast as one contiguous buffer, for callers crossing an FFI
+/// boundary.
+///
+/// Same forecasts as `predict_static_batch`, flattened in row-major order:
+/// values `i * horizon..(i + 1) * horizon` belong to series `i`.
+///
+/// # Returns
+///
+/// Tuple `(values, horizon)` with `n_series * horizon` values; reshape with
+/// `(len(values) // horizon, horizon)`
+///
+/// # Errors
+///
+/// Returns PyValueError under the same conditions as `predict_static_batch`.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// values, horizon = sa_native.predict_static_batch_flat([[1.0, 2.0], [5.0]], 2)
+/// # values == [2.0, 2.0, 5.0, 5.0], horizon == 2
+/// ```
+#[pyfunction]
+fn predict_static_batch_flat(series: Vec<Vec<f64>>, horizon: usize) -> PyResult<(Vec<f64>, usize)> {
+    predict_static_batch_flat_impl(&series, horizon)
+        .map_err(PyValueError::new_err)
+}
+
+/// Predict future values as the mean of the historical data.
+///
+/// # Errors
+///
+/// Returns PyValueError if data is empty or horizon is 0.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// result = sa_native.predict_mean([1.0, 2.0, 6.0], 2)
+/// # Returns [3.0, 3.0]
+/// ```
+#[pyfunction]
+fn predict_mean(data: Vec<f64>, horizon: usize) -> PyResult<Vec<f64>> {
+    predict_mean_impl(&data, horizon)
+        .map_err(PyValueError::new_err)
+}
+
+/// Predict future values of an integer series (mock implementation).
+///
+/// Integer counterpart of `predict_static` that accepts Python `int` lists
+/// without converting them to floats. Carry-forward is exact for integers.
+/// Methods whose results are inherently fractional (mean, trend) remain
+/// float-only.
+///
+/// # Errors
+///
+/// Returns PyValueError if data is empty or horizon is 0.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// result = sa_native.predict_static_i64([3, 7, 12], 2)
+/// # Returns [12, 12]
+/// ```
+#[pyfunction]
+fn predict_static_i64(data: Vec<i64>, horizon: usize) -> PyResult<Vec<i64>> {
+    predict_static_i64_impl(&data, horizon)
+        .map_err(PyValueError::new_err)
 }
 
 /// Python module for time series prediction.
@@ -61,6 +451,121 @@ fn predict_static(data: Vec<f64>, horizon: usize) -> PyResult<Vec<f64>> {
 #[pymodule]
 fn sa_native(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(predict_static, m)?)?;
+    m.add_function(wrap_pyfunction!(predict_static_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(predict_static_batch_checked, m)?)?;
+    m.add_function(wrap_pyfunction!(predict_static_batch_flat, m)?)?;
+    m.add_function(wrap_pyfunction!(predict_static_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(predict_static_into_np, m)?)?;
+    m.add_function(wrap_pyfunction!(predict_mean, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::predict_linear_trend, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::fit_weighted_linear_trend, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::predict_weighted_linear_trend, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::fit_robust_trend, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::predict_robust_trend, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::predict_exponential_trend, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::predict_pct_change, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::predict_drift, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::predict_polynomial, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::predict_spline, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::predict_logistic, m)?)?;
+    m.add_function(wrap_pyfunction!(backtest::rolling_forecasts, m)?)?;
+    m.add_function(wrap_pyfunction!(backtest::forecast_history, m)?)?;
+    m.add_function(wrap_pyfunction!(backtest::innovation_std, m)?)?;
+    m.add_function(wrap_pyfunction!(backtest::backtest_static, m)?)?;
+    m.add_function(wrap_pyfunction!(backtest::backtest_coverage, m)?)?;
+    m.add_function(wrap_pyfunction!(backtest::backtest_intervals, m)?)?;
+    m.add_function(wrap_pyfunction!(backtest::backtest_per_step_error, m)?)?;
+    m.add_function(wrap_pyfunction!(backtest::fit_ensemble_weights, m)?)?;
+    m.add_function(wrap_pyfunction!(backtest::forecast_value_add, m)?)?;
+    m.add_function(wrap_pyfunction!(backtest::evaluate, m)?)?;
+    m.add_function(wrap_pyfunction!(intervals::predict_mean_intervals, m)?)?;
+    m.add_function(wrap_pyfunction!(intervals::predict_asymmetric_intervals, m)?)?;
+    m.add_function(wrap_pyfunction!(intervals::forecast_quantiles, m)?)?;
+    m.add_function(wrap_pyfunction!(intervals::simulate_paths, m)?)?;
+    m.add_function(wrap_pyfunction!(intervals::predict_bootstrap_intervals, m)?)?;
+    m.add_function(wrap_pyfunction!(intervals::predict_bootstrap_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(intervals::predict_conformal, m)?)?;
+    m.add_function(wrap_pyfunction!(composite::predict_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(composite::predict_horizon_blend, m)?)?;
+    m.add_function(wrap_pyfunction!(composite::predict_composite, m)?)?;
+    m.add_function(wrap_pyfunction!(composite::predict_decomposition, m)?)?;
+    m.add_function(wrap_pyfunction!(decompose::decompose_multiplicative, m)?)?;
+    m.add_function(wrap_pyfunction!(composite::predict_on_difference, m)?)?;
+    m.add_function(wrap_pyfunction!(composite::combine_inverse_variance, m)?)?;
+    m.add_function(wrap_pyfunction!(composite::combine_optimal, m)?)?;
+    m.add_function(wrap_pyfunction!(composite::apply_overrides, m)?)?;
+    m.add_function(wrap_pyfunction!(composite::predict_anchored, m)?)?;
+    m.add_function(wrap_pyfunction!(composite::predict_bounded, m)?)?;
+    m.add_function(wrap_pyfunction!(composite::predict_with_bounds, m)?)?;
+    m.add_function(wrap_pyfunction!(composite::predict_counts, m)?)?;
+    m.add_class::<model::FittedModel>()?;
+    m.add_function(wrap_pyfunction!(model::fit, m)?)?;
+    m.add_function(wrap_pyfunction!(model::min_history, m)?)?;
+    m.add_function(wrap_pyfunction!(model::in_sample_sse, m)?)?;
+    m.add_class::<model::Forecast>()?;
+    m.add_function(wrap_pyfunction!(model::forecast, m)?)?;
+    m.add_function(wrap_pyfunction!(auto::auto_forecast, m)?)?;
+    m.add_function(wrap_pyfunction!(auto::predict_ses_auto, m)?)?;
+    m.add_function(wrap_pyfunction!(fingerprint::fingerprint, m)?)?;
+    m.add_function(wrap_pyfunction!(hierarchy::reconcile_bottom_up, m)?)?;
+    m.add_function(wrap_pyfunction!(hierarchy::reconcile_top_down, m)?)?;
+    m.add_function(wrap_pyfunction!(hierarchy::reconcile_ols, m)?)?;
+    m.add_function(wrap_pyfunction!(smoothing::predict_ses, m)?)?;
+    m.add_function(wrap_pyfunction!(smoothing::fit_ses, m)?)?;
+    m.add_function(wrap_pyfunction!(smoothing::ses_sse_gradient, m)?)?;
+    m.add_function(wrap_pyfunction!(smoothing::predict_adaptive_ses, m)?)?;
+    m.add_function(wrap_pyfunction!(smoothing::predict_holt, m)?)?;
+    m.add_function(wrap_pyfunction!(smoothing::predict_ets_aan, m)?)?;
+    m.add_function(wrap_pyfunction!(smoothing::rolling_mean, m)?)?;
+    m.add_function(wrap_pyfunction!(smoothing::rolling_apply, m)?)?;
+    m.add_function(wrap_pyfunction!(smoothing::predict_moving_average, m)?)?;
+    m.add_function(wrap_pyfunction!(smoothing::predict_ewma, m)?)?;
+    m.add_class::<streaming::StreamingSes>()?;
+    m.add_class::<streaming::BatchModels>()?;
+    m.add_class::<streaming::ForecastIterator>()?;
+    m.add_function(wrap_pyfunction!(streaming::predict_static_iter, m)?)?;
+    m.add_function(wrap_pyfunction!(streaming::predict_drift_iter, m)?)?;
+    m.add_class::<anomaly::StreamingAnomalyDetector>()?;
+    m.add_function(wrap_pyfunction!(anomaly::detect_outliers_zscore, m)?)?;
+    m.add_function(wrap_pyfunction!(anomaly::detect_level_shift, m)?)?;
+    m.add_function(wrap_pyfunction!(anomaly::forecast_after_shift, m)?)?;
+    m.add_function(wrap_pyfunction!(seasonal::predict_seasonal_dummy, m)?)?;
+    m.add_function(wrap_pyfunction!(seasonal::predict_cyclic, m)?)?;
+    m.add_function(wrap_pyfunction!(seasonal::predict_holt_winters, m)?)?;
+    m.add_function(wrap_pyfunction!(seasonal::predict_holt_winters_mixed, m)?)?;
+    m.add_function(wrap_pyfunction!(seasonal::fit_holt_winters_nm, m)?)?;
+    m.add_function(wrap_pyfunction!(seasonal::holt_winters_seasonal_indices, m)?)?;
+    m.add_function(wrap_pyfunction!(seasonal::fourier_terms, m)?)?;
+    m.add_function(wrap_pyfunction!(seasonal::monthly_seasonal_profile, m)?)?;
+    m.add_function(wrap_pyfunction!(seasonal::apply_monthly_profile, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::growth_rates, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::data_report, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::nanmean, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::nanstd, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::mad, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::effective_sample_size, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::cross_correlation, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::series_features, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::suggest_period_fft, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::forecastability, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::z_score, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::estimate_diff_order, m)?)?;
+    m.add_function(wrap_pyfunction!(metrics::compare_forecasts, m)?)?;
+    m.add_function(wrap_pyfunction!(metrics::trimmed_rmse, m)?)?;
+    m.add_function(wrap_pyfunction!(metrics::mse_decomposition, m)?)?;
+    m.add_function(wrap_pyfunction!(metrics::validate_forecast_plausibility, m)?)?;
+    m.add_function(wrap_pyfunction!(export::forecast_to_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(export::forecast_batch_to_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(preprocessing::collapse_duplicates, m)?)?;
+    m.add_function(wrap_pyfunction!(preprocessing::align_series, m)?)?;
+    m.add_function(wrap_pyfunction!(preprocessing::resample_to_length, m)?)?;
+    m.add_function(wrap_pyfunction!(preprocessing::fit_to_length, m)?)?;
+    m.add_function(wrap_pyfunction!(preprocessing::rolling_rank, m)?)?;
+    m.add_function(wrap_pyfunction!(preprocessing::impute_linear, m)?)?;
+    m.add_function(wrap_pyfunction!(preprocessing::mask_events, m)?)?;
+    m.add_function(wrap_pyfunction!(preprocessing::check_monotonic_timestamps, m)?)?;
+    m.add_function(wrap_pyfunction!(transform::conditional_detrend, m)?)?;
+    m.add_function(wrap_pyfunction!(transform::auto_detrend, m)?)?;
     Ok(())
 }
 
@@ -129,4 +634,169 @@ mod tests {
         // All values should be 30.0 (last value)
         assert!(predictions.iter().all(|&x| x == 30.0));
     }
+
+    #[test]
+    fn test_predict_static_batch_matches_single() {
+        let series = vec![vec![1.0, 2.0, 3.0], vec![42.0]];
+        let result = predict_static_batch_impl(&series, 2).unwrap();
+
+        assert_eq!(result.len(), 2);
+        for (data, predictions) in series.iter().zip(&result) {
+            assert_eq!(predictions, &predict_static_impl(data, 2).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_predict_static_batch_zero_horizon_matches_single() {
+        // The shared horizon is validated exactly as for a single series
+        let series = vec![vec![1.0, 2.0], vec![3.0]];
+        let batch_err = predict_static_batch_impl(&series, 0).unwrap_err();
+        let single_err = predict_static_impl(&series[0], 0).unwrap_err();
+
+        assert_eq!(batch_err, single_err);
+        assert_eq!(batch_err, "Horizon must be greater than 0");
+    }
+
+    #[test]
+    fn test_predict_static_batch_invalid_series_matches_single() {
+        let series = vec![vec![1.0, 2.0], vec![]];
+        let batch_err = predict_static_batch_impl(&series, 3).unwrap_err();
+
+        assert_eq!(batch_err, predict_static_impl(&[], 3).unwrap_err());
+    }
+
+    #[test]
+    fn test_predict_static_batch_empty() {
+        let result = predict_static_batch_impl(&[], 3);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Batch cannot be empty");
+    }
+
+    #[test]
+    fn test_predict_static_batch_on_error_modes() {
+        let series = vec![vec![1.0, 2.0], vec![], vec![5.0]];
+
+        let raised = predict_static_batch_on_error_impl(&series, 2, OnError::Raise);
+        assert_eq!(raised.unwrap_err(), predict_static_impl(&[], 2).unwrap_err());
+
+        let (skipped, warnings) =
+            predict_static_batch_on_error_impl(&series, 2, OnError::Skip).unwrap();
+        assert_eq!(skipped, vec![vec![2.0, 2.0], vec![5.0, 5.0]]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("skipped series 1: "));
+
+        let (filled, warnings) =
+            predict_static_batch_on_error_impl(&series, 2, OnError::Nan).unwrap();
+        assert_eq!(filled.len(), 3);
+        assert_eq!(filled[0], vec![2.0, 2.0]);
+        assert!(filled[1].len() == 2 && filled[1].iter().all(|v| v.is_nan()));
+        assert_eq!(filled[2], vec![5.0, 5.0]);
+        assert!(warnings[0].starts_with("series 1 forecast as NaN: "));
+    }
+
+    #[test]
+    fn test_predict_static_batch_on_error_zero_horizon_still_raises() {
+        let series = vec![vec![1.0], vec![2.0]];
+        for on_error in [OnError::Skip, OnError::Nan] {
+            let result = predict_static_batch_on_error_impl(&series, 0, on_error);
+            assert_eq!(result.unwrap_err(), "Horizon must be greater than 0");
+        }
+        assert_eq!(
+            OnError::parse("ignore").unwrap_err(),
+            "Unknown on_error mode 'ignore', expected one of: raise, skip, nan"
+        );
+    }
+
+    #[test]
+    fn test_predict_static_batch_checked_reports_skipped_indices() {
+        let series = vec![vec![], vec![1.0, 2.0], vec![], vec![5.0]];
+        let (forecasts, skipped) = predict_static_batch_checked_impl(&series, 2).unwrap();
+
+        assert_eq!(forecasts, vec![vec![2.0, 2.0], vec![5.0, 5.0]]);
+        assert_eq!(skipped, vec![0, 2]);
+        assert_eq!(
+            predict_static_batch_checked_impl(&series, 0).unwrap_err(),
+            "Horizon must be greater than 0"
+        );
+    }
+
+    #[test]
+    fn test_predict_static_batch_flat_is_row_major() {
+        let series = vec![vec![1.0, 2.0], vec![5.0], vec![-3.0, 0.5, 7.0]];
+        let nested = predict_static_batch_impl(&series, 3).unwrap();
+        let (flat, horizon) = predict_static_batch_flat_impl(&series, 3).unwrap();
+
+        assert_eq!(horizon, 3);
+        assert_eq!(flat.len(), series.len() * horizon);
+        for (i, row) in nested.iter().enumerate() {
+            assert_eq!(&flat[i * horizon..(i + 1) * horizon], row.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_predict_static_batch_flat_empty() {
+        let result = predict_static_batch_flat_impl(&[], 3);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Batch cannot be empty");
+    }
+
+    #[test]
+    fn test_predict_static_i64_carries_last_value_exactly() {
+        // Beyond 2^53, so a round trip through f64 would not be exact
+        let big = i64::MAX - 1;
+        let result = predict_static_i64_impl(&[1, 2, big], 3).unwrap();
+
+        assert_eq!(result, vec![big, big, big]);
+    }
+
+    #[test]
+    fn test_predict_static_i64_validation_matches_float() {
+        assert_eq!(
+            predict_static_i64_impl(&[], 3).unwrap_err(),
+            predict_static_impl(&[], 3).unwrap_err()
+        );
+        assert_eq!(
+            predict_static_i64_impl(&[1], 0).unwrap_err(),
+            predict_static_impl(&[1.0], 0).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_predict_static_float_unaffected() {
+        let result = predict_static_impl(&[1.5, 2.5], 2).unwrap();
+
+        assert_eq!(result, vec![2.5, 2.5]);
+    }
+
+    #[test]
+    fn test_predict_mean() {
+        let result = predict_mean_impl(&[1.0, 2.0, 6.0], 2).unwrap();
+
+        assert_eq!(result, vec![3.0, 3.0]);
+    }
+
+    #[test]
+    fn test_predict_static_into_reuses_buffer() {
+        let mut out = vec![0.0; 3];
+        let buffer = out.as_ptr();
+
+        predict_static_into_impl(&[1.0, 2.0, 3.0], &mut out, 3).unwrap();
+        assert_eq!(out, vec![3.0, 3.0, 3.0]);
+
+        predict_static_into_impl(&[1.0, 2.0, 3.0, 7.5], &mut out, 3).unwrap();
+        assert_eq!(out, predict_static_impl(&[1.0, 2.0, 3.0, 7.5], 3).unwrap());
+        assert_eq!(out.as_ptr(), buffer);
+    }
+
+    #[test]
+    fn test_predict_static_into_length_mismatch() {
+        let mut out = vec![0.0; 2];
+        let result = predict_static_into_impl(&[1.0], &mut out, 3);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Output length (2) must match horizon (3)");
+        assert_eq!(out, vec![0.0, 0.0]);
+    }
 }
@@ -0,0 +1,103 @@
+/// Pivots smaller than this are treated as zero (singular system).
+const SINGULAR_TOLERANCE: f64 = 1e-12;
+
+/// Solve the square system `a * x = b` by Gaussian elimination with partial
+/// pivoting.
+pub(crate) fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Result<Vec<f64>, String> {
+    let n = b.len();
+    if a.len() != n || a.iter().any(|row| row.len() != n) {
+        return Err("Linear system must be square and match the right-hand side".to_string());
+    }
+
+    // Scale the tolerance to the magnitude of the matrix
+    let scale = a
+        .iter()
+        .flat_map(|row| row.iter())
+        .fold(0.0_f64, |m, v| m.max(v.abs()))
+        .max(1.0);
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+            .unwrap();
+        if a[pivot][col].abs() < SINGULAR_TOLERANCE * scale {
+            return Err("Linear system is singular".to_string());
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            if factor != 0.0 {
+                for (value, pivot_value) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                    *value -= factor * pivot_value;
+                }
+                b[col + 1 + offset] -= factor * b[col];
+            }
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+
+    Ok(x)
+}
+
+/// Ordinary least squares coefficients for the design matrix `rows` (one row
+/// per observation) and targets `y`, via the normal equations.
+pub(crate) fn least_squares(rows: &[Vec<f64>], y: &[f64]) -> Result<Vec<f64>, String> {
+    let p = rows.first().map_or(0, |row| row.len());
+    if rows.len() != y.len() || p == 0 {
+        return Err("Design matrix must have one non-empty row per observation".to_string());
+    }
+
+    let mut xtx = vec![vec![0.0; p]; p];
+    let mut xty = vec![0.0; p];
+    for (row, target) in rows.iter().zip(y) {
+        for i in 0..p {
+            xty[i] += row[i] * target;
+            for j in 0..p {
+                xtx[i][j] += row[i] * row[j];
+            }
+        }
+    }
+
+    solve(xtx, xty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_with_pivoting() {
+        // The zero in the top-left corner requires a row swap
+        let a = vec![vec![0.0, 2.0], vec![1.0, 1.0]];
+        let x = solve(a, vec![4.0, 3.0]).unwrap();
+
+        assert!((x[0] - 1.0).abs() < 1e-12);
+        assert!((x[1] - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_solve_singular() {
+        let a = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+
+        assert_eq!(solve(a, vec![1.0, 2.0]).unwrap_err(), "Linear system is singular");
+    }
+
+    #[test]
+    fn test_least_squares_recovers_line() {
+        let rows: Vec<Vec<f64>> = (0..5).map(|t| vec![1.0, t as f64]).collect();
+        let y: Vec<f64> = (0..5).map(|t| 3.0 - 2.0 * t as f64).collect();
+        let coef = least_squares(&rows, &y).unwrap();
+
+        assert!((coef[0] - 3.0).abs() < 1e-9);
+        assert!((coef[1] + 2.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,482 @@
+use std::collections::HashMap;
+
+use crate::predict_mean_impl;
+use crate::seasonal::fit_holt_winters_impl;
+use crate::smoothing::{holt_components_impl, ses_levels_impl};
+use crate::trend::{
+    compound_from, extrapolate_exponential, extrapolate_line, fit_drift_impl,
+    fit_exponential_trend_impl, fit_linear_trend_impl, fit_pct_change_impl,
+};
+
+/// Names accepted by the `method` argument of the method-generic functions.
+pub(crate) const METHODS: &[&str] = &[
+    "static",
+    "mean",
+    "linear_trend",
+    "exponential_trend",
+    "pct_change",
+    "drift",
+    "ses",
+    "holt",
+    "holt_winters",
+];
+
+/// Smoothing parameters used by the smoothing methods when not given in
+/// `params`.
+const DEFAULT_ALPHA: f64 = 0.3;
+const DEFAULT_BETA: f64 = 0.1;
+const DEFAULT_GAMMA: f64 = 0.1;
+
+/// Fitted state of a forecasting method, from which any horizon can be
+/// forecast without refitting.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum FittedState {
+    Static { last: f64 },
+    Mean { mean: f64 },
+    LinearTrend { slope: f64, intercept: f64, n: usize },
+    ExponentialTrend { growth: f64, scale: f64, n: usize },
+    PctChange { last: f64, rate: f64 },
+    Drift { last: f64, slope: f64 },
+    Ses { level: f64 },
+    Holt { level: f64, trend: f64 },
+    HoltWinters { level: f64, trend: f64, seasonals: Vec<f64>, n: usize },
+}
+
+impl FittedState {
+    /// Forecast `horizon` steps ahead from the fitted state.
+    pub(crate) fn forecast(&self, horizon: usize) -> Result<Vec<f64>, String> {
+        if horizon == 0 {
+            return Err("Horizon must be greater than 0".to_string());
+        }
+
+        Ok(match *self {
+            FittedState::Static { last } => vec![last; horizon],
+            FittedState::Mean { mean } => vec![mean; horizon],
+            FittedState::LinearTrend { slope, intercept, n } => {
+                extrapolate_line(slope, intercept, n, horizon)
+            }
+            FittedState::ExponentialTrend { growth, scale, n } => {
+                extrapolate_exponential(growth, scale, n, horizon)
+            }
+            FittedState::PctChange { last, rate } => compound_from(last, rate, horizon),
+            FittedState::Drift { last, slope } => {
+                (1..=horizon).map(|h| last + h as f64 * slope).collect()
+            }
+            FittedState::Ses { level } => vec![level; horizon],
+            FittedState::Holt { level, trend } => {
+                (1..=horizon).map(|h| level + h as f64 * trend).collect()
+            }
+            FittedState::HoltWinters { level, trend, ref seasonals, n } => (1..=horizon)
+                .map(|h| level + h as f64 * trend + seasonals[(n + h - 1) % seasonals.len()])
+                .collect(),
+        })
+    }
+}
+
+/// Whether `method` forecasts by a recursion from its state after the last
+/// observation, so that appending its own forecasts to the data leaves the
+/// forecast unchanged while appending other values steers it. The global
+/// fits ("mean" and the trend curves) are not recursive.
+pub(crate) fn is_recursive_method(method: &str) -> bool {
+    matches!(method, "static" | "pct_change" | "drift" | "ses" | "holt" | "holt_winters")
+}
+
+/// Parameter names accepted by each method.
+fn method_params(method: &str) -> Result<&'static [&'static str], String> {
+    match method {
+        "static" | "mean" | "linear_trend" | "exponential_trend" | "pct_change" | "drift" => {
+            Ok(&[])
+        }
+        "ses" => Ok(&["alpha"]),
+        "holt" => Ok(&["alpha", "beta"]),
+        "holt_winters" => Ok(&["season_length", "alpha", "beta", "gamma"]),
+        _ => Err(format!(
+            "Unknown method '{}', expected one of: {}",
+            method,
+            METHODS.join(", ")
+        )),
+    }
+}
+
+/// Check that the method exists and accepts every given parameter name.
+pub(crate) fn check_method_params(method: &str, params: &HashMap<String, f64>) -> Result<(), String> {
+    let accepted = method_params(method)?;
+    if let Some(name) = params.keys().find(|name| !accepted.contains(&name.as_str())) {
+        return Err(format!("Unknown parameter '{}' for method '{}'", name, method));
+    }
+
+    Ok(())
+}
+
+/// Minimum number of observations `method` needs to be fitted with `params`.
+///
+/// `fit_method_impl` rejects shorter data with this same threshold, so the
+/// reported minimum is the one actually enforced.
+pub(crate) fn min_history_impl(method: &str, params: &HashMap<String, f64>) -> Result<usize, String> {
+    check_method_params(method, params)?;
+
+    Ok(match method {
+        "linear_trend" | "exponential_trend" | "pct_change" | "drift" | "holt" => 2,
+        // Saturates, as no data can be long enough for such a season length
+        "holt_winters" => season_length_param(method, params)?.saturating_mul(2),
+        _ => 1,
+    })
+}
+
+/// Parameters `method` is actually fitted with: those in `params`, plus the
+/// defaults of the smoothing parameters left out.
+pub(crate) fn effective_params_impl(
+    method: &str,
+    params: &HashMap<String, f64>,
+) -> Result<HashMap<String, f64>, String> {
+    check_method_params(method, params)?;
+
+    let mut effective = params.clone();
+    for &name in method_params(method)? {
+        let default = match name {
+            "alpha" => DEFAULT_ALPHA,
+            "beta" => DEFAULT_BETA,
+            "gamma" => DEFAULT_GAMMA,
+            _ => continue,
+        };
+        effective.entry(name.to_string()).or_insert(default);
+    }
+
+    Ok(effective)
+}
+
+/// Value of the parameter `name`, or `default` when it is not given.
+fn param(params: &HashMap<String, f64>, name: &str, default: f64) -> f64 {
+    params.get(name).copied().unwrap_or(default)
+}
+
+/// The required whole-number `season_length` parameter of a seasonal method.
+fn season_length_param(method: &str, params: &HashMap<String, f64>) -> Result<usize, String> {
+    let value = match params.get("season_length") {
+        Some(&value) => value,
+        None => return Err(format!("Method '{}' requires parameter 'season_length'", method)),
+    };
+
+    if !(value >= 2.0 && value.fract() == 0.0 && value.is_finite()) {
+        return Err(format!(
+            "Parameter 'season_length' must be a whole number of at least 2, got {}",
+            value
+        ));
+    }
+
+    Ok(value as usize)
+}
+
+/// Holt-Winters fit with the parameters given in `params` or their defaults.
+fn fit_holt_winters_params(
+    data: &[f64],
+    method: &str,
+    params: &HashMap<String, f64>,
+) -> Result<crate::seasonal::HoltWintersFit, String> {
+    fit_holt_winters_impl(
+        data,
+        season_length_param(method, params)?,
+        param(params, "alpha", DEFAULT_ALPHA),
+        param(params, "beta", DEFAULT_BETA),
+        param(params, "gamma", DEFAULT_GAMMA),
+    )
+}
+
+/// Fit the method registered under `method` to the data.
+pub(crate) fn fit_method_impl(
+    data: &[f64],
+    method: &str,
+    params: &HashMap<String, f64>,
+) -> Result<FittedState, String> {
+    let min_history = min_history_impl(method, params)?;
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    if data.len() < min_history {
+        return Err(format!(
+            "Method '{}' requires at least {} data points, got {}",
+            method,
+            min_history,
+            data.len()
+        ));
+    }
+
+    match method {
+        "static" => Ok(FittedState::Static { last: data[data.len() - 1] }),
+        "mean" => Ok(FittedState::Mean { mean: predict_mean_impl(data, 1)?[0] }),
+        "linear_trend" => {
+            let (slope, intercept) = fit_linear_trend_impl(data)?;
+            Ok(FittedState::LinearTrend { slope, intercept, n: data.len() })
+        }
+        "exponential_trend" => {
+            let (growth, scale) = fit_exponential_trend_impl(data)?;
+            Ok(FittedState::ExponentialTrend { growth, scale, n: data.len() })
+        }
+        "pct_change" => {
+            let rate = fit_pct_change_impl(data)?;
+            Ok(FittedState::PctChange { last: data[data.len() - 1], rate })
+        }
+        "drift" => {
+            let slope = fit_drift_impl(data)?;
+            Ok(FittedState::Drift { last: data[data.len() - 1], slope })
+        }
+        "ses" => {
+            let levels = ses_levels_impl(data, param(params, "alpha", DEFAULT_ALPHA))?;
+            Ok(FittedState::Ses { level: levels[levels.len() - 1] })
+        }
+        "holt" => {
+            let (levels, trends) = holt_components_impl(
+                data,
+                param(params, "alpha", DEFAULT_ALPHA),
+                param(params, "beta", DEFAULT_BETA),
+            )?;
+            Ok(FittedState::Holt {
+                level: levels[levels.len() - 1],
+                trend: trends[trends.len() - 1],
+            })
+        }
+        "holt_winters" => {
+            let fit = fit_holt_winters_params(data, method, params)?;
+            Ok(FittedState::HoltWinters {
+                level: fit.level,
+                trend: fit.trend,
+                seasonals: fit.seasonals,
+                n: data.len(),
+            })
+        }
+        _ => unreachable!("method names are validated by method_params"),
+    }
+}
+
+/// In-sample fitted values of `method`, one per observation.
+///
+/// For "ses" and "holt" these are one-step-ahead forecasts, so entry `t` uses
+/// data up to `t - 1` and the first entry is NaN; "holt_winters" is the same
+/// but NaN over its whole first (initialization) season. The trend methods return
+/// the fitted curve at each index. Methods without a meaningful in-sample
+/// fit ("static", "mean", "pct_change", "drift") return an empty vector.
+pub(crate) fn fitted_values_impl(
+    data: &[f64],
+    method: &str,
+    params: &HashMap<String, f64>,
+) -> Result<Vec<f64>, String> {
+    check_method_params(method, params)?;
+
+    let n = data.len();
+    match method {
+        "ses" => {
+            let levels = ses_levels_impl(data, param(params, "alpha", DEFAULT_ALPHA))?;
+            Ok(std::iter::once(f64::NAN).chain(levels[..n - 1].iter().copied()).collect())
+        }
+        "holt" => {
+            let (levels, trends) = holt_components_impl(
+                data,
+                param(params, "alpha", DEFAULT_ALPHA),
+                param(params, "beta", DEFAULT_BETA),
+            )?;
+            Ok(std::iter::once(f64::NAN)
+                .chain((0..n - 1).map(|t| levels[t] + trends[t]))
+                .collect())
+        }
+        "holt_winters" => Ok(fit_holt_winters_params(data, method, params)?.fitted),
+        "linear_trend" => {
+            let (slope, intercept) = fit_linear_trend_impl(data)?;
+            Ok(extrapolate_line(slope, intercept, 0, n))
+        }
+        "exponential_trend" => {
+            let (growth, scale) = fit_exponential_trend_impl(data)?;
+            Ok(extrapolate_exponential(growth, scale, 0, n))
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Sum of squared in-sample residuals of `method` with `params`, over the
+/// points that have a fitted value.
+///
+/// This is the objective the smoothing-parameter optimizers minimize.
+pub(crate) fn in_sample_sse_impl(
+    data: &[f64],
+    method: &str,
+    params: &HashMap<String, f64>,
+) -> Result<f64, String> {
+    let fitted = fitted_values_impl(data, method, params)?;
+    if fitted.is_empty() {
+        return Err(format!("Method '{}' has no in-sample fit", method));
+    }
+
+    Ok(data
+        .iter()
+        .zip(&fitted)
+        .filter(|(_, f)| !f.is_nan())
+        .map(|(y, f)| (y - f).powi(2))
+        .sum())
+}
+
+/// Dispatch a forecast to the forecaster registered under `method`.
+pub(crate) fn forecast_with_method_impl(
+    data: &[f64],
+    horizon: usize,
+    method: &str,
+) -> Result<Vec<f64>, String> {
+    fit_method_impl(data, method, &HashMap::new())?.forecast(horizon)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forecast_with_method_static() {
+        let result = forecast_with_method_impl(&[1.0, 2.0, 3.0], 2, "static").unwrap();
+
+        assert_eq!(result, vec![3.0, 3.0]);
+    }
+
+    #[test]
+    fn test_forecast_with_method_unknown() {
+        let result = forecast_with_method_impl(&[1.0, 2.0, 3.0], 2, "magic");
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            format!("Unknown method 'magic', expected one of: {}", METHODS.join(", "))
+        );
+    }
+
+    #[test]
+    fn test_fit_method_unknown_parameter() {
+        let params = HashMap::from([("alpha".to_string(), 0.5)]);
+        let result = fit_method_impl(&[1.0, 2.0, 3.0], "static", &params);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Unknown parameter 'alpha' for method 'static'");
+    }
+
+    #[test]
+    fn test_fit_method_holt_matches_predict_holt() {
+        let data = [3.0, 5.0, 4.0, 7.0, 9.0];
+        let params = HashMap::from([("alpha".to_string(), 0.4), ("beta".to_string(), 0.2)]);
+        let state = fit_method_impl(&data, "holt", &params).unwrap();
+
+        assert_eq!(
+            state.forecast(3).unwrap(),
+            crate::smoothing::predict_holt_impl(&data, 3, 0.4, 0.2).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_fitted_values_ses_reconstructs_levels() {
+        let data = [10.0, 20.0, 20.0, 5.0];
+        let params = HashMap::from([("alpha".to_string(), 0.5)]);
+        let fitted = fitted_values_impl(&data, "ses", &params).unwrap();
+        let levels = ses_levels_impl(&data, 0.5).unwrap();
+
+        assert_eq!(fitted.len(), data.len());
+        assert!(fitted[0].is_nan());
+        assert_eq!(&fitted[1..], &levels[..3]);
+    }
+
+    #[test]
+    fn test_fitted_values_linear_trend_is_fitted_line() {
+        let fitted = fitted_values_impl(&[1.0, 3.0, 5.0], "linear_trend", &HashMap::new()).unwrap();
+
+        assert_eq!(fitted, vec![1.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_min_history_matches_enforced_threshold() {
+        for method in METHODS {
+            let params = if *method == "holt_winters" {
+                HashMap::from([("season_length".to_string(), 3.0)])
+            } else {
+                HashMap::new()
+            };
+            let min = min_history_impl(method, &params).unwrap();
+            let data: Vec<f64> = (1..=min + 1).map(|t| t as f64).collect();
+
+            assert!(fit_method_impl(&data[..min], method, &params).is_ok(), "{}", method);
+            assert!(fit_method_impl(&data[..min - 1], method, &params).is_err(), "{}", method);
+        }
+    }
+
+    #[test]
+    fn test_fit_method_below_min_history() {
+        let result = fit_method_impl(&[1.0], "holt", &HashMap::new());
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Method 'holt' requires at least 2 data points, got 1");
+    }
+
+    #[test]
+    fn test_in_sample_sse_ses() {
+        let params = HashMap::from([("alpha".to_string(), 0.5)]);
+        // One-step forecasts 10, 15, 17.5 against 20, 20, 5
+        let sse = in_sample_sse_impl(&[10.0, 20.0, 20.0, 5.0], "ses", &params).unwrap();
+
+        assert_eq!(sse, 100.0 + 25.0 + 156.25);
+    }
+
+    #[test]
+    fn test_in_sample_sse_without_fit() {
+        let result = in_sample_sse_impl(&[1.0, 2.0], "static", &HashMap::new());
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Method 'static' has no in-sample fit");
+    }
+
+    #[test]
+    fn test_fit_method_holt_winters_matches_predict_holt_winters() {
+        let data: Vec<f64> = (0..16).map(|t| [2.0, 7.0, 4.0, 1.0][t % 4] + 0.5 * t as f64).collect();
+        let params = HashMap::from([("season_length".to_string(), 4.0), ("gamma".to_string(), 0.3)]);
+        let state = fit_method_impl(&data, "holt_winters", &params).unwrap();
+
+        assert_eq!(min_history_impl("holt_winters", &params).unwrap(), 8);
+        assert_eq!(
+            state.forecast(5).unwrap(),
+            crate::seasonal::predict_holt_winters_impl(&data, 5, 4, 0.3, 0.1, 0.3).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_holt_winters_season_length_required() {
+        let result = fit_method_impl(&[1.0; 8], "holt_winters", &HashMap::new());
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Method 'holt_winters' requires parameter 'season_length'"
+        );
+
+        let params = HashMap::from([("season_length".to_string(), 2.5)]);
+        assert_eq!(
+            min_history_impl("holt_winters", &params).unwrap_err(),
+            "Parameter 'season_length' must be a whole number of at least 2, got 2.5"
+        );
+    }
+
+    #[test]
+    fn test_holt_winters_huge_season_length() {
+        let params = HashMap::from([("season_length".to_string(), 2.0_f64.powi(63))]);
+
+        assert_eq!(min_history_impl("holt_winters", &params).unwrap(), usize::MAX);
+        assert_eq!(
+            fit_method_impl(&[1.0; 8], "holt_winters", &params).unwrap_err(),
+            format!(
+                "Method 'holt_winters' requires at least {} data points, got 8",
+                usize::MAX
+            )
+        );
+    }
+
+    #[test]
+    fn test_effective_params_fill_defaults() {
+        let given = HashMap::from([("alpha".to_string(), 0.6)]);
+        let holt = effective_params_impl("holt", &given).unwrap();
+
+        assert_eq!(holt, HashMap::from([("alpha".to_string(), 0.6), ("beta".to_string(), 0.1)]));
+        assert_eq!(effective_params_impl("ses", &HashMap::new()).unwrap()["alpha"], 0.3);
+        assert!(effective_params_impl("drift", &HashMap::new()).unwrap().is_empty());
+    }
+}
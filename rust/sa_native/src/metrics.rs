@@ -0,0 +1,407 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::stats::quantile_sorted;
+
+/// Core forecast comparison logic: `(max_abs_diff, mean_abs_diff, rmse_diff)`.
+pub(crate) fn compare_forecasts_impl(a: &[f64], b: &[f64]) -> Result<(f64, f64, f64), String> {
+    if a.len() != b.len() {
+        return Err(format!(
+            "Forecast lengths must match, got {} and {}",
+            a.len(),
+            b.len()
+        ));
+    }
+
+    if a.is_empty() {
+        return Err("Forecasts cannot be empty".to_string());
+    }
+
+    if a.iter().chain(b).any(|v| !v.is_finite()) {
+        return Err("Forecasts must be finite".to_string());
+    }
+
+    let mut max_abs = 0.0_f64;
+    let mut sum_abs = 0.0;
+    let mut sum_sq = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        let diff = (x - y).abs();
+        max_abs = max_abs.max(diff);
+        sum_abs += diff;
+        sum_sq += diff * diff;
+    }
+
+    let n = a.len() as f64;
+    Ok((max_abs, sum_abs / n, (sum_sq / n).sqrt()))
+}
+
+/// Measure how far two forecasts of the same horizon are apart.
+///
+/// Handy for regression-testing model or parameter changes.
+///
+/// # Returns
+///
+/// Tuple `(max_abs_diff, mean_abs_diff, rmse_diff)`
+///
+/// # Errors
+///
+/// Returns PyValueError if the forecasts are empty, differ in length or
+/// contain non-finite values.
+#[pyfunction]
+pub fn compare_forecasts(a: Vec<f64>, b: Vec<f64>) -> PyResult<(f64, f64, f64)> {
+    compare_forecasts_impl(&a, &b).map_err(PyValueError::new_err)
+}
+
+/// Check that actual and predicted values pair up and are not empty.
+fn validate_paired(actual: &[f64], predicted: &[f64]) -> Result<(), String> {
+    if actual.len() != predicted.len() {
+        return Err(format!(
+            "Actual length ({}) must match predicted length ({})",
+            actual.len(),
+            predicted.len()
+        ));
+    }
+
+    if actual.is_empty() {
+        return Err("Inputs cannot be empty".to_string());
+    }
+
+    Ok(())
+}
+
+/// Core trimmed RMSE logic: RMSE of the squared errors left after dropping
+/// the largest `floor(trim * n)` of them.
+pub(crate) fn trimmed_rmse_impl(
+    actual: &[f64],
+    predicted: &[f64],
+    trim: f64,
+) -> Result<f64, String> {
+    validate_paired(actual, predicted)?;
+
+    if !(0.0..1.0).contains(&trim) {
+        return Err(format!("Trim must be in [0, 1), got {}", trim));
+    }
+
+    let mut squared: Vec<f64> = actual
+        .iter()
+        .zip(predicted)
+        .map(|(a, p)| (a - p).powi(2))
+        .collect();
+    squared.sort_by(f64::total_cmp);
+
+    // trim < 1 always keeps at least one error
+    let kept = squared.len() - (trim * squared.len() as f64).floor() as usize;
+    Ok((squared[..kept].iter().sum::<f64>() / kept as f64).sqrt())
+}
+
+/// Core MSE decomposition logic: `(mse, bias_squared, variance_of_error)`
+/// with the error taken as `predicted - actual` and its population variance,
+/// so that `mse = bias_squared + variance_of_error` up to rounding.
+pub(crate) fn mse_decomposition_impl(
+    actual: &[f64],
+    predicted: &[f64],
+) -> Result<(f64, f64, f64), String> {
+    validate_paired(actual, predicted)?;
+
+    let n = actual.len() as f64;
+    let errors: Vec<f64> = predicted.iter().zip(actual).map(|(p, a)| p - a).collect();
+    let bias = errors.iter().sum::<f64>() / n;
+    let mse = errors.iter().map(|e| e * e).sum::<f64>() / n;
+    let variance = errors.iter().map(|e| (e - bias).powi(2)).sum::<f64>() / n;
+
+    Ok((mse, bias * bias, variance))
+}
+
+/// Core plausibility check: indices of forecast steps whose change exceeds
+/// `max_jump_factor` times the median absolute one-step change of the data.
+pub(crate) fn validate_forecast_plausibility_impl(
+    data: &[f64],
+    forecast: &[f64],
+    max_jump_factor: f64,
+) -> Result<Vec<usize>, String> {
+    if data.len() < 2 {
+        return Err("Plausibility checks require at least two data points".to_string());
+    }
+
+    if data.iter().any(|v| !v.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    if forecast.is_empty() {
+        return Err("Forecast cannot be empty".to_string());
+    }
+
+    if !max_jump_factor.is_finite() || max_jump_factor <= 0.0 {
+        return Err(format!(
+            "Max jump factor must be a finite value greater than 0, got {}",
+            max_jump_factor
+        ));
+    }
+
+    let mut steps: Vec<f64> = data.windows(2).map(|w| (w[1] - w[0]).abs()).collect();
+    steps.sort_by(f64::total_cmp);
+    let limit = max_jump_factor * quantile_sorted(&steps, 0.5);
+
+    let mut previous = data[data.len() - 1];
+    let mut flagged = Vec::new();
+    for (h, &value) in forecast.iter().enumerate() {
+        // A non-finite step can never be plausible
+        let jump = (value - previous).abs();
+        if jump.is_nan() || jump > limit {
+            flagged.push(h);
+        }
+        previous = value;
+    }
+
+    Ok(flagged)
+}
+
+/// RMSE that ignores the worst errors.
+///
+/// The largest `floor(trim * n)` squared errors are discarded before taking
+/// the root mean, so a few catastrophic misses do not dominate a model
+/// comparison. `trim = 0` is the ordinary RMSE.
+///
+/// # Arguments
+///
+/// * `actual` - Observed values
+/// * `predicted` - Forecast values, same length
+/// * `trim` - Fraction of the largest squared errors to discard, in [0, 1)
+///
+/// # Errors
+///
+/// Returns PyValueError if the inputs are empty or differ in length, or trim
+/// is outside [0, 1).
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.trimmed_rmse([1.0, 2.0, 3.0, 4.0], [1.5, 2.5, 3.5, 40.0], 0.25)
+/// # 0.5: the 36-unit miss is discarded
+/// ```
+#[pyfunction]
+pub fn trimmed_rmse(actual: Vec<f64>, predicted: Vec<f64>, trim: f64) -> PyResult<f64> {
+    trimmed_rmse_impl(&actual, &predicted, trim).map_err(PyValueError::new_err)
+}
+
+/// Split the mean squared error into systematic and random parts.
+///
+/// With the error `predicted - actual`, the bias is its mean and the
+/// variance its population variance, so `mse = bias_squared +
+/// variance_of_error`. A forecast that is consistently off shows up as bias;
+/// one that is right on average but noisy shows up as variance.
+///
+/// # Returns
+///
+/// Tuple `(mse, bias_squared, variance_of_error)`
+///
+/// # Errors
+///
+/// Returns PyValueError if the inputs are empty or differ in length.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.mse_decomposition([1.0, 2.0, 3.0], [2.0, 3.0, 4.0])
+/// # (1.0, 1.0, 0.0): the error is all bias
+/// ```
+#[pyfunction]
+pub fn mse_decomposition(actual: Vec<f64>, predicted: Vec<f64>) -> PyResult<(f64, f64, f64)> {
+    mse_decomposition_impl(&actual, &predicted).map_err(PyValueError::new_err)
+}
+
+/// Flag forecast steps that jump implausibly far.
+///
+/// The typical step of the history is the median absolute one-step change.
+/// A forecast step is flagged when its change from the previous forecast
+/// value, or from the last observation for the first step, exceeds
+/// `max_jump_factor` times that typical step. Non-finite forecast values are
+/// always flagged. On a perfectly flat history the typical step is 0, so any
+/// change is flagged.
+///
+/// # Arguments
+///
+/// * `data` - Historical data the forecast was made from
+/// * `forecast` - Forecast to check
+/// * `max_jump_factor` - Allowed multiple of the typical step
+///
+/// # Returns
+///
+/// Indices of the flagged forecast steps, in increasing order
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * data has fewer than two points or a non-finite value
+/// * the forecast is empty
+/// * max_jump_factor is not finite and positive
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.validate_forecast_plausibility([1.0, 2.0, 3.0], [4.0, 20.0, 21.0], 3.0)
+/// # [1]: the step from 4 to 20 is 16 typical steps
+/// ```
+#[pyfunction]
+pub fn validate_forecast_plausibility(
+    data: Vec<f64>,
+    forecast: Vec<f64>,
+    max_jump_factor: f64,
+) -> PyResult<Vec<usize>> {
+    validate_forecast_plausibility_impl(&data, &forecast, max_jump_factor)
+        .map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_compare_forecasts_identical() {
+        let a = vec![1.0, 2.0, 3.0];
+        let result = compare_forecasts_impl(&a, &a).unwrap();
+
+        assert_eq!(result, (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_compare_forecasts_known_offset() {
+        let a = vec![1.0, 2.0, 3.0, 4.0];
+        let b: Vec<f64> = a.iter().map(|x| x + 2.0).collect();
+        let (max_abs, mean_abs, rmse) = compare_forecasts_impl(&a, &b).unwrap();
+
+        assert_eq!(max_abs, 2.0);
+        assert_eq!(mean_abs, 2.0);
+        assert_eq!(rmse, 2.0);
+    }
+
+    #[test]
+    fn test_compare_forecasts_mixed_differences() {
+        let (max_abs, mean_abs, rmse) = compare_forecasts_impl(&[0.0, 0.0], &[3.0, -4.0]).unwrap();
+
+        assert_eq!(max_abs, 4.0);
+        assert_eq!(mean_abs, 3.5);
+        assert!((rmse - 12.5_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_compare_forecasts_length_mismatch() {
+        let result = compare_forecasts_impl(&[1.0], &[1.0, 2.0]);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Forecast lengths must match, got 1 and 2");
+    }
+
+    #[test]
+    fn test_compare_forecasts_rejects_non_finite() {
+        for value in [f64::NAN, f64::INFINITY] {
+            let result = compare_forecasts_impl(&[1.0, value], &[1.0, 3.0]);
+            assert_eq!(result.unwrap_err(), "Forecasts must be finite");
+
+            let result = compare_forecasts_impl(&[1.0, 3.0], &[1.0, value]);
+            assert_eq!(result.unwrap_err(), "Forecasts must be finite");
+        }
+    }
+
+    #[test]
+    fn test_validate_forecast_plausibility_runaway_drift() {
+        let data: Vec<f64> = (0..20).map(|t| t as f64 + [0.2, -0.2][t % 2]).collect();
+        // Steps of 1, 2, 4, 8, 16 and 32 after the last observation
+        let mut level = data[19];
+        let runaway: Vec<f64> = (0..6)
+            .map(|h| {
+                level += 2.0_f64.powi(h);
+                level
+            })
+            .collect();
+
+        // The typical step is 0.6, so only steps above 2.4 are flagged
+        let flagged = validate_forecast_plausibility_impl(&data, &runaway, 4.0).unwrap();
+        assert_eq!(flagged, vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_validate_forecast_plausibility_flat_forecast() {
+        let data = [5.0, 7.0, 6.0, 8.0, 7.0];
+        let flagged = validate_forecast_plausibility_impl(&data, &[7.0; 10], 1.0).unwrap();
+
+        assert!(flagged.is_empty());
+    }
+
+    #[test]
+    fn test_validate_forecast_plausibility_flags_nan() {
+        let flagged =
+            validate_forecast_plausibility_impl(&[1.0, 2.0, 3.0], &[4.0, f64::NAN, 5.0], 5.0);
+
+        assert_eq!(flagged.unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_trimmed_rmse_discards_one_huge_error() {
+        let actual: Vec<f64> = (0..20).map(|t| t as f64).collect();
+        let mut predicted: Vec<f64> =
+            actual.iter().map(|a| a + [0.5, -0.5][*a as usize % 2]).collect();
+        predicted[7] += 1000.0;
+
+        let full = trimmed_rmse_impl(&actual, &predicted, 0.0).unwrap();
+        let trimmed = trimmed_rmse_impl(&actual, &predicted, 0.05).unwrap();
+
+        assert_eq!(full, compare_forecasts_impl(&actual, &predicted).unwrap().2);
+        assert!(full > 100.0);
+        assert!((trimmed - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_trimmed_rmse_invalid_trim() {
+        let result = trimmed_rmse_impl(&[1.0], &[2.0], 1.0);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Trim must be in [0, 1), got 1");
+    }
+
+    #[test]
+    fn test_trimmed_rmse_length_mismatch() {
+        let result = trimmed_rmse_impl(&[1.0, 2.0], &[2.0], 0.1);
+
+        assert_eq!(result.unwrap_err(), "Actual length (2) must match predicted length (1)");
+    }
+
+    #[test]
+    fn test_mse_decomposition_offset_forecast_is_bias() {
+        let mut rng = Rng::new(3);
+        let actual: Vec<f64> = (0..200).map(|t| t as f64).collect();
+        let predicted: Vec<f64> = actual.iter().map(|a| a + 2.0 + 0.05 * rng.normal()).collect();
+        let (mse, bias_squared, variance) = mse_decomposition_impl(&actual, &predicted).unwrap();
+
+        assert!((mse - (bias_squared + variance)).abs() < 1e-9);
+        assert!(bias_squared / mse > 0.99);
+        assert!((bias_squared - 4.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_mse_decomposition_noisy_unbiased_forecast_is_variance() {
+        // Antithetic noise keeps the mean error exactly zero
+        let mut rng = Rng::new(4);
+        let noise: Vec<f64> = (0..100).map(|_| rng.normal()).collect();
+        let actual = vec![10.0; 200];
+        let predicted: Vec<f64> = noise.iter().flat_map(|e| [10.0 + e, 10.0 - e]).collect();
+        let (mse, bias_squared, variance) = mse_decomposition_impl(&actual, &predicted).unwrap();
+
+        assert!((mse - (bias_squared + variance)).abs() < 1e-9);
+        assert!(bias_squared < 1e-20);
+        assert!(variance / mse > 0.99);
+    }
+
+    #[test]
+    fn test_mse_decomposition_validation() {
+        assert_eq!(
+            mse_decomposition_impl(&[1.0], &[1.0, 2.0]).unwrap_err(),
+            "Actual length (1) must match predicted length (2)"
+        );
+        assert_eq!(mse_decomposition_impl(&[], &[]).unwrap_err(), "Inputs cannot be empty");
+    }
+}
@@ -0,0 +1,658 @@
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::methods::{
+    effective_params_impl, fit_method_impl, fitted_values_impl, in_sample_sse_impl,
+    min_history_impl, FittedState,
+};
+
+/// Method used when the requested one cannot be fitted to short data.
+const FALLBACK_METHOD: &str = "static";
+
+/// A forecasting method fitted to a series.
+///
+/// The fitted state is computed once by `fit` and reused by every call to
+/// `forecast`, so forecasting the same series at several horizons does not
+/// refit the model.
+#[pyclass]
+pub struct FittedModel {
+    method: String,
+    state: FittedState,
+}
+
+#[pymethods]
+impl FittedModel {
+    /// Name of the fitted method.
+    #[getter]
+    fn method(&self) -> String {
+        self.method.clone()
+    }
+
+    /// Forecast `horizon` steps past the end of the fitted series.
+    ///
+    /// # Errors
+    ///
+    /// Returns PyValueError if horizon is 0.
+    fn forecast(&self, horizon: usize) -> PyResult<Vec<f64>> {
+        self.state.forecast(horizon).map_err(PyValueError::new_err)
+    }
+}
+
+/// Fit a forecasting method to historical data.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `method` - Forecasting method name (e.g. "static", "linear_trend")
+/// * `params` - Optional method parameters by name
+///
+/// # Returns
+///
+/// A `FittedModel` whose `forecast(horizon)` reuses the fitted state
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * the method or one of the parameter names is unknown
+/// * the method cannot be fitted to the data
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// model = sa_native.fit([1.0, 2.0, 3.0], "linear_trend")
+/// model.forecast(2)   # [4.0, 5.0]
+/// model.forecast(4)   # [4.0, 5.0, 6.0, 7.0], without refitting
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, method, params=None))]
+pub fn fit(
+    data: Vec<f64>,
+    method: String,
+    params: Option<HashMap<String, f64>>,
+) -> PyResult<FittedModel> {
+    let state = fit_method_impl(&data, &method, &params.unwrap_or_default())
+        .map_err(PyValueError::new_err)?;
+    Ok(FittedModel { method, state })
+}
+
+/// Minimum number of observations a method needs.
+///
+/// This is the threshold the method-generic functions enforce, so data of
+/// at least this length never fails for being too short.
+///
+/// # Errors
+///
+/// Returns PyValueError if the method or one of the parameter names is
+/// unknown.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.min_history("linear_trend")   # 2
+/// ```
+#[pyfunction]
+#[pyo3(signature = (method, params=None))]
+pub fn min_history(method: String, params: Option<HashMap<String, f64>>) -> PyResult<usize> {
+    min_history_impl(&method, &params.unwrap_or_default()).map_err(PyValueError::new_err)
+}
+
+/// Sum of squared in-sample residuals of a method.
+///
+/// Residuals are taken against the values in `Forecast.fitted`: one-step-ahead
+/// forecasts for "ses" and "holt", the fitted curve for the trend methods.
+/// This is the objective minimized by `fit_ses`, so custom optimizers can
+/// reuse it.
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * the method or one of the parameter names is unknown
+/// * the method has no in-sample fit ("static", "mean", "pct_change",
+///   "drift")
+/// * the method cannot be fitted to the data
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.in_sample_sse([10.0, 20.0, 20.0], "ses", {"alpha": 0.5})   # 125.0
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, method, params=None))]
+pub fn in_sample_sse(
+    data: Vec<f64>,
+    method: String,
+    params: Option<HashMap<String, f64>>,
+) -> PyResult<f64> {
+    in_sample_sse_impl(&data, &method, &params.unwrap_or_default()).map_err(PyValueError::new_err)
+}
+
+/// Result of the high-level `forecast` function.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct Forecast {
+    /// Point forecasts, one per horizon step.
+    #[pyo3(get)]
+    pub values: Vec<f64>,
+    /// Method that produced the values, which differs from the requested
+    /// one when a fallback was used.
+    #[pyo3(get)]
+    pub method: String,
+    /// Human-readable notes about the forecast, such as fallbacks taken.
+    /// The point forecast is still valid when this is non-empty.
+    #[pyo3(get)]
+    pub warnings: Vec<String>,
+    /// In-sample fitted values aligned with the input data. Smoothing
+    /// methods give one-step-ahead values, NaN at the first point; trend
+    /// methods give the fitted curve. Empty for methods without a fit.
+    #[pyo3(get)]
+    pub fitted: Vec<f64>,
+    /// Parameters the method was fitted with, including defaults and
+    /// auto-fitted values. Empty for methods without parameters.
+    #[pyo3(get)]
+    pub params: HashMap<String, f64>,
+}
+
+/// Direction a monotone forecast may not reverse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Monotone {
+    Increasing,
+    Decreasing,
+}
+
+impl Monotone {
+    pub(crate) fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "increasing" => Ok(Monotone::Increasing),
+            "decreasing" => Ok(Monotone::Decreasing),
+            _ => Err(format!(
+                "Unknown monotone constraint '{}', expected one of: increasing, decreasing",
+                name
+            )),
+        }
+    }
+
+    /// Clamp each value so it never moves against the direction relative to
+    /// the previous one, the first being compared with `start`.
+    pub(crate) fn clamp(self, start: f64, values: &mut [f64]) {
+        let mut previous = start;
+        for value in values.iter_mut() {
+            *value = match self {
+                Monotone::Increasing => value.max(previous),
+                Monotone::Decreasing => value.min(previous),
+            };
+            previous = *value;
+        }
+    }
+}
+
+/// Post-processing options of the high-level `forecast`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ForecastOptions {
+    /// Decimal places to round to; negative values round to tens, hundreds...
+    pub(crate) round_to: Option<i32>,
+    /// Direction the forecast must keep from the last observation on.
+    pub(crate) monotone: Option<Monotone>,
+}
+
+/// Round to `decimals` places with ties going to the even neighbour, so
+/// repeated rounding of halves does not drift upward.
+pub(crate) fn round_half_even(value: f64, decimals: i32) -> f64 {
+    let factor = 10f64.powi(decimals.abs());
+    let scaled = if decimals >= 0 {
+        value * factor
+    } else {
+        value / factor
+    };
+    if !scaled.is_finite() {
+        return value;
+    }
+
+    let rounded = if (scaled - scaled.trunc()).abs() == 0.5 {
+        2.0 * (scaled / 2.0).round()
+    } else {
+        scaled.round()
+    };
+
+    if decimals >= 0 {
+        rounded / factor
+    } else {
+        rounded * factor
+    }
+}
+
+/// Core high-level forecast logic.
+///
+/// When the data is too short for the requested method, or the method
+/// produces non-finite values, the forecast falls back to `FALLBACK_METHOD`
+/// and records a warning instead of failing.
+pub(crate) fn forecast_impl(
+    data: &[f64],
+    horizon: usize,
+    method: &str,
+    params: &HashMap<String, f64>,
+    options: &ForecastOptions,
+) -> Result<Forecast, String> {
+    // Unknown methods and parameters are errors even when the data is short
+    let min_history = min_history_impl(method, params)?;
+
+    let mut warnings = Vec::new();
+    let mut used_method = method;
+
+    let fitted = match fit_method_impl(data, method, params) {
+        Ok(state) => state,
+        Err(_) if !data.is_empty() && data.len() < min_history => {
+            warnings.push(format!(
+                "fell back to {}: insufficient data for {} ({} points, needs {})",
+                FALLBACK_METHOD,
+                method,
+                data.len(),
+                min_history
+            ));
+            used_method = FALLBACK_METHOD;
+            fit_method_impl(data, used_method, &HashMap::new())?
+        }
+        Err(e) => return Err(e),
+    };
+
+    let mut values = fitted.forecast(horizon)?;
+
+    // Overflowing or degenerate fits must not leak NaN/inf into results
+    if used_method != FALLBACK_METHOD && values.iter().any(|v| !v.is_finite()) {
+        warnings.push(format!(
+            "fell back to {}: {} produced non-finite forecasts",
+            FALLBACK_METHOD, used_method
+        ));
+        used_method = FALLBACK_METHOD;
+        values = fit_method_impl(data, used_method, &HashMap::new())?.forecast(horizon)?;
+    }
+
+    let used_params = if used_method == method {
+        params.clone()
+    } else {
+        HashMap::new()
+    };
+    let mut fitted = fitted_values_impl(data, used_method, &used_params)?;
+    let effective_params = effective_params_impl(used_method, &used_params)?;
+
+    // Clamp before rounding: rounding is monotone, so it keeps the order
+    if let Some(monotone) = options.monotone {
+        monotone.clamp(data[data.len() - 1], &mut values);
+    }
+
+    if let Some(decimals) = options.round_to {
+        for value in values.iter_mut().chain(fitted.iter_mut()) {
+            *value = round_half_even(*value, decimals);
+        }
+    }
+
+    Ok(Forecast {
+        values,
+        method: used_method.to_string(),
+        warnings,
+        fitted,
+        params: effective_params,
+    })
+}
+
+/// Forecast future values with the given method, collecting warnings.
+///
+/// Situations worth surfacing that do not prevent a forecast, such as falling
+/// back to a simpler method on short data, are reported in
+/// `Forecast.warnings` rather than raised. A method whose forecast contains
+/// NaN or infinite values is also replaced by the `static` forecast, so the
+/// returned values are always finite for finite input.
+///
+/// `Forecast.fitted` holds in-sample fitted values for plotting the model
+/// over the history: one-step-ahead values for "ses" and "holt" (NaN at the
+/// first point, which has no history) and the fitted curve for the trend
+/// methods. It is empty for methods without an in-sample fit.
+///
+/// With `round_to`, values are rounded in Rust to that many decimals using
+/// round-half-to-even; negative counts round to tens, hundreds and so on.
+///
+/// With `monotone`, the forecast is clamped so it never decreases
+/// ("increasing") or never increases ("decreasing"): each step is held at
+/// the previous value, starting from the last observation, whenever it would
+/// move the wrong way. Fitted values are left as they are.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Number of future values to predict
+/// * `method` - Forecasting method name (e.g. "static", "linear_trend")
+/// * `params` - Optional method parameters by name
+/// * `round_to` - Optional number of decimals to round to (None: no rounding)
+/// * `monotone` - Optional constraint, "increasing" or "decreasing"
+///
+/// # Returns
+///
+/// A `Forecast` with `values`, the `method` actually used, `warnings`,
+/// `fitted` and the `params` it was fitted with
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * data is empty or horizon is 0
+/// * the method or one of the parameter names is unknown
+/// * the method fails for a reason other than insufficient data
+/// * monotone is not "increasing" or "decreasing"
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// result = sa_native.forecast([5.0], 2, "linear_trend")
+/// # result.values == [5.0, 5.0], result.method == "static"
+/// # result.warnings == ["fell back to static: insufficient data for ..."]
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, horizon, method, params=None, round_to=None, monotone=None))]
+pub fn forecast(
+    data: Vec<f64>,
+    horizon: usize,
+    method: String,
+    params: Option<HashMap<String, f64>>,
+    round_to: Option<i32>,
+    monotone: Option<String>,
+) -> PyResult<Forecast> {
+    let monotone = monotone
+        .as_deref()
+        .map(Monotone::parse)
+        .transpose()
+        .map_err(PyValueError::new_err)?;
+    let options = ForecastOptions { round_to, monotone };
+    forecast_impl(
+        &data,
+        horizon,
+        &method,
+        &params.unwrap_or_default(),
+        &options,
+    )
+    .map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::predict_static_impl;
+    use crate::trend::{predict_exponential_trend_impl, predict_linear_trend_impl};
+
+    #[test]
+    fn test_fitted_model_matches_all_in_one_functions() {
+        let data = vec![2.0, 3.0, 5.0, 8.0, 13.0];
+        let no_params = HashMap::new();
+        let static_fit = fit_method_impl(&data, "static", &no_params).unwrap();
+        let linear_fit = fit_method_impl(&data, "linear_trend", &no_params).unwrap();
+        let exp_fit = fit_method_impl(&data, "exponential_trend", &no_params).unwrap();
+
+        for horizon in [1, 5, 10] {
+            assert_eq!(
+                static_fit.forecast(horizon).unwrap(),
+                predict_static_impl(&data, horizon).unwrap()
+            );
+            assert_eq!(
+                linear_fit.forecast(horizon).unwrap(),
+                predict_linear_trend_impl(&data, horizon).unwrap()
+            );
+            assert_eq!(
+                exp_fit.forecast(horizon).unwrap(),
+                predict_exponential_trend_impl(&data, horizon).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_fitted_model_zero_horizon() {
+        let state = fit_method_impl(&[1.0, 2.0], "static", &HashMap::new()).unwrap();
+        let result = state.forecast(0);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Horizon must be greater than 0");
+    }
+
+    #[test]
+    fn test_fit_empty_data() {
+        let result = fit_method_impl(&[], "static", &HashMap::new());
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Input data cannot be empty");
+    }
+
+    #[test]
+    fn test_forecast_short_series_falls_back_with_warning() {
+        let result = forecast_impl(
+            &[5.0],
+            3,
+            "linear_trend",
+            &HashMap::new(),
+            &ForecastOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result.values, vec![5.0, 5.0, 5.0]);
+        assert_eq!(result.method, "static");
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0]
+            .starts_with("fell back to static: insufficient data for linear_trend"));
+    }
+
+    #[test]
+    fn test_forecast_well_behaved_has_no_warnings() {
+        let data = vec![1.0, 2.0, 3.0, 4.0];
+        let result = forecast_impl(
+            &data,
+            2,
+            "linear_trend",
+            &HashMap::new(),
+            &ForecastOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result.method, "linear_trend");
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.values, predict_linear_trend_impl(&data, 2).unwrap());
+    }
+
+    #[test]
+    fn test_forecast_invalid_data_still_errors() {
+        // Non-positive data is not a short-data problem, so there is no fallback
+        let result = forecast_impl(
+            &[1.0, -2.0, 3.0],
+            2,
+            "exponential_trend",
+            &HashMap::new(),
+            &ForecastOptions::default(),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Exponential trend requires strictly positive data"
+        );
+    }
+
+    #[test]
+    fn test_forecast_short_series_unknown_parameter_errors() {
+        let params = HashMap::from([("alpha".to_string(), 0.5)]);
+        let result = forecast_impl(
+            &[5.0],
+            3,
+            "linear_trend",
+            &params,
+            &ForecastOptions::default(),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Unknown parameter 'alpha' for method 'linear_trend'"
+        );
+    }
+
+    #[test]
+    fn test_forecast_non_finite_result_falls_back() {
+        // Compounding a 1e300-fold change overflows to infinity
+        let data = vec![1.0, 1e300];
+        let result = forecast_impl(
+            &data,
+            3,
+            "pct_change",
+            &HashMap::new(),
+            &ForecastOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result.method, "static");
+        assert_eq!(result.values, vec![1e300; 3]);
+        assert!(result.values.iter().all(|v| v.is_finite()));
+        assert_eq!(
+            result.warnings,
+            vec!["fell back to static: pct_change produced non-finite forecasts".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_forecast_empty_data() {
+        let result = forecast_impl(
+            &[],
+            2,
+            "linear_trend",
+            &HashMap::new(),
+            &ForecastOptions::default(),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Input data cannot be empty");
+    }
+
+    #[test]
+    fn test_round_half_even() {
+        assert_eq!(round_half_even(2.5, 0), 2.0);
+        assert_eq!(round_half_even(3.5, 0), 4.0);
+        assert_eq!(round_half_even(-2.5, 0), -2.0);
+        assert_eq!(round_half_even(0.125, 2), 0.12);
+        assert_eq!(round_half_even(0.375, 2), 0.38);
+        assert_eq!(round_half_even(1.23456, 3), 1.235);
+        assert_eq!(round_half_even(1250.0, -2), 1200.0);
+        assert_eq!(round_half_even(1350.0, -2), 1400.0);
+        assert_eq!(round_half_even(1234.0, -1), 1230.0);
+    }
+
+    #[test]
+    fn test_forecast_round_to() {
+        let data = vec![0.0, 1.0 / 3.0, 2.0 / 3.0];
+        let round = |decimals| {
+            let options = ForecastOptions {
+                round_to: Some(decimals),
+                ..Default::default()
+            };
+            forecast_impl(&data, 2, "linear_trend", &HashMap::new(), &options)
+                .unwrap()
+                .values
+        };
+
+        assert_eq!(round(2), vec![1.0, 1.33]);
+        assert_eq!(round(0), vec![1.0, 1.0]);
+
+        let big = vec![1234.0, 1250.0];
+        let options = ForecastOptions {
+            round_to: Some(-2),
+            ..Default::default()
+        };
+        let result = forecast_impl(&big, 1, "static", &HashMap::new(), &options).unwrap();
+        assert_eq!(result.values, vec![1200.0]);
+    }
+
+    #[test]
+    fn test_forecast_monotone_clamps_dips() {
+        // A rising series whose seasonal dips outweigh the trend
+        let pattern = [2.0, -2.0, 1.0, -1.0];
+        let data: Vec<f64> = (0..16).map(|t| 10.0 + 0.5 * t as f64 + pattern[t % 4]).collect();
+        let params = HashMap::from([("season_length".to_string(), 4.0)]);
+        let forecast = |options: &ForecastOptions| {
+            forecast_impl(&data, 8, "holt_winters", &params, options).unwrap()
+        };
+
+        let unconstrained = forecast(&ForecastOptions::default());
+        let raw = fit_method_impl(&data, "holt_winters", &params).unwrap().forecast(8).unwrap();
+        assert_eq!(unconstrained.values, raw);
+        assert!(unconstrained.values.windows(2).any(|w| w[1] < w[0]));
+
+        let clamped = forecast(&ForecastOptions {
+            monotone: Some(Monotone::Increasing),
+            ..Default::default()
+        });
+        assert!(clamped.values[0] >= data[15]);
+        assert!(clamped.values.windows(2).all(|w| w[1] >= w[0]));
+        let mut running_max = data[15];
+        for (value, original) in clamped.values.iter().zip(&raw) {
+            running_max = running_max.max(*original);
+            assert_eq!(*value, running_max);
+        }
+        assert_eq!(clamped.fitted.len(), unconstrained.fitted.len());
+    }
+
+    #[test]
+    fn test_monotone_clamp_holds_previous_value() {
+        let mut values = vec![5.0, 4.0, 6.0, 5.5, 7.0];
+        Monotone::Increasing.clamp(4.5, &mut values);
+        assert_eq!(values, vec![5.0, 5.0, 6.0, 6.0, 7.0]);
+
+        let mut values = vec![5.0, 4.0, 6.0, 3.0];
+        Monotone::Decreasing.clamp(4.5, &mut values);
+        assert_eq!(values, vec![4.5, 4.0, 4.0, 3.0]);
+
+        assert_eq!(
+            Monotone::parse("up").unwrap_err(),
+            "Unknown monotone constraint 'up', expected one of: increasing, decreasing"
+        );
+    }
+
+    #[test]
+    fn test_forecast_round_to_none_leaves_values() {
+        let data = vec![0.0, 1.0 / 3.0];
+        let result = forecast_impl(
+            &data,
+            1,
+            "static",
+            &HashMap::new(),
+            &ForecastOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result.values, vec![1.0 / 3.0]);
+    }
+
+    #[test]
+    fn test_forecast_ses_fitted_reconstructs_levels() {
+        let data = vec![10.0, 20.0, 20.0, 5.0];
+        let params = HashMap::from([("alpha".to_string(), 0.5)]);
+        let result =
+            forecast_impl(&data, 2, "ses", &params, &ForecastOptions::default()).unwrap();
+
+        // The fitted value at t is the level after observing t - 1
+        assert!(result.fitted[0].is_nan());
+        assert_eq!(&result.fitted[1..], &[10.0, 15.0, 17.5]);
+        assert_eq!(result.values, vec![11.25, 11.25]);
+    }
+
+    #[test]
+    fn test_forecast_fallback_has_no_fitted_values() {
+        let result = forecast_impl(
+            &[5.0],
+            1,
+            "holt",
+            &HashMap::new(),
+            &ForecastOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result.method, "static");
+        assert!(result.fitted.is_empty());
+    }
+}
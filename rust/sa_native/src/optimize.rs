@@ -0,0 +1,146 @@
+/// Reflection, expansion, contraction and shrink coefficients of the
+/// Nelder-Mead simplex.
+const REFLECTION: f64 = 1.0;
+const EXPANSION: f64 = 2.0;
+const CONTRACTION: f64 = 0.5;
+const SHRINK: f64 = 0.5;
+
+/// Edge length of the initial simplex around the starting point.
+const INITIAL_STEP: f64 = 0.1;
+
+/// Smallest coordinate a point folded into `(0, 1]` can take.
+const UNIT_FLOOR: f64 = 1e-6;
+
+/// Largest distance of any simplex vertex from the best one, per
+/// coordinate, at which the search may stop.
+const POINT_TOLERANCE: f64 = 1e-8;
+
+/// Fold a coordinate back into `(0, 1]` by mirroring it at the bounds.
+pub(crate) fn reflect_into_unit(x: f64) -> f64 {
+    // Mirroring at 0 and 1 repeats with period 2
+    let folded = x.rem_euclid(2.0);
+    let mirrored = if folded > 1.0 { 2.0 - folded } else { folded };
+    mirrored.max(UNIT_FLOOR)
+}
+
+/// The point `centroid + coefficient * (centroid - worst)`.
+fn along(centroid: &[f64], worst: &[f64], coefficient: f64) -> Vec<f64> {
+    centroid.iter().zip(worst).map(|(c, w)| c + coefficient * (c - w)).collect()
+}
+
+/// A point with every coordinate folded into `(0, 1]`.
+fn fold(point: &[f64]) -> Vec<f64> {
+    point.iter().map(|&x| reflect_into_unit(x)).collect()
+}
+
+/// Minimize `objective` over the box `(0, 1]^d` with the Nelder-Mead
+/// downhill simplex, starting from `start`.
+///
+/// Trial points that leave the box are reflected back into it with
+/// `reflect_into_unit` before the objective is evaluated, so it only sees
+/// valid parameters. The simplex itself keeps the unreflected coordinates:
+/// folding its vertices could map two of them onto the same point and
+/// collapse it. NaN objective values count as infinitely bad. The search
+/// stops once the objective values across the simplex agree to within
+/// `tolerance` relative to the best and the vertices lie within
+/// `POINT_TOLERANCE` of each other, or after `max_iterations`.
+///
+/// Returns the best point found and its objective value.
+pub(crate) fn nelder_mead_unit_box<F>(
+    mut objective: F,
+    start: &[f64],
+    tolerance: f64,
+    max_iterations: usize,
+) -> Result<(Vec<f64>, f64), String>
+where
+    F: FnMut(&[f64]) -> Result<f64, String>,
+{
+    let mut evaluate = |point: Vec<f64>| -> Result<(Vec<f64>, f64), String> {
+        let value = objective(&fold(&point))?;
+        Ok((point, if value.is_nan() { f64::INFINITY } else { value }))
+    };
+
+    let d = start.len();
+    let origin = fold(start);
+    let mut simplex = vec![evaluate(origin.clone())?];
+    for axis in 0..d {
+        // Step away from the nearer bound so each vertex stays distinct
+        let mut vertex = origin.clone();
+        vertex[axis] += if origin[axis] > 0.5 { -INITIAL_STEP } else { INITIAL_STEP };
+        simplex.push(evaluate(vertex)?);
+    }
+
+    for _ in 0..max_iterations {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let best = simplex[0].1;
+        let worst = simplex[d].1;
+        let spread = simplex[1..]
+            .iter()
+            .flat_map(|(point, _)| point.iter().zip(&simplex[0].0).map(|(x, b)| (x - b).abs()))
+            .fold(0.0_f64, f64::max);
+        if worst - best <= tolerance * (best.abs() + tolerance) && spread <= POINT_TOLERANCE {
+            break;
+        }
+
+        let centroid: Vec<f64> = (0..d)
+            .map(|i| simplex[..d].iter().map(|(point, _)| point[i]).sum::<f64>() / d as f64)
+            .collect();
+
+        let reflected = evaluate(along(&centroid, &simplex[d].0, REFLECTION))?;
+        if reflected.1 < best {
+            let expanded = evaluate(along(&centroid, &simplex[d].0, EXPANSION))?;
+            simplex[d] = if expanded.1 < reflected.1 { expanded } else { reflected };
+        } else if reflected.1 < simplex[d - 1].1 {
+            simplex[d] = reflected;
+        } else {
+            // Contract toward the better of the reflected and worst points
+            let outside = reflected.1 < worst;
+            let coefficient = if outside { CONTRACTION } else { -CONTRACTION };
+            let contracted = evaluate(along(&centroid, &simplex[d].0, coefficient))?;
+            if contracted.1 < reflected.1.min(worst) {
+                simplex[d] = contracted;
+            } else {
+                let anchor = simplex[0].0.clone();
+                for vertex in simplex.iter_mut().skip(1) {
+                    let shrunk = anchor
+                        .iter()
+                        .zip(&vertex.0)
+                        .map(|(a, x)| a + SHRINK * (x - a))
+                        .collect();
+                    *vertex = evaluate(shrunk)?;
+                }
+            }
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let (point, value) = simplex.swap_remove(0);
+    Ok((fold(&point), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reflect_into_unit_mirrors_at_bounds() {
+        assert_eq!(reflect_into_unit(0.25), 0.25);
+        assert_eq!(reflect_into_unit(1.0), 1.0);
+        assert!((reflect_into_unit(1.25) - 0.75).abs() < 1e-12);
+        assert!((reflect_into_unit(-0.25) - 0.25).abs() < 1e-12);
+        assert_eq!(reflect_into_unit(0.0), UNIT_FLOOR);
+    }
+
+    #[test]
+    fn test_nelder_mead_finds_interior_and_boundary_minima() {
+        // Minimum at (0.3, 0.7) inside the box, and at x = 1 on the bound
+        let bowl = |p: &[f64]| Ok((p[0] - 0.3).powi(2) + 2.0 * (p[1] - 0.7).powi(2));
+        let (point, value) = nelder_mead_unit_box(bowl, &[0.9, 0.1], 1e-14, 1000).unwrap();
+        assert!((point[0] - 0.3).abs() < 1e-5 && (point[1] - 0.7).abs() < 1e-5);
+        assert!(value < 1e-10);
+
+        let slope = |p: &[f64]| Ok((p[0] - 1.5).powi(2));
+        let (point, _) = nelder_mead_unit_box(slope, &[0.2], 1e-14, 1000).unwrap();
+        assert!(point[0] > 0.999 && point[0] <= 1.0);
+    }
+}
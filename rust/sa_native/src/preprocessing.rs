@@ -0,0 +1,573 @@
+use std::collections::BTreeMap;
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::smoothing::validate_window;
+
+/// Core duplicate-timestamp collapsing logic.
+pub(crate) fn collapse_duplicates_impl(
+    timestamps: &[i64],
+    values: &[f64],
+    op: &str,
+) -> Result<(Vec<i64>, Vec<f64>), String> {
+    if timestamps.len() != values.len() {
+        return Err(format!(
+            "Timestamps length ({}) must match values length ({})",
+            timestamps.len(),
+            values.len()
+        ));
+    }
+
+    if !matches!(op, "sum" | "mean" | "last") {
+        return Err(format!(
+            "Unknown op '{}', expected one of: sum, mean, last",
+            op
+        ));
+    }
+
+    // Values per timestamp, kept in input order so "last" is well defined
+    let mut groups: BTreeMap<i64, Vec<f64>> = BTreeMap::new();
+    for (t, v) in timestamps.iter().zip(values) {
+        groups.entry(*t).or_default().push(*v);
+    }
+
+    let mut out_timestamps = Vec::with_capacity(groups.len());
+    let mut out_values = Vec::with_capacity(groups.len());
+    for (t, group) in groups {
+        let reduced = match op {
+            "sum" => group.iter().sum(),
+            "mean" => group.iter().sum::<f64>() / group.len() as f64,
+            _ => group[group.len() - 1],
+        };
+        out_timestamps.push(t);
+        out_values.push(reduced);
+    }
+
+    Ok((out_timestamps, out_values))
+}
+
+/// Core linear resampling logic.
+pub(crate) fn resample_to_length_impl(data: &[f64], target_len: usize) -> Result<Vec<f64>, String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    if target_len < 2 {
+        return Err("Target length must be at least 2".to_string());
+    }
+
+    let last = data.len() - 1;
+    let step = last as f64 / (target_len - 1) as f64;
+    Ok((0..target_len)
+        .map(|k| {
+            let position = k as f64 * step;
+            let left = (position.floor() as usize).min(last);
+            let right = (left + 1).min(last);
+            let frac = position - left as f64;
+            data[left] + frac * (data[right] - data[left])
+        })
+        .collect())
+}
+
+/// Core length normalization logic: keep the last `target_len` values, or
+/// left-pad with `pad_value` up to `target_len`.
+pub(crate) fn fit_to_length_impl(data: &[f64], target_len: usize, pad_value: f64) -> Vec<f64> {
+    if data.len() >= target_len {
+        return data[data.len() - target_len..].to_vec();
+    }
+
+    let mut fitted = vec![pad_value; target_len - data.len()];
+    fitted.extend_from_slice(data);
+    fitted
+}
+
+/// Core rolling rank logic: at each index from `window - 1`, the fraction of
+/// the trailing `window` values (the current one included) that are less
+/// than or equal to the current value.
+pub(crate) fn rolling_rank_impl(data: &[f64], window: usize) -> Result<Vec<f64>, String> {
+    validate_window(window, data.len())?;
+
+    if data.iter().any(|v| !v.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    let mut ranks = vec![f64::NAN; data.len()];
+    for (rank, span) in ranks[window - 1..].iter_mut().zip(data.windows(window)) {
+        let current = span[window - 1];
+        *rank = span.iter().filter(|v| **v <= current).count() as f64 / window as f64;
+    }
+
+    Ok(ranks)
+}
+
+/// Core linear imputation logic: every non-finite value is replaced by linear
+/// interpolation between its nearest finite neighbours, or by the nearest
+/// finite value at either end of the series.
+pub(crate) fn impute_linear_impl(data: &[f64]) -> Result<Vec<f64>, String> {
+    let known: Vec<usize> = (0..data.len()).filter(|&i| data[i].is_finite()).collect();
+    let (first, last) = match (known.first(), known.last()) {
+        (Some(&first), Some(&last)) => (first, last),
+        _ => return Err("At least one finite value is required for imputation".to_string()),
+    };
+
+    let mut filled = data.to_vec();
+    for value in &mut filled[..first] {
+        *value = data[first];
+    }
+    for value in &mut filled[last + 1..] {
+        *value = data[last];
+    }
+    for pair in known.windows(2) {
+        let (left, right) = (pair[0], pair[1]);
+        let span = (right - left) as f64;
+        for (i, value) in filled.iter_mut().enumerate().take(right).skip(left + 1) {
+            let frac = (i - left) as f64 / span;
+            *value = data[left] + frac * (data[right] - data[left]);
+        }
+    }
+
+    Ok(filled)
+}
+
+/// Core event masking logic.
+pub(crate) fn mask_events_impl(data: &[f64], event_indices: &[usize]) -> Result<Vec<f64>, String> {
+    if let Some(&index) = event_indices.iter().find(|&&i| i >= data.len()) {
+        return Err(format!(
+            "Event index {} is out of range for data of length {}",
+            index,
+            data.len()
+        ));
+    }
+
+    let mut masked = data.to_vec();
+    for &index in event_indices {
+        masked[index] = f64::NAN;
+    }
+
+    impute_linear_impl(&masked)
+}
+
+/// Shared timestamps and the values of each series at them.
+type Aligned = (Vec<i64>, Vec<f64>, Vec<f64>);
+
+/// Values of one series keyed by timestamp, rejecting mismatched lengths and
+/// repeated timestamps; `name` identifies the series in errors.
+fn index_by_timestamp(
+    name: &str,
+    timestamps: &[i64],
+    values: &[f64],
+) -> Result<BTreeMap<i64, f64>, String> {
+    if timestamps.len() != values.len() {
+        return Err(format!(
+            "Series {}: timestamps length ({}) must match values length ({})",
+            name,
+            timestamps.len(),
+            values.len()
+        ));
+    }
+
+    let mut indexed = BTreeMap::new();
+    for (t, v) in timestamps.iter().zip(values) {
+        if indexed.insert(*t, *v).is_some() {
+            return Err(format!(
+                "Series {}: timestamp {} appears more than once; collapse duplicates first",
+                name, t
+            ));
+        }
+    }
+
+    Ok(indexed)
+}
+
+/// Core alignment logic: the inner join of two series on their timestamps,
+/// in increasing timestamp order.
+pub(crate) fn align_series_impl(
+    ts_a: &[i64],
+    val_a: &[f64],
+    ts_b: &[i64],
+    val_b: &[f64],
+) -> Result<Aligned, String> {
+    let a = index_by_timestamp("a", ts_a, val_a)?;
+    let b = index_by_timestamp("b", ts_b, val_b)?;
+
+    let mut timestamps = Vec::new();
+    let mut aligned_a = Vec::new();
+    let mut aligned_b = Vec::new();
+    for (t, value_a) in &a {
+        if let Some(value_b) = b.get(t) {
+            timestamps.push(*t);
+            aligned_a.push(*value_a);
+            aligned_b.push(*value_b);
+        }
+    }
+
+    Ok((timestamps, aligned_a, aligned_b))
+}
+
+/// Core timestamp ordering check: reports the first adjacent pair that is
+/// not strictly increasing.
+pub(crate) fn check_monotonic_timestamps_impl(timestamps: &[i64]) -> Result<(), String> {
+    match timestamps.windows(2).position(|w| w[0] >= w[1]) {
+        Some(i) => Err(format!(
+            "Timestamps must be strictly increasing: timestamps[{}] = {} is not after timestamps[{}] = {}",
+            i + 1,
+            timestamps[i + 1],
+            i,
+            timestamps[i]
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Collapse values sharing a timestamp into one value per timestamp.
+///
+/// # Arguments
+///
+/// * `timestamps` - Time bucket of each value, in any order
+/// * `values` - Observed values
+/// * `op` - Reduction applied to each group: "sum", "mean" or "last" (the
+///   value appearing last in the input)
+///
+/// # Returns
+///
+/// `(timestamps, values)` with unique timestamps in increasing order
+///
+/// # Errors
+///
+/// Returns PyValueError if the lengths differ or the op is unknown.
+#[pyfunction]
+pub fn collapse_duplicates(
+    timestamps: Vec<i64>,
+    values: Vec<f64>,
+    op: String,
+) -> PyResult<(Vec<i64>, Vec<f64>)> {
+    collapse_duplicates_impl(&timestamps, &values, &op).map_err(PyValueError::new_err)
+}
+
+/// Resample a series onto `target_len` evenly spaced points.
+///
+/// The new points span the original index range `[0, len(data) - 1]`, so the
+/// first and last values are kept and intermediate ones are linearly
+/// interpolated between their neighbours.
+///
+/// # Errors
+///
+/// Returns PyValueError if data is empty or target_len is less than 2.
+#[pyfunction]
+pub fn resample_to_length(data: Vec<f64>, target_len: usize) -> PyResult<Vec<f64>> {
+    resample_to_length_impl(&data, target_len).map_err(PyValueError::new_err)
+}
+
+/// Pad or truncate a series to exactly `target_len` values, e.g. for a
+/// fixed-size model input.
+///
+/// The end of the series is treated as the part that matters: a series that
+/// is too long loses its oldest values from the front, keeping the most
+/// recent `target_len`, and one that is too short is left-padded with
+/// `pad_value` (which may be NaN) so its latest value stays last.
+#[pyfunction]
+pub fn fit_to_length(data: Vec<f64>, target_len: usize, pad_value: f64) -> PyResult<Vec<f64>> {
+    Ok(fit_to_length_impl(&data, target_len, pad_value))
+}
+
+/// Percentile rank of each value within its trailing window.
+///
+/// Entry `i` is the fraction of `data[i + 1 - window..=i]`, the current
+/// value included, that is less than or equal to `data[i]`: 1.0 for a new
+/// high of the window and `1 / window` for a strict new low. Ties count as
+/// at or below, so a flat stretch ranks 1.0.
+///
+/// # Returns
+///
+/// A vector of the same length as data, with values in `(0, 1]`; the first
+/// `window - 1` entries are NaN because no full window ends there
+///
+/// # Errors
+///
+/// Returns PyValueError if window is 0 or exceeds the data length, or if
+/// data has a non-finite value.
+#[pyfunction]
+pub fn rolling_rank(data: Vec<f64>, window: usize) -> PyResult<Vec<f64>> {
+    rolling_rank_impl(&data, window).map_err(PyValueError::new_err)
+}
+
+/// Fill missing values by linear interpolation.
+///
+/// NaN and infinite values are treated as missing. Gaps between two finite
+/// values are linearly interpolated; leading and trailing gaps take the
+/// nearest finite value.
+///
+/// # Errors
+///
+/// Returns PyValueError if the data has no finite value.
+#[pyfunction]
+pub fn impute_linear(data: Vec<f64>) -> PyResult<Vec<f64>> {
+    impute_linear_impl(&data).map_err(PyValueError::new_err)
+}
+
+/// Replace known one-off event values so they do not mislead forecasters.
+///
+/// The values at `event_indices` are discarded and re-imputed from their
+/// neighbours exactly as `impute_linear` would.
+///
+/// # Errors
+///
+/// Returns PyValueError if an index is out of range, or if no finite value
+/// remains after masking.
+#[pyfunction]
+pub fn mask_events(data: Vec<f64>, event_indices: Vec<usize>) -> PyResult<Vec<f64>> {
+    mask_events_impl(&data, &event_indices).map_err(PyValueError::new_err)
+}
+
+/// Align two series on the timestamps they share.
+///
+/// Inner-joins the series: only timestamps present in both are kept,
+/// returned in increasing order with the value of each series at them.
+/// Inputs may be in any order, but each series must have unique timestamps
+/// (see `collapse_duplicates`).
+///
+/// # Returns
+///
+/// `(timestamps, values_a, values_b)`, all of the same length
+///
+/// # Errors
+///
+/// Returns PyValueError if a series has mismatched timestamp and value
+/// lengths or a repeated timestamp.
+#[pyfunction]
+pub fn align_series(
+    ts_a: Vec<i64>,
+    val_a: Vec<f64>,
+    ts_b: Vec<i64>,
+    val_b: Vec<f64>,
+) -> PyResult<Aligned> {
+    align_series_impl(&ts_a, &val_a, &ts_b, &val_b).map_err(PyValueError::new_err)
+}
+
+/// Check that timestamps are strictly increasing before resampling or
+/// aggregating values against them.
+///
+/// Empty and single-element inputs pass.
+///
+/// # Errors
+///
+/// Returns PyValueError naming the first adjacent pair that is out of order
+/// or duplicated.
+#[pyfunction]
+pub fn check_monotonic_timestamps(timestamps: Vec<i64>) -> PyResult<()> {
+    check_monotonic_timestamps_impl(&timestamps).map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collapse_duplicates_out_of_order() {
+        let timestamps = vec![3, 1, 3, 2, 1];
+        let values = vec![1.0, 2.0, 3.0, 4.0, 6.0];
+
+        let (ts, sums) = collapse_duplicates_impl(&timestamps, &values, "sum").unwrap();
+        assert_eq!(ts, vec![1, 2, 3]);
+        assert_eq!(sums, vec![8.0, 4.0, 4.0]);
+
+        let (_, means) = collapse_duplicates_impl(&timestamps, &values, "mean").unwrap();
+        assert_eq!(means, vec![4.0, 4.0, 2.0]);
+
+        let (_, lasts) = collapse_duplicates_impl(&timestamps, &values, "last").unwrap();
+        assert_eq!(lasts, vec![6.0, 4.0, 3.0]);
+    }
+
+    #[test]
+    fn test_collapse_duplicates_length_mismatch() {
+        let result = collapse_duplicates_impl(&[1, 2], &[1.0], "sum");
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Timestamps length (2) must match values length (1)"
+        );
+    }
+
+    #[test]
+    fn test_collapse_duplicates_unknown_op() {
+        let result = collapse_duplicates_impl(&[1], &[1.0], "max");
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Unknown op 'max', expected one of: sum, mean, last");
+    }
+
+    #[test]
+    fn test_align_series_keeps_sorted_intersection() {
+        let ts_a = [5, 1, 3, 7, 2];
+        let val_a = [50.0, 10.0, 30.0, 70.0, 20.0];
+        let ts_b = [3, 8, 2, 5, 0];
+        let val_b = [-3.0, -8.0, -2.0, -5.0, 0.0];
+        let (timestamps, a, b) = align_series_impl(&ts_a, &val_a, &ts_b, &val_b).unwrap();
+
+        assert_eq!(timestamps, vec![2, 3, 5]);
+        assert_eq!(a, vec![20.0, 30.0, 50.0]);
+        assert_eq!(b, vec![-2.0, -3.0, -5.0]);
+    }
+
+    #[test]
+    fn test_align_series_validation() {
+        assert_eq!(
+            align_series_impl(&[1, 2], &[1.0, 2.0], &[1], &[]).unwrap_err(),
+            "Series b: timestamps length (1) must match values length (0)"
+        );
+        assert_eq!(
+            align_series_impl(&[1, 1], &[1.0, 2.0], &[1], &[3.0]).unwrap_err(),
+            "Series a: timestamp 1 appears more than once; collapse duplicates first"
+        );
+    }
+
+    #[test]
+    fn test_resample_to_same_length_is_identity() {
+        let data = vec![3.0, -1.0, 4.0, 1.5, 9.0];
+        let result = resample_to_length_impl(&data, data.len()).unwrap();
+
+        for (a, b) in result.iter().zip(&data) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_resample_upsampling_linear_stays_linear() {
+        let data = vec![0.0, 2.0, 4.0, 6.0];
+        let result = resample_to_length_impl(&data, 7).unwrap();
+
+        for (k, value) in result.iter().enumerate() {
+            assert!((value - k as f64).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_resample_downsampling_keeps_endpoints() {
+        let data: Vec<f64> = (0..100).map(|t| (t as f64 * 0.3).sin()).collect();
+        let result = resample_to_length_impl(&data, 10).unwrap();
+
+        assert_eq!(result.len(), 10);
+        assert_eq!(result[0], data[0]);
+        assert!((result[9] - data[99]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_resample_invalid_target() {
+        let result = resample_to_length_impl(&[1.0, 2.0], 1);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Target length must be at least 2");
+    }
+
+    #[test]
+    fn test_fit_to_length_truncates_from_front() {
+        let result = fit_to_length_impl(&[1.0, 2.0, 3.0, 4.0, 5.0], 3, 0.0);
+
+        assert_eq!(result, vec![3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_fit_to_length_left_pads() {
+        let result = fit_to_length_impl(&[1.0, 2.0], 5, -1.0);
+
+        assert_eq!(result, vec![-1.0, -1.0, -1.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_fit_to_length_exact_length_is_identity() {
+        let data = vec![3.0, f64::NAN, 4.0];
+        let result = fit_to_length_impl(&data, 3, 0.0);
+
+        assert_eq!(result[0], 3.0);
+        assert!(result[1].is_nan());
+        assert_eq!(result[2], 4.0);
+    }
+
+    #[test]
+    fn test_rolling_rank_new_high_ranks_one() {
+        let data = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0];
+        let result = rolling_rank_impl(&data, 4).unwrap();
+
+        assert!(result[..3].iter().all(|r| r.is_nan()));
+        // [1, 4, 1, 5] and [4, 1, 5, 9] end on new highs; [1, 5, 9, 2] has 1, 2 <= 2
+        assert_eq!(&result[3..], &[0.5, 1.0, 1.0, 0.5]);
+    }
+
+    #[test]
+    fn test_rolling_rank_invalid_window() {
+        assert_eq!(rolling_rank_impl(&[1.0], 0).unwrap_err(), "Window must be greater than 0");
+        assert_eq!(
+            rolling_rank_impl(&[1.0, 2.0], 3).unwrap_err(),
+            "Window (3) cannot exceed data length (2)"
+        );
+    }
+
+    #[test]
+    fn test_impute_linear_fills_gaps_and_edges() {
+        let data = vec![f64::NAN, 1.0, f64::NAN, f64::NAN, 4.0, f64::INFINITY];
+        let result = impute_linear_impl(&data).unwrap();
+
+        assert_eq!(result, vec![1.0, 1.0, 2.0, 3.0, 4.0, 4.0]);
+    }
+
+    #[test]
+    fn test_impute_linear_all_missing() {
+        let result = impute_linear_impl(&[f64::NAN, f64::NAN]);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "At least one finite value is required for imputation"
+        );
+    }
+
+    #[test]
+    fn test_mask_events_smooths_spike() {
+        let data = vec![10.0, 11.0, 500.0, 13.0, 14.0];
+        let result = mask_events_impl(&data, &[2]).unwrap();
+
+        assert_eq!(result, vec![10.0, 11.0, 12.0, 13.0, 14.0]);
+    }
+
+    #[test]
+    fn test_mask_events_out_of_range() {
+        let result = mask_events_impl(&[1.0, 2.0], &[0, 5]);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Event index 5 is out of range for data of length 2"
+        );
+    }
+
+    #[test]
+    fn test_check_monotonic_timestamps_sorted() {
+        assert!(check_monotonic_timestamps_impl(&[-3, 0, 10, 11, 400]).is_ok());
+        assert!(check_monotonic_timestamps_impl(&[7]).is_ok());
+        assert!(check_monotonic_timestamps_impl(&[]).is_ok());
+    }
+
+    #[test]
+    fn test_check_monotonic_timestamps_out_of_order() {
+        let result = check_monotonic_timestamps_impl(&[1, 2, 9, 5, 3]);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Timestamps must be strictly increasing: timestamps[3] = 5 is not after timestamps[2] = 9"
+        );
+    }
+
+    #[test]
+    fn test_check_monotonic_timestamps_duplicate() {
+        let result = check_monotonic_timestamps_impl(&[1, 4, 4]);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Timestamps must be strictly increasing: timestamps[2] = 4 is not after timestamps[1] = 4"
+        );
+    }
+}
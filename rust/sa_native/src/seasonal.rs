@@ -0,0 +1,1155 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::linalg::least_squares;
+use crate::methods::in_sample_sse_impl;
+use crate::optimize::nelder_mead_unit_box;
+use crate::smoothing::validate_smoothing;
+
+/// Convergence tolerance and iteration cap of `fit_holt_winters_nm_impl`.
+const HW_NM_TOLERANCE: f64 = 1e-10;
+const HW_NM_MAX_ITERATIONS: usize = 2000;
+
+/// Values of each smoothing parameter tried when seeding the simplex.
+const HW_NM_SEEDS: [f64; 3] = [0.1, 0.5, 0.9];
+
+/// Check that a season length is usable and that the data holds at least two
+/// full seasons.
+pub(crate) fn validate_seasons(n: usize, season_length: usize) -> Result<(), String> {
+    if season_length < 2 {
+        return Err("Season length must be at least 2".to_string());
+    }
+
+    // Halving n rather than doubling season_length cannot overflow
+    if season_length > n / 2 {
+        return Err(format!(
+            "At least two full seasons ({} points) are required, got {}",
+            2 * season_length as u128,
+            n
+        ));
+    }
+
+    Ok(())
+}
+
+/// Centered moving average over one season, the classical decomposition
+/// trend estimate. Even periods use the `2 x period` average (half weights at
+/// both ends) so the window stays centered. Entries without a full window at
+/// either edge are NaN.
+pub(crate) fn centered_moving_average(data: &[f64], period: usize) -> Vec<f64> {
+    let half = period / 2;
+    let mut trend = vec![f64::NAN; data.len()];
+    if data.len() < 2 * half + 1 {
+        return trend;
+    }
+
+    for t in half..data.len() - half {
+        let window = &data[t - half..=t + half];
+        trend[t] = if 2 * half == period {
+            let inner: f64 = window[1..period].iter().sum();
+            (inner + 0.5 * (window[0] + window[period])) / period as f64
+        } else {
+            window.iter().sum::<f64>() / period as f64
+        };
+    }
+
+    trend
+}
+
+/// Classical additive decomposition `y = T + S + R`, returning the trend and
+/// the seasonal component per phase (phase 0 is the first observation).
+///
+/// The trend is the centered moving average over one season, NaN at the
+/// edges; the seasonal component is the zero-mean average of the detrended
+/// values for each phase. Callers validate the season length first.
+pub(crate) fn classical_decomposition(data: &[f64], season_length: usize) -> (Vec<f64>, Vec<f64>) {
+    let trend = centered_moving_average(data, season_length);
+
+    let mut sums = vec![0.0; season_length];
+    let mut counts = vec![0usize; season_length];
+    for (t, (y, level)) in data.iter().zip(&trend).enumerate() {
+        if !level.is_nan() {
+            sums[t % season_length] += y - level;
+            counts[t % season_length] += 1;
+        }
+    }
+    let mut seasonal: Vec<f64> = sums.iter().zip(&counts).map(|(s, c)| s / *c as f64).collect();
+    let seasonal_mean = seasonal.iter().sum::<f64>() / season_length as f64;
+    for value in seasonal.iter_mut() {
+        *value -= seasonal_mean;
+    }
+
+    (trend, seasonal)
+}
+
+/// Fitted trend-plus-seasonal-dummy regression.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SeasonalDummyFit {
+    pub(crate) intercept: f64,
+    pub(crate) slope: f64,
+    /// Offset of each season phase relative to phase 0 (so `offsets[0] == 0`).
+    pub(crate) offsets: Vec<f64>,
+}
+
+impl SeasonalDummyFit {
+    /// Fitted value at time index `t`.
+    pub(crate) fn value_at(&self, t: usize) -> f64 {
+        self.intercept + self.slope * t as f64 + self.offsets[t % self.offsets.len()]
+    }
+}
+
+/// Core seasonal dummy regression: OLS of the data on an intercept, a linear
+/// trend and `season_length - 1` dummies, phase 0 being the baseline.
+pub(crate) fn fit_seasonal_dummy_impl(
+    data: &[f64],
+    season_length: usize,
+) -> Result<SeasonalDummyFit, String> {
+    validate_seasons(data.len(), season_length)?;
+
+    if data.iter().any(|y| !y.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    let rows: Vec<Vec<f64>> = (0..data.len())
+        .map(|t| {
+            let mut row = vec![0.0; season_length + 1];
+            row[0] = 1.0;
+            row[1] = t as f64;
+            let phase = t % season_length;
+            if phase > 0 {
+                row[phase + 1] = 1.0;
+            }
+            row
+        })
+        .collect();
+
+    let coef = least_squares(&rows, data)?;
+    let mut offsets = vec![0.0];
+    offsets.extend_from_slice(&coef[2..]);
+
+    Ok(SeasonalDummyFit { intercept: coef[0], slope: coef[1], offsets })
+}
+
+/// Core seasonal dummy prediction logic.
+pub(crate) fn predict_seasonal_dummy_impl(
+    data: &[f64],
+    horizon: usize,
+    season_length: usize,
+) -> Result<Vec<f64>, String> {
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let fit = fit_seasonal_dummy_impl(data, season_length)?;
+    let n = data.len();
+    Ok((n..n + horizon).map(|t| fit.value_at(t)).collect())
+}
+
+/// Core cyclic extrapolation logic: the average of the complete cycles that
+/// end at the last observation, tiled over the horizon.
+pub(crate) fn predict_cyclic_impl(
+    data: &[f64],
+    horizon: usize,
+    period: usize,
+) -> Result<Vec<f64>, String> {
+    if period == 0 {
+        return Err("Period must be greater than 0".to_string());
+    }
+
+    if data.len() < period {
+        return Err(format!(
+            "At least one full cycle ({} points) is required, got {}",
+            period,
+            data.len()
+        ));
+    }
+
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    // Cycles are counted back from the end, so an incomplete cycle can only
+    // be at the start and the forecast continues the phase of the last point
+    let cycles = data.len() / period;
+    let start = data.len() - cycles * period;
+    let mut cycle = vec![0.0; period];
+    for chunk in data[start..].chunks(period) {
+        for (sum, value) in cycle.iter_mut().zip(chunk) {
+            *sum += value / cycles as f64;
+        }
+    }
+
+    Ok((0..horizon).map(|h| cycle[h % period]).collect())
+}
+
+/// State of additive Holt-Winters smoothing after the last observation.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct HoltWintersFit {
+    pub(crate) level: f64,
+    pub(crate) trend: f64,
+    /// Seasonal term of each phase, indexed by `t % season_length`.
+    pub(crate) seasonals: Vec<f64>,
+    /// One-step-ahead fitted values; NaN over the first season, which only
+    /// initializes the state.
+    pub(crate) fitted: Vec<f64>,
+}
+
+impl HoltWintersFit {
+    /// Forecast `horizon` steps past the end of a series of length `n`.
+    pub(crate) fn forecast(&self, n: usize, horizon: usize) -> Vec<f64> {
+        let m = self.seasonals.len();
+        (1..=horizon)
+            .map(|h| self.level + h as f64 * self.trend + self.seasonals[(n + h - 1) % m])
+            .collect()
+    }
+}
+
+/// Core additive Holt-Winters smoothing.
+///
+/// The trend starts at the per-step change between the first two season
+/// means, the seasonal terms at the first season's deviations from that
+/// trend line, and the level at the line's value at the end of the first
+/// season. Updates run from the second season on.
+pub(crate) fn fit_holt_winters_impl(
+    data: &[f64],
+    season_length: usize,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+) -> Result<HoltWintersFit, String> {
+    validate_seasons(data.len(), season_length)?;
+    validate_smoothing("Alpha", alpha)?;
+    validate_smoothing("Beta", beta)?;
+    validate_smoothing("Gamma", gamma)?;
+
+    let m = season_length;
+    let first_mean = data[..m].iter().sum::<f64>() / m as f64;
+    let second_mean = data[m..2 * m].iter().sum::<f64>() / m as f64;
+
+    // The season mean sits at mid-season: detrend the first season around
+    // it, then move the level to the season's last point
+    let mut trend = (second_mean - first_mean) / m as f64;
+    let middle = (m - 1) as f64 / 2.0;
+    let mut seasonals: Vec<f64> = data[..m]
+        .iter()
+        .enumerate()
+        .map(|(t, y)| y - (first_mean + trend * (t as f64 - middle)))
+        .collect();
+    let mut level = first_mean + trend * middle;
+    let mut fitted = vec![f64::NAN; data.len()];
+
+    for t in m..data.len() {
+        let phase = t % m;
+        let season = seasonals[phase];
+        fitted[t] = level + trend + season;
+
+        let previous = level;
+        level = alpha * (data[t] - season) + (1.0 - alpha) * (level + trend);
+        trend = beta * (level - previous) + (1.0 - beta) * trend;
+        seasonals[phase] = gamma * (data[t] - level) + (1.0 - gamma) * season;
+    }
+
+    Ok(HoltWintersFit { level, trend, seasonals, fitted })
+}
+
+/// Core additive Holt-Winters prediction logic.
+pub(crate) fn predict_holt_winters_impl(
+    data: &[f64],
+    horizon: usize,
+    season_length: usize,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+) -> Result<Vec<f64>, String> {
+    let fit = fit_holt_winters_impl(data, season_length, alpha, beta, gamma)?;
+
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    Ok(fit.forecast(data.len(), horizon))
+}
+
+/// Core mixed Holt-Winters prediction logic: additive level and trend with
+/// multiplicative seasonal factors, forecasting `(level + h * trend) * season`.
+///
+/// Initialized as the additive fit, with the seasonal factors the first
+/// season's ratios to its trend line. Every factor must stay positive: the
+/// series must be positive and the level must remain so while smoothing.
+pub(crate) fn predict_holt_winters_mixed_impl(
+    data: &[f64],
+    horizon: usize,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    season_length: usize,
+) -> Result<Vec<f64>, String> {
+    validate_seasons(data.len(), season_length)?;
+    validate_smoothing("Alpha", alpha)?;
+    validate_smoothing("Beta", beta)?;
+    validate_smoothing("Gamma", gamma)?;
+
+    if let Some(y) = data.iter().find(|y| !(y.is_finite() && **y > 0.0)) {
+        return Err(format!(
+            "Multiplicative seasonality requires finite positive data, got {}",
+            y
+        ));
+    }
+
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let m = season_length;
+    let first_mean = data[..m].iter().sum::<f64>() / m as f64;
+    let second_mean = data[m..2 * m].iter().sum::<f64>() / m as f64;
+
+    let mut trend = (second_mean - first_mean) / m as f64;
+    let middle = (m - 1) as f64 / 2.0;
+    let mut seasonals = Vec::with_capacity(m);
+    for (t, y) in data[..m].iter().enumerate() {
+        let line = first_mean + trend * (t as f64 - middle);
+        if line <= 0.0 {
+            return Err(format!(
+                "Initial trend line reaches {} at index {}; seasonal factors need a positive level",
+                line, t
+            ));
+        }
+        seasonals.push(y / line);
+    }
+    let mut level = first_mean + trend * middle;
+
+    for (t, y) in data.iter().enumerate().skip(m) {
+        let phase = t % m;
+        let season = seasonals[phase];
+
+        let previous = level;
+        level = alpha * (y / season) + (1.0 - alpha) * (level + trend);
+        if !(level > 0.0 && level.is_finite()) {
+            return Err(format!(
+                "Level fell to {} at index {}; seasonal factors need a positive level",
+                level, t
+            ));
+        }
+        trend = beta * (level - previous) + (1.0 - beta) * trend;
+        seasonals[phase] = gamma * (y / level) + (1.0 - gamma) * season;
+    }
+
+    let n = data.len();
+    Ok((1..=horizon)
+        .map(|h| (level + h as f64 * trend) * seasonals[(n + h - 1) % m])
+        .collect())
+}
+
+/// Core Holt-Winters fitting logic: the `(alpha, beta, gamma)` in `(0, 1]^3`
+/// minimizing the in-sample one-step SSE, the same objective as
+/// `in_sample_sse` for "holt_winters".
+///
+/// The simplex starts from the best point of a coarse seed grid and the
+/// search is restarted once from its result, since a simplex can collapse
+/// before reaching the minimum.
+pub(crate) fn fit_holt_winters_nm_impl(
+    data: &[f64],
+    season_length: usize,
+) -> Result<(f64, f64, f64), String> {
+    validate_seasons(data.len(), season_length)?;
+
+    if data.iter().any(|y| !y.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    let sse = |params: &[f64]| -> Result<f64, String> {
+        let params = HashMap::from([
+            ("season_length".to_string(), season_length as f64),
+            ("alpha".to_string(), params[0]),
+            ("beta".to_string(), params[1]),
+            ("gamma".to_string(), params[2]),
+        ]);
+        in_sample_sse_impl(data, "holt_winters", &params)
+    };
+
+    let mut start = (vec![HW_NM_SEEDS[0]; 3], f64::INFINITY);
+    for alpha in HW_NM_SEEDS {
+        for beta in HW_NM_SEEDS {
+            for gamma in HW_NM_SEEDS {
+                let point = vec![alpha, beta, gamma];
+                let value = sse(&point)?;
+                if value < start.1 {
+                    start = (point, value);
+                }
+            }
+        }
+    }
+
+    let (first, _) = nelder_mead_unit_box(sse, &start.0, HW_NM_TOLERANCE, HW_NM_MAX_ITERATIONS)?;
+    let (best, _) = nelder_mead_unit_box(sse, &first, HW_NM_TOLERANCE, HW_NM_MAX_ITERATIONS)?;
+
+    Ok((best[0], best[1], best[2]))
+}
+
+/// Core logic for the final seasonal terms of an additive Holt-Winters fit.
+pub(crate) fn holt_winters_seasonal_indices_impl(
+    data: &[f64],
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    season_length: usize,
+) -> Result<Vec<f64>, String> {
+    Ok(fit_holt_winters_impl(data, season_length, alpha, beta, gamma)?.seasonals)
+}
+
+/// Check that month labels are in 1..=12 and pair up with `n` values.
+fn validate_months(months: &[u32], n: usize) -> Result<(), String> {
+    if months.len() != n {
+        return Err(format!("Months length ({}) must match values length ({})", months.len(), n));
+    }
+
+    if let Some(month) = months.iter().find(|m| !(1..=12).contains(*m)) {
+        return Err(format!("Months must be in 1..=12, got {}", month));
+    }
+
+    Ok(())
+}
+
+/// Core monthly profile logic: the mean of each calendar month divided by
+/// the average of the monthly means, NaN for months without data.
+pub(crate) fn monthly_seasonal_profile_impl(
+    values: &[f64],
+    months: &[u32],
+) -> Result<Vec<f64>, String> {
+    if values.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    validate_months(months, values.len())?;
+
+    if values.iter().any(|v| !v.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    let mut sums = [0.0; 12];
+    let mut counts = [0usize; 12];
+    for (value, month) in values.iter().zip(months) {
+        sums[*month as usize - 1] += value;
+        counts[*month as usize - 1] += 1;
+    }
+
+    let means: Vec<f64> = sums
+        .iter()
+        .zip(&counts)
+        .map(|(sum, &count)| if count > 0 { sum / count as f64 } else { f64::NAN })
+        .collect();
+    let observed: Vec<f64> = means.iter().copied().filter(|m| !m.is_nan()).collect();
+    let overall = observed.iter().sum::<f64>() / observed.len() as f64;
+    if overall <= 0.0 {
+        return Err(format!(
+            "A multiplicative monthly profile requires a positive average level, got {}",
+            overall
+        ));
+    }
+
+    Ok(means.iter().map(|m| m / overall).collect())
+}
+
+/// Core monthly profile application: each value multiplied by the index of
+/// its calendar month.
+pub(crate) fn apply_monthly_profile_impl(
+    forecast: &[f64],
+    months: &[u32],
+    profile: &[f64],
+) -> Result<Vec<f64>, String> {
+    if profile.len() != 12 {
+        return Err(format!("Monthly profile must have 12 indices, got {}", profile.len()));
+    }
+
+    validate_months(months, forecast.len())?;
+
+    forecast
+        .iter()
+        .zip(months)
+        .map(|(value, month)| match profile[*month as usize - 1] {
+            index if index.is_finite() => Ok(value * index),
+            _ => Err(format!("Monthly profile has no finite index for month {}", month)),
+        })
+        .collect()
+}
+
+/// Core Fourier basis logic: one row per time step `t` holding
+/// `sin(2 pi k t / period), cos(2 pi k t / period)` for `k = 1..=n_harmonics`.
+pub(crate) fn fourier_terms_impl(
+    n: usize,
+    period: usize,
+    n_harmonics: usize,
+) -> Result<Vec<Vec<f64>>, String> {
+    if n == 0 {
+        return Err("Number of time steps must be greater than 0".to_string());
+    }
+
+    if n_harmonics == 0 {
+        return Err("Number of harmonics must be greater than 0".to_string());
+    }
+
+    // At k = period / 2 the sine column vanishes and higher k alias lower ones
+    if n_harmonics >= period.div_ceil(2) {
+        return Err(format!(
+            "Harmonics must satisfy 2 * n_harmonics < period, got {} harmonics for period {}",
+            n_harmonics, period
+        ));
+    }
+
+    Ok((0..n)
+        .map(|t| {
+            (1..=n_harmonics)
+                .flat_map(|k| {
+                    let angle = 2.0 * PI * (k * t % period) as f64 / period as f64;
+                    [angle.sin(), angle.cos()]
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Predict future values with a linear trend plus seasonal dummy regression.
+///
+/// Fits `y_t = a + b*t + c_{t mod s}` by ordinary least squares, using
+/// `season_length - 1` dummy regressors with phase 0 as the baseline, and
+/// extrapolates it. Suited to explicit effects such as month-of-year.
+///
+/// # Arguments
+///
+/// * `data` - Historical data, phase 0 being the first observation
+/// * `horizon` - Number of future values to predict
+/// * `season_length` - Number of observations per season (e.g. 12 for monthly)
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * season_length is less than 2
+/// * data holds fewer than two full seasons or a non-finite value
+/// * horizon is 0
+#[pyfunction]
+pub fn predict_seasonal_dummy(
+    data: Vec<f64>,
+    horizon: usize,
+    season_length: usize,
+) -> PyResult<Vec<f64>> {
+    predict_seasonal_dummy_impl(&data, horizon, season_length).map_err(PyValueError::new_err)
+}
+
+/// Forecast a repeating pattern by its average cycle.
+///
+/// The history is cut into complete cycles of `period` points, counted back
+/// from the last observation (leading points that do not fill a cycle are
+/// ignored). The cycles are averaged point by point into a representative
+/// cycle, which is tiled across the horizon continuing from the phase of the
+/// last observation. Unlike a seasonal-naive forecast, which copies the last
+/// cycle, averaging smooths out noise; there is no level or trend
+/// adjustment, so it suits data that is essentially periodic.
+///
+/// # Arguments
+///
+/// * `data` - Historical data
+/// * `horizon` - Number of future values to predict
+/// * `period` - Number of observations per cycle
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * period is 0
+/// * data holds less than one full cycle
+/// * horizon is 0
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.predict_cyclic([1.0, 5.0, 3.0, 5.0, 3.0], 3, 2)
+/// # [5.0, 3.0, 5.0]: the leading 1.0 does not fill a cycle
+/// ```
+#[pyfunction]
+pub fn predict_cyclic(data: Vec<f64>, horizon: usize, period: usize) -> PyResult<Vec<f64>> {
+    predict_cyclic_impl(&data, horizon, period).map_err(PyValueError::new_err)
+}
+
+/// Predict future values with additive Holt-Winters smoothing.
+///
+/// Level, trend and seasonal terms are smoothed with `alpha`, `beta` and
+/// `gamma`; the forecast `h` steps ahead is `level + h * trend + season`,
+/// with the seasonal term of the matching phase. The first season only
+/// initializes the state, so at least two full seasons are required.
+///
+/// # Arguments
+///
+/// * `data` - Historical data, phase 0 being the first observation
+/// * `horizon` - Number of future values to predict
+/// * `season_length` - Number of observations per season
+/// * `alpha`, `beta`, `gamma` - Smoothing parameters in `(0, 1]`
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * season_length is less than 2 or data holds fewer than two full seasons
+/// * a smoothing parameter is out of range
+/// * horizon is 0
+#[pyfunction]
+pub fn predict_holt_winters(
+    data: Vec<f64>,
+    horizon: usize,
+    season_length: usize,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+) -> PyResult<Vec<f64>> {
+    predict_holt_winters_impl(&data, horizon, season_length, alpha, beta, gamma)
+        .map_err(PyValueError::new_err)
+}
+
+/// Predict with Holt-Winters smoothing, additive trend and multiplicative
+/// season.
+///
+/// Level and trend are smoothed additively, as in `predict_holt_winters`,
+/// while the seasonal component is a factor per phase, smoothed from the
+/// ratios `y / level`. The forecast `h` steps ahead is
+/// `(level + h * trend) * season`, so seasonal swings grow in proportion to
+/// the trending level rather than staying a fixed size. The first season
+/// only initializes the state, so at least two full seasons are required.
+///
+/// # Arguments
+///
+/// * `data` - Historical data, strictly positive, phase 0 being the first
+///   observation
+/// * `horizon` - Number of future values to predict
+/// * `alpha`, `beta`, `gamma` - Smoothing parameters in `(0, 1]`
+/// * `season_length` - Number of observations per season
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * season_length is less than 2 or data holds fewer than two full seasons
+/// * a smoothing parameter is out of range, or horizon is 0
+/// * a value is not finite and positive, or the smoothed level stops being
+///   positive, which would make the seasonal factors meaningless
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.predict_holt_winters_mixed(sales, 12, 0.3, 0.1, 0.1, 12)
+/// ```
+#[pyfunction]
+pub fn predict_holt_winters_mixed(
+    data: Vec<f64>,
+    horizon: usize,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    season_length: usize,
+) -> PyResult<Vec<f64>> {
+    predict_holt_winters_mixed_impl(&data, horizon, alpha, beta, gamma, season_length)
+        .map_err(PyValueError::new_err)
+}
+
+/// Fit additive Holt-Winters smoothing parameters by Nelder-Mead search.
+///
+/// Minimizes the in-sample one-step SSE, the objective of
+/// `in_sample_sse(data, "holt_winters", ...)`, over `(0, 1]^3` with a
+/// downhill simplex. Trial points that stray outside the box are reflected
+/// back into it. This is far cheaper than a fine grid over three parameters
+/// and not confined to its resolution.
+///
+/// # Arguments
+///
+/// * `data` - Historical data, phase 0 being the first observation
+/// * `season_length` - Number of observations per season
+///
+/// # Returns
+///
+/// Tuple `(alpha, beta, gamma)`
+///
+/// # Errors
+///
+/// Returns PyValueError if season_length is less than 2, data holds fewer
+/// than two full seasons, or data has a non-finite value.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// alpha, beta, gamma = sa_native.fit_holt_winters_nm(sales, 12)
+/// sa_native.predict_holt_winters(sales, 12, 12, alpha, beta, gamma)
+/// ```
+#[pyfunction]
+pub fn fit_holt_winters_nm(data: Vec<f64>, season_length: usize) -> PyResult<(f64, f64, f64)> {
+    fit_holt_winters_nm_impl(&data, season_length).map_err(PyValueError::new_err)
+}
+
+/// Seasonal profile learned by additive Holt-Winters smoothing.
+///
+/// Returns the `season_length` seasonal terms left at the end of the fit,
+/// indexed by phase: element `p` belongs to observations `t` with
+/// `t % season_length == p`, phase 0 being the first observation. The terms
+/// are additive offsets from the level, so they sum to roughly zero and can
+/// be compared across series.
+///
+/// # Arguments
+///
+/// * `data` - Historical data, phase 0 being the first observation
+/// * `alpha`, `beta`, `gamma` - Smoothing parameters in `(0, 1]`
+/// * `season_length` - Number of observations per season
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * season_length is less than 2 or data holds fewer than two full seasons
+/// * a smoothing parameter is out of range
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// profile = sa_native.holt_winters_seasonal_indices(sales, 0.3, 0.1, 0.2, 12)
+/// peak_month = max(range(12), key=profile.__getitem__)
+/// ```
+#[pyfunction]
+pub fn holt_winters_seasonal_indices(
+    data: Vec<f64>,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    season_length: usize,
+) -> PyResult<Vec<f64>> {
+    holt_winters_seasonal_indices_impl(&data, alpha, beta, gamma, season_length)
+        .map_err(PyValueError::new_err)
+}
+
+/// Fourier regressors for a seasonal period, for use in custom regressions.
+///
+/// Row `t` holds `sin(2 pi k t / period)` and `cos(2 pi k t / period)` for
+/// each harmonic `k = 1..=n_harmonics`, in that order, so the matrix has
+/// `n` rows and `2 * n_harmonics` columns. Row 0 is phase 0 of the season.
+///
+/// # Arguments
+///
+/// * `n` - Number of time steps (rows)
+/// * `period` - Season length in time steps
+/// * `n_harmonics` - Number of sine/cosine pairs
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * n or n_harmonics is 0
+/// * `2 * n_harmonics >= period`, where the columns would alias
+///
+/// # Examples
+///
+/// ```python
+/// import numpy as np
+/// import sa_native
+/// X = np.array(sa_native.fourier_terms(48, 12, 2))
+/// # X.shape == (48, 4): sin/cos of the yearly and half-yearly cycles
+/// ```
+#[pyfunction]
+pub fn fourier_terms(n: usize, period: usize, n_harmonics: usize) -> PyResult<Vec<Vec<f64>>> {
+    fourier_terms_impl(n, period, n_harmonics).map_err(PyValueError::new_err)
+}
+
+/// Average seasonal index of each calendar month.
+///
+/// Fixed-period seasonality counts observations, which drifts when the data
+/// is irregular (e.g. missing months); labelling each value with its month
+/// ties the pattern to the calendar instead. The index of a month is the
+/// mean of its values divided by the average of the twelve monthly means,
+/// so 1.2 means 20% above a typical month and the observed indices average
+/// 1.0. Divide history by the indices to deseasonalize it, forecast, and
+/// reseasonalize with `apply_monthly_profile`. The profile has no trend
+/// adjustment, so strongly trending data should be detrended first.
+///
+/// # Arguments
+///
+/// * `values` - Observed values
+/// * `months` - Calendar month of each value, 1 (January) to 12 (December)
+///
+/// # Returns
+///
+/// Twelve indices, entry `m - 1` for month `m`; NaN for a month without any
+/// values
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * values is empty or has a non-finite value
+/// * the lengths differ or a month is outside 1..=12
+/// * the average monthly mean is not positive
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// profile = sa_native.monthly_seasonal_profile(sales, months)
+/// deseasonalized = [v / profile[m - 1] for v, m in zip(sales, months)]
+/// base = sa_native.predict_ses(deseasonalized, 6, 0.3)
+/// forecast = sa_native.apply_monthly_profile(base, [1, 2, 3, 4, 5, 6], profile)
+/// ```
+#[pyfunction]
+pub fn monthly_seasonal_profile(values: Vec<f64>, months: Vec<u32>) -> PyResult<Vec<f64>> {
+    monthly_seasonal_profile_impl(&values, &months).map_err(PyValueError::new_err)
+}
+
+/// Reseasonalize a forecast with a monthly profile.
+///
+/// Multiplies each forecast value by the index of its calendar month from
+/// `monthly_seasonal_profile`, turning a forecast of deseasonalized data
+/// back into one with the monthly pattern.
+///
+/// # Arguments
+///
+/// * `forecast` - Deseasonalized forecast values
+/// * `months` - Calendar month of each forecast step, 1 to 12
+/// * `profile` - Twelve monthly indices
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * the profile does not have 12 indices
+/// * the lengths differ or a month is outside 1..=12
+/// * a forecast step falls in a month whose index is not finite
+#[pyfunction]
+pub fn apply_monthly_profile(
+    forecast: Vec<f64>,
+    months: Vec<u32>,
+    profile: Vec<f64>,
+) -> PyResult<Vec<f64>> {
+    apply_monthly_profile_impl(&forecast, &months, &profile).map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_fit_seasonal_dummy_recovers_offsets() {
+        let offsets = [0.0, 5.0, -3.0, 2.0, 8.0, -1.0, 0.5, 4.0, -6.0, 1.0, 3.0, 10.0];
+        let data: Vec<f64> = (0..36)
+            .map(|t| 100.0 + 0.5 * t as f64 + offsets[t % 12])
+            .collect();
+        let fit = fit_seasonal_dummy_impl(&data, 12).unwrap();
+
+        assert!((fit.intercept - 100.0).abs() < 1e-6);
+        assert!((fit.slope - 0.5).abs() < 1e-6);
+        for (fitted, expected) in fit.offsets.iter().zip(&offsets) {
+            assert!((fitted - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_predict_seasonal_dummy_continues_pattern() {
+        let pattern = [1.0, 4.0, 2.0];
+        let data: Vec<f64> = (0..9).map(|t| 2.0 * t as f64 + pattern[t % 3]).collect();
+        let result = predict_seasonal_dummy_impl(&data, 3, 3).unwrap();
+
+        for (h, value) in result.iter().enumerate() {
+            let t = 9 + h;
+            assert!((value - (2.0 * t as f64 + pattern[t % 3])).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_predict_seasonal_dummy_requires_two_seasons() {
+        let result = predict_seasonal_dummy_impl(&[1.0; 7], 2, 4);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "At least two full seasons (8 points) are required, got 7"
+        );
+    }
+
+    #[test]
+    fn test_predict_seasonal_dummy_rejects_non_finite_data() {
+        let mut data = [1.0, 3.0, 2.0, 1.0, 3.0, 2.0];
+        data[4] = f64::NAN;
+        let result = predict_seasonal_dummy_impl(&data, 2, 3);
+
+        assert_eq!(result.unwrap_err(), "Input data must be finite");
+    }
+
+    #[test]
+    fn test_validate_seasons_huge_season_length() {
+        let season_length = 1usize << 63;
+        let expected =
+            "At least two full seasons (18446744073709551616 points) are required, got 8";
+
+        assert_eq!(validate_seasons(8, season_length).unwrap_err(), expected);
+        let result = predict_holt_winters_impl(&[1.0; 8], 1, season_length, 0.5, 0.1, 0.1);
+        assert_eq!(result.unwrap_err(), expected);
+        assert_eq!(predict_seasonal_dummy_impl(&[1.0; 8], 1, season_length).unwrap_err(), expected);
+        let result = crate::stats::series_features_impl(&[1.0; 8], season_length);
+        assert_eq!(result.unwrap_err(), expected);
+
+        // Auto selection skips the seasonal candidate instead of panicking
+        let data: Vec<f64> = (0..8).map(|t| t as f64).collect();
+        let result = crate::auto::auto_forecast_impl(&data, 2, Some(season_length), 0.5).unwrap();
+        assert_eq!(result.warnings, vec![format!("skipped holt_winters: {}", expected)]);
+    }
+
+    #[test]
+    fn test_centered_moving_average_removes_season_from_line() {
+        let pattern = [3.0, -1.0, -4.0, 2.0];
+        let data: Vec<f64> = (0..12).map(|t| 1.0 + 0.5 * t as f64 + pattern[t % 4]).collect();
+        let trend = centered_moving_average(&data, 4);
+
+        assert!(trend[..2].iter().chain(&trend[10..]).all(|v| v.is_nan()));
+        for (t, value) in trend.iter().enumerate().take(10).skip(2) {
+            assert!((value - (1.0 + 0.5 * t as f64)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_predict_cyclic_averages_noisy_cycles() {
+        let pattern = [4.0, 7.0, 5.0, 1.0, 3.0];
+        let mut rng = Rng::new(13);
+        // 2 leading points, then 20 noisy cycles starting at pattern[0]
+        let data: Vec<f64> = (3..105).map(|t| pattern[t % 5] + 0.5 * rng.normal()).collect();
+        let result = predict_cyclic_impl(&data, 7, 5).unwrap();
+
+        for (h, value) in result.iter().enumerate() {
+            assert!((value - pattern[h % 5]).abs() < 0.3);
+        }
+        assert_eq!(result[5], result[0]);
+    }
+
+    #[test]
+    fn test_predict_cyclic_requires_full_cycle() {
+        let result = predict_cyclic_impl(&[1.0, 2.0], 3, 3);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "At least one full cycle (3 points) is required, got 2");
+        assert_eq!(predict_cyclic_impl(&[1.0], 3, 0).unwrap_err(), "Period must be greater than 0");
+    }
+
+    #[test]
+    fn test_predict_holt_winters_continues_exact_pattern() {
+        let pattern = [4.0, -1.0, 2.0, -5.0];
+        let series = |t: usize| 50.0 + 0.75 * t as f64 + pattern[t % 4];
+        let data: Vec<f64> = (0..24).map(series).collect();
+        let result = predict_holt_winters_impl(&data, 6, 4, 0.3, 0.1, 0.2).unwrap();
+
+        for (h, value) in result.iter().enumerate() {
+            assert!((value - series(24 + h)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_fit_holt_winters_fitted_values_are_one_step() {
+        let data: Vec<f64> = (0..12).map(|t| [1.0, 5.0, 3.0][t % 3] + t as f64).collect();
+        let fit = fit_holt_winters_impl(&data, 3, 0.5, 0.5, 0.5).unwrap();
+
+        assert!(fit.fitted[..3].iter().all(|v| v.is_nan()));
+        assert!(fit.fitted[3..].iter().all(|v| v.is_finite()));
+        // The one-step forecast at t uses only data before t
+        let prefix = fit_holt_winters_impl(&data[..8], 3, 0.5, 0.5, 0.5).unwrap();
+        assert_eq!(prefix.forecast(8, 1)[0], fit.fitted[8]);
+    }
+
+    #[test]
+    fn test_predict_holt_winters_invalid_gamma() {
+        let result = predict_holt_winters_impl(&[1.0; 8], 2, 4, 0.5, 0.5, 0.0);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Gamma must be in (0, 1], got 0");
+    }
+
+    #[test]
+    fn test_predict_holt_winters_mixed_scales_season_with_level() {
+        let factors = [0.8, 1.2, 1.1, 0.9];
+        let truth = |t: usize| (10.0 + 0.5 * t as f64) * factors[t % 4];
+        let data: Vec<f64> = (0..48).map(truth).collect();
+        let forecast = predict_holt_winters_mixed_impl(&data, 8, 0.3, 0.1, 0.1, 4).unwrap();
+
+        let amplitude = |values: &[f64]| {
+            let max = values.iter().cloned().fold(f64::MIN, f64::max);
+            let min = values.iter().cloned().fold(f64::MAX, f64::min);
+            max - min
+        };
+        let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+
+        // The next two seasons rise and swing wider, like the true series
+        assert!(mean(&forecast[4..]) > mean(&forecast[..4]));
+        assert!(mean(&forecast[..4]) > mean(&data[44..]));
+        assert!(amplitude(&forecast[4..]) > amplitude(&forecast[..4]));
+        assert!(amplitude(&forecast[..4]) > amplitude(&data[44..]));
+        for (h, value) in forecast.iter().enumerate() {
+            assert!((value / truth(48 + h) - 1.0).abs() < 0.02);
+        }
+    }
+
+    #[test]
+    fn test_predict_holt_winters_mixed_requires_positive_data() {
+        let mut data = vec![5.0; 8];
+        data[3] = -1.0;
+        let result = predict_holt_winters_mixed_impl(&data, 2, 0.3, 0.1, 0.1, 4);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Multiplicative seasonality requires finite positive data, got -1"
+        );
+    }
+
+    #[test]
+    fn test_fit_holt_winters_nm_no_worse_than_grid() {
+        let pattern = [3.0, -2.0, 1.5, -2.5];
+        let mut rng = Rng::new(21);
+        let data: Vec<f64> = (0..60)
+            .map(|t| 40.0 + 0.3 * t as f64 + pattern[t % 4] + 0.8 * rng.normal())
+            .collect();
+        let sse = |alpha: f64, beta: f64, gamma: f64| {
+            let fit = fit_holt_winters_impl(&data, 4, alpha, beta, gamma).unwrap();
+            data[4..].iter().zip(&fit.fitted[4..]).map(|(y, f)| (y - f).powi(2)).sum::<f64>()
+        };
+
+        let steps: Vec<f64> = (1..=20).map(|i| i as f64 * 0.05).collect();
+        let mut grid_best = f64::INFINITY;
+        for &alpha in &steps {
+            for &beta in &steps {
+                for &gamma in &steps {
+                    grid_best = grid_best.min(sse(alpha, beta, gamma));
+                }
+            }
+        }
+
+        let (alpha, beta, gamma) = fit_holt_winters_nm_impl(&data, 4).unwrap();
+        for value in [alpha, beta, gamma] {
+            assert!(value > 0.0 && value <= 1.0);
+        }
+        assert!(sse(alpha, beta, gamma) <= grid_best * (1.0 + 1e-6));
+    }
+
+    #[test]
+    fn test_fit_holt_winters_nm_requires_two_seasons() {
+        let result = fit_holt_winters_nm_impl(&[1.0; 7], 4);
+
+        assert_eq!(
+            result.unwrap_err(),
+            "At least two full seasons (8 points) are required, got 7"
+        );
+    }
+
+    #[test]
+    fn test_fit_holt_winters_nm_rejects_non_finite_data() {
+        let mut data: Vec<f64> = (0..16).map(|t| [3.0, 5.0, 4.0, 1.0][t % 4]).collect();
+        data[9] = f64::NAN;
+        let result = fit_holt_winters_nm_impl(&data, 4);
+
+        assert_eq!(result.unwrap_err(), "Input data must be finite");
+    }
+
+    #[test]
+    fn test_holt_winters_seasonal_indices_recover_pattern() {
+        // The pattern has mean 1, which belongs to the level, not the season
+        let pattern = [5.0, 0.0, 3.0, -4.0];
+        let data: Vec<f64> = (0..24).map(|t| 20.0 - 0.5 * t as f64 + pattern[t % 4]).collect();
+        let indices = holt_winters_seasonal_indices_impl(&data, 0.3, 0.1, 0.2, 4).unwrap();
+
+        for (index, expected) in indices.iter().zip([4.0, -1.0, 2.0, -5.0]) {
+            assert!((index - expected).abs() < 1e-9);
+        }
+        assert!(indices.iter().sum::<f64>().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_holt_winters_seasonal_indices_requires_two_seasons() {
+        let result = holt_winters_seasonal_indices_impl(&[1.0; 7], 0.5, 0.5, 0.5, 4);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "At least two full seasons (8 points) are required, got 7"
+        );
+    }
+
+    #[test]
+    fn test_fourier_terms_first_sine_column() {
+        let terms = fourier_terms_impl(36, 12, 2).unwrap();
+        assert_eq!(terms.len(), 36);
+        assert!(terms.iter().all(|row| row.len() == 4));
+
+        let sine: Vec<f64> = terms.iter().map(|row| row[0]).collect();
+        for t in 0..24 {
+            assert!((sine[t] - sine[t + 12]).abs() < 1e-12);
+        }
+        let peak = sine.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let trough = sine.iter().cloned().fold(f64::INFINITY, f64::min);
+        assert!((peak - 1.0).abs() < 1e-12 && (trough + 1.0).abs() < 1e-12);
+        assert!((sine[3] - 1.0).abs() < 1e-12);
+        assert!(sine[0].abs() < 1e-12 && terms[0][1] == 1.0);
+    }
+
+    #[test]
+    fn test_fourier_terms_aliasing() {
+        assert!(fourier_terms_impl(10, 5, 2).is_ok());
+
+        let result = fourier_terms_impl(10, 4, 2);
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Harmonics must satisfy 2 * n_harmonics < period, got 2 harmonics for period 4"
+        );
+    }
+
+    #[test]
+    fn test_fourier_terms_huge_harmonics() {
+        let result = fourier_terms_impl(1, 5, 1usize << 63);
+
+        assert_eq!(
+            result.unwrap_err(),
+            "Harmonics must satisfy 2 * n_harmonics < period, got 9223372036854775808 harmonics \
+             for period 5"
+        );
+    }
+
+    #[test]
+    fn test_monthly_seasonal_profile_finds_december_peak() {
+        let mut rng = Rng::new(12);
+        let months: Vec<u32> = (0..24).map(|t| t % 12 + 1).collect();
+        let values: Vec<f64> = months
+            .iter()
+            .map(|&m| if m == 12 { 180.0 } else { 100.0 } + 5.0 * rng.normal())
+            .collect();
+        let profile = monthly_seasonal_profile_impl(&values, &months).unwrap();
+
+        let peak = (0..12).max_by(|&a, &b| profile[a].total_cmp(&profile[b])).unwrap();
+        assert_eq!(peak + 1, 12);
+        assert!((profile.iter().sum::<f64>() / 12.0 - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_apply_monthly_profile_reseasonalizes() {
+        let mut profile = vec![1.0; 12];
+        profile[11] = 1.5;
+        profile[0] = f64::NAN;
+        let result = apply_monthly_profile_impl(&[10.0, 10.0], &[11, 12], &profile).unwrap();
+
+        assert_eq!(result, vec![10.0, 15.0]);
+        assert_eq!(
+            apply_monthly_profile_impl(&[10.0], &[1], &profile).unwrap_err(),
+            "Monthly profile has no finite index for month 1"
+        );
+    }
+
+    #[test]
+    fn test_monthly_seasonal_profile_validation() {
+        assert_eq!(
+            monthly_seasonal_profile_impl(&[1.0, 2.0], &[1]).unwrap_err(),
+            "Months length (1) must match values length (2)"
+        );
+        assert_eq!(
+            monthly_seasonal_profile_impl(&[1.0, 2.0], &[1, 13]).unwrap_err(),
+            "Months must be in 1..=12, got 13"
+        );
+    }
+}
@@ -0,0 +1,861 @@
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::types::PyList;
+
+use crate::methods::in_sample_sse_impl;
+
+/// Grid resolution and golden-section iterations of `fit_ses_impl`.
+const SES_GRID_STEPS: usize = 100;
+const SES_REFINE_ITERATIONS: usize = 60;
+
+/// Smoothing constant of the error tracking signal in Trigg-Leach SES.
+const TRACKING_SMOOTHING: f64 = 0.2;
+
+/// Check that a smoothing parameter lies in `(0, 1]`.
+pub(crate) fn validate_smoothing(name: &str, value: f64) -> Result<(), String> {
+    if !(value > 0.0 && value <= 1.0) {
+        return Err(format!("{} must be in (0, 1], got {}", name, value));
+    }
+
+    Ok(())
+}
+
+/// Core simple exponential smoothing: the smoothed level after each
+/// observation, starting from the first value.
+pub(crate) fn ses_levels_impl(data: &[f64], alpha: f64) -> Result<Vec<f64>, String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    validate_smoothing("Alpha", alpha)?;
+
+    let mut levels = Vec::with_capacity(data.len());
+    let mut level = data[0];
+    levels.push(level);
+    for y in &data[1..] {
+        level += alpha * (y - level);
+        levels.push(level);
+    }
+
+    Ok(levels)
+}
+
+/// Core simple exponential smoothing prediction logic.
+pub(crate) fn predict_ses_impl(data: &[f64], horizon: usize, alpha: f64) -> Result<Vec<f64>, String> {
+    let levels = ses_levels_impl(data, alpha)?;
+
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    Ok(vec![levels[levels.len() - 1]; horizon])
+}
+
+/// Core SES fitting logic: the alpha in `(0, 1]` minimizing the in-sample
+/// one-step SSE, the same objective as `in_sample_sse` for "ses".
+///
+/// A coarse grid locates the best region and golden-section search refines
+/// within one grid step of it, so a multi-modal objective cannot trap the
+/// search in a poor local minimum far from the grid optimum.
+pub(crate) fn fit_ses_impl(data: &[f64]) -> Result<f64, String> {
+    if data.len() < 2 {
+        return Err("SES fitting requires at least two data points".to_string());
+    }
+
+    let sse = |alpha: f64| -> Result<f64, String> {
+        in_sample_sse_impl(data, "ses", &HashMap::from([("alpha".to_string(), alpha)]))
+    };
+
+    let step = 1.0 / SES_GRID_STEPS as f64;
+    let mut best = (1.0, sse(1.0)?);
+    for i in 1..SES_GRID_STEPS {
+        let alpha = i as f64 * step;
+        let value = sse(alpha)?;
+        if value < best.1 {
+            best = (alpha, value);
+        }
+    }
+
+    // Golden-section search on the bracket around the grid optimum
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let mut low = (best.0 - step).max(step * 1e-3);
+    let mut high = (best.0 + step).min(1.0);
+    for _ in 0..SES_REFINE_ITERATIONS {
+        let a = high - ratio * (high - low);
+        let b = low + ratio * (high - low);
+        if sse(a)? <= sse(b)? {
+            high = b;
+        } else {
+            low = a;
+        }
+    }
+
+    let refined = (low + high) / 2.0;
+    Ok(if sse(refined)? <= best.1 { refined } else { best.0 })
+}
+
+/// Core SES gradient logic: the derivative with respect to alpha of the
+/// in-sample one-step SSE, `sum (y[t] - l[t-1])^2` over `t >= 1`.
+///
+/// Differentiating the level update `l[t] = l[t-1] + alpha * e[t]` gives the
+/// recursion `l'[t] = e[t] + (1 - alpha) * l'[t-1]` with `l'[0] = 0`, since
+/// the level starts at the first observation, and the SSE derivative is
+/// `-2 * sum e[t] * l'[t-1]`.
+pub(crate) fn ses_sse_gradient_impl(data: &[f64], alpha: f64) -> Result<f64, String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    validate_smoothing("Alpha", alpha)?;
+
+    let mut level = data[0];
+    let mut level_derivative = 0.0;
+    let mut gradient = 0.0;
+    for y in &data[1..] {
+        let error = y - level;
+        gradient -= 2.0 * error * level_derivative;
+        level_derivative = error + (1.0 - alpha) * level_derivative;
+        level += alpha * error;
+    }
+
+    Ok(gradient)
+}
+
+/// Core Trigg-Leach adaptive SES: the final level and the alpha used at
+/// each update (one per observation after the first).
+///
+/// The one-step error `e` feeds a smoothed error `E` and a smoothed absolute
+/// error `M`, both with `TRACKING_SMOOTHING`. The alpha applied to `e` is the
+/// tracking signal `|E / M|`, which nears 1 while errors keep one sign (after
+/// a level shift) and falls back as they start to cancel.
+pub(crate) fn adaptive_ses_impl(data: &[f64]) -> Result<(f64, Vec<f64>), String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    let mut level = data[0];
+    let mut smoothed_error = 0.0;
+    let mut smoothed_abs_error = 0.0;
+    let mut alpha = TRACKING_SMOOTHING;
+    let mut alphas = Vec::with_capacity(data.len() - 1);
+    for y in &data[1..] {
+        let error = y - level;
+        smoothed_error = TRACKING_SMOOTHING * error + (1.0 - TRACKING_SMOOTHING) * smoothed_error;
+        smoothed_abs_error =
+            TRACKING_SMOOTHING * error.abs() + (1.0 - TRACKING_SMOOTHING) * smoothed_abs_error;
+        // Keep the previous alpha until there has been a non-zero error
+        if smoothed_abs_error > 0.0 {
+            alpha = (smoothed_error / smoothed_abs_error).abs();
+        }
+        level += alpha * error;
+        alphas.push(alpha);
+    }
+
+    Ok((level, alphas))
+}
+
+/// Core adaptive SES prediction logic.
+pub(crate) fn predict_adaptive_ses_impl(data: &[f64], horizon: usize) -> Result<Vec<f64>, String> {
+    let (level, _) = adaptive_ses_impl(data)?;
+
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    Ok(vec![level; horizon])
+}
+
+/// Core Holt linear smoothing: the level and trend after each observation.
+///
+/// The level starts at the first value and the trend at the first
+/// difference, so both series have one entry per observation.
+pub(crate) fn holt_components_impl(
+    data: &[f64],
+    alpha: f64,
+    beta: f64,
+) -> Result<(Vec<f64>, Vec<f64>), String> {
+    if data.len() < 2 {
+        return Err("Holt's method requires at least two data points".to_string());
+    }
+
+    validate_smoothing("Alpha", alpha)?;
+    validate_smoothing("Beta", beta)?;
+
+    let mut levels = Vec::with_capacity(data.len());
+    let mut trends = Vec::with_capacity(data.len());
+    let mut level = data[0];
+    let mut trend = data[1] - data[0];
+    levels.push(level);
+    trends.push(trend);
+    for y in &data[1..] {
+        let previous = level;
+        level = alpha * y + (1.0 - alpha) * (level + trend);
+        trend = beta * (level - previous) + (1.0 - beta) * trend;
+        levels.push(level);
+        trends.push(trend);
+    }
+
+    Ok((levels, trends))
+}
+
+/// Core Holt linear smoothing prediction logic.
+pub(crate) fn predict_holt_impl(
+    data: &[f64],
+    horizon: usize,
+    alpha: f64,
+    beta: f64,
+) -> Result<Vec<f64>, String> {
+    let (levels, trends) = holt_components_impl(data, alpha, beta)?;
+
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let level = levels[levels.len() - 1];
+    let trend = trends[trends.len() - 1];
+    Ok((1..=horizon).map(|h| level + h as f64 * trend).collect())
+}
+
+/// Core ETS(A,A,N) prediction logic. The point forecasts of the
+/// additive-error, additive-trend, non-seasonal model are exactly Holt's.
+pub(crate) fn predict_ets_aan_impl(
+    data: &[f64],
+    horizon: usize,
+    alpha: f64,
+    beta: f64,
+) -> Result<Vec<f64>, String> {
+    predict_holt_impl(data, horizon, alpha, beta)
+}
+
+/// Check that a trailing window is non-empty and fits in `n` values.
+pub(crate) fn validate_window(window: usize, n: usize) -> Result<(), String> {
+    if window == 0 {
+        return Err("Window must be greater than 0".to_string());
+    }
+
+    if window > n {
+        return Err(format!("Window ({}) cannot exceed data length ({})", window, n));
+    }
+
+    Ok(())
+}
+
+/// Neumaier's compensated sum: the running total and the rounding error lost
+/// from it, so adding and later subtracting a huge value leaves the small
+/// ones intact.
+#[derive(Clone, Copy, Default)]
+struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    fn of(values: &[f64]) -> Self {
+        let mut total = CompensatedSum::default();
+        for &value in values {
+            total.add(value);
+        }
+        total
+    }
+
+    fn add(&mut self, value: f64) {
+        let sum = self.sum + value;
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - sum) + value;
+        } else {
+            self.compensation += (value - sum) + self.sum;
+        }
+        self.sum = sum;
+    }
+
+    fn value(&self) -> f64 {
+        // Past a non-finite total the compensation has no meaning
+        if self.sum.is_finite() {
+            self.sum + self.compensation
+        } else {
+            self.sum
+        }
+    }
+}
+
+/// Core trailing rolling mean using a running sum, O(n) in the series length
+/// regardless of the window size.
+///
+/// Entry `i` is the mean of `data[i + 1 - window..=i]`; the first `window - 1`
+/// entries have no full window and are NaN. The running sum is compensated
+/// and re-summed from scratch once per window, which bounds the rounding it
+/// carries. A non-finite total, from a NaN or infinity in the window or from
+/// overflow, cannot be updated by subtraction, so such windows are summed
+/// directly; a non-finite value only affects the windows that contain it.
+pub(crate) fn rolling_mean_impl(data: &[f64], window: usize) -> Result<Vec<f64>, String> {
+    validate_window(window, data.len())?;
+
+    let mut means = vec![f64::NAN; data.len()];
+    let mut sum = CompensatedSum::of(&data[..window]);
+    means[window - 1] = sum.value() / window as f64;
+    for i in window..data.len() {
+        let start = i + 1 - window;
+        if start.is_multiple_of(window) || !sum.value().is_finite() {
+            sum = CompensatedSum::of(&data[start..=i]);
+        } else {
+            sum.add(data[i]);
+            sum.add(-data[i - window]);
+        }
+        means[i] = sum.value() / window as f64;
+    }
+
+    Ok(means)
+}
+
+/// Core moving average prediction logic.
+pub(crate) fn predict_moving_average_impl(
+    data: &[f64],
+    horizon: usize,
+    window: usize,
+) -> Result<Vec<f64>, String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    let means = rolling_mean_impl(data, window)?;
+
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    Ok(vec![means[means.len() - 1]; horizon])
+}
+
+/// Core EWMA prediction logic: the weighted mean of the data with weight
+/// `(1 - alpha)^k` at lag `k`, where `alpha = 2 / (span + 1)`.
+///
+/// The weights are normalized over the observed lags, as in pandas'
+/// `ewm(span=span).mean()`, so the first observation does not act as a
+/// prior level the way it does in SES.
+pub(crate) fn predict_ewma_impl(
+    data: &[f64],
+    horizon: usize,
+    span: f64,
+) -> Result<Vec<f64>, String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    if span.is_nan() || span < 1.0 {
+        return Err(format!("Span must be at least 1, got {}", span));
+    }
+
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let decay = 1.0 - 2.0 / (span + 1.0);
+    let mut weight = 1.0;
+    let mut weighted_sum = 0.0;
+    let mut total_weight = 0.0;
+    for y in data.iter().rev() {
+        weighted_sum += weight * y;
+        total_weight += weight;
+        weight *= decay;
+    }
+
+    Ok(vec![weighted_sum / total_weight; horizon])
+}
+
+/// Predict future values with simple exponential smoothing.
+///
+/// The level starts at the first observation and moves toward each new
+/// observation by a fraction `alpha` of the error. The forecast is the final
+/// level for every step.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Number of future values to predict
+/// * `alpha` - Smoothing parameter in `(0, 1]`
+///
+/// # Errors
+///
+/// Returns PyValueError if data is empty, horizon is 0 or alpha is out of
+/// range.
+#[pyfunction]
+pub fn predict_ses(data: Vec<f64>, horizon: usize, alpha: f64) -> PyResult<Vec<f64>> {
+    predict_ses_impl(&data, horizon, alpha).map_err(PyValueError::new_err)
+}
+
+/// Fit the SES smoothing parameter by minimizing the in-sample SSE.
+///
+/// The objective is exactly `in_sample_sse(data, "ses", {"alpha": alpha})`.
+///
+/// # Returns
+///
+/// The optimal alpha in `(0, 1]`
+///
+/// # Errors
+///
+/// Returns PyValueError if data has fewer than two points.
+#[pyfunction]
+pub fn fit_ses(data: Vec<f64>) -> PyResult<f64> {
+    fit_ses_impl(&data).map_err(PyValueError::new_err)
+}
+
+/// Derivative of the SES in-sample SSE with respect to alpha.
+///
+/// The objective is the one `fit_ses` minimizes, `in_sample_sse(data, "ses",
+/// {"alpha": alpha})`. Its exact derivative comes from differentiating the
+/// level recursion alongside the level itself, in one pass over the data, so
+/// gradient-based optimizers need no finite differences.
+///
+/// # Arguments
+///
+/// * `data` - Historical data
+/// * `alpha` - Smoothing parameter in `(0, 1]`
+///
+/// # Errors
+///
+/// Returns PyValueError if data is empty or alpha is out of range.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.ses_sse_gradient([10.0, 12.0, 14.0], 0.5)
+/// # -12.0: a larger alpha would track the rise more closely
+/// ```
+#[pyfunction]
+pub fn ses_sse_gradient(data: Vec<f64>, alpha: f64) -> PyResult<f64> {
+    ses_sse_gradient_impl(&data, alpha).map_err(PyValueError::new_err)
+}
+
+/// Predict future values with adaptive-response-rate SES (Trigg-Leach).
+///
+/// Instead of a fixed alpha, each update uses the tracking signal
+/// `|E / M|`, the ratio of the smoothed error to the smoothed absolute error
+/// (both smoothed with 0.2). After a level shift the errors share a sign and
+/// alpha rises toward 1, so the level catches up quickly; once errors cancel
+/// out again alpha relaxes. The forecast is the final level for every step.
+///
+/// # Errors
+///
+/// Returns PyValueError if data is empty or horizon is 0.
+#[pyfunction]
+pub fn predict_adaptive_ses(data: Vec<f64>, horizon: usize) -> PyResult<Vec<f64>> {
+    predict_adaptive_ses_impl(&data, horizon).map_err(PyValueError::new_err)
+}
+
+/// Predict future values with Holt's linear (double exponential) smoothing.
+///
+/// A level and a trend are smoothed with `alpha` and `beta` respectively;
+/// the forecast `h` steps ahead is `level + h * trend`.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Number of future values to predict
+/// * `alpha` - Level smoothing parameter in `(0, 1]`
+/// * `beta` - Trend smoothing parameter in `(0, 1]`
+///
+/// # Errors
+///
+/// Returns PyValueError if data has fewer than two points, horizon is 0 or
+/// alpha or beta is out of range.
+#[pyfunction]
+pub fn predict_holt(data: Vec<f64>, horizon: usize, alpha: f64, beta: f64) -> PyResult<Vec<f64>> {
+    predict_holt_impl(&data, horizon, alpha, beta).map_err(PyValueError::new_err)
+}
+
+/// Predict future values with the ETS(A,A,N) model.
+///
+/// Additive error, additive trend and no seasonality, named as in R's
+/// `forecast::ets(model = "AAN")`. Its point forecasts match
+/// `predict_holt` with the same parameters.
+///
+/// # Errors
+///
+/// Returns PyValueError under the same conditions as `predict_holt`.
+#[pyfunction]
+pub fn predict_ets_aan(data: Vec<f64>, horizon: usize, alpha: f64, beta: f64) -> PyResult<Vec<f64>> {
+    predict_ets_aan_impl(&data, horizon, alpha, beta).map_err(PyValueError::new_err)
+}
+
+/// Trailing rolling mean over a fixed window.
+///
+/// Computed with a running sum that adds the entering value and subtracts the
+/// leaving one, so the cost is O(n) for any window size. The sum is
+/// compensated and recomputed once per window, so a huge value does not
+/// disturb the windows after it has left, and results stay within rounding of
+/// a per-window sum. A NaN or infinity makes only the windows containing it
+/// non-finite; those windows are summed directly.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `window` - Number of trailing values averaged at each position
+///
+/// # Returns
+///
+/// A vector of the same length as data; the first `window - 1` entries are
+/// NaN because no full window ends there
+///
+/// # Errors
+///
+/// Returns PyValueError if window is 0 or exceeds the data length.
+#[pyfunction]
+pub fn rolling_mean(data: Vec<f64>, window: usize) -> PyResult<Vec<f64>> {
+    rolling_mean_impl(&data, window).map_err(PyValueError::new_err)
+}
+
+/// Apply a Python callable to every trailing window, for custom rolling
+/// features.
+///
+/// For each index `i` from `window - 1`, `func` is called with
+/// `data[i + 1 - window..=i]` as a list and must return a finite number,
+/// which becomes entry `i`. The callable runs while holding the GIL, once
+/// per window, so this is convenient rather than fast; prefer the built-in
+/// rolling functions where one fits.
+///
+/// # Returns
+///
+/// A vector of the same length as data; the first `window - 1` entries are
+/// NaN because no full window ends there
+///
+/// # Errors
+///
+/// Returns PyValueError if window is 0 or exceeds the data length, or if
+/// `func` returns a non-finite number, and PyTypeError if it returns
+/// something that is not a number. Exceptions raised by `func` propagate
+/// unchanged.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.rolling_apply([1.0, 4.0, 2.0, 8.0], 2, lambda w: max(w) - min(w))
+/// # [nan, 3.0, 2.0, 6.0]
+/// ```
+#[pyfunction]
+pub fn rolling_apply(
+    py: Python<'_>,
+    data: Vec<f64>,
+    window: usize,
+    func: PyObject,
+) -> PyResult<Vec<f64>> {
+    validate_window(window, data.len()).map_err(PyValueError::new_err)?;
+
+    let mut results = vec![f64::NAN; data.len()];
+    let spans = results[window - 1..].iter_mut().zip(data.windows(window));
+    for (offset, (result, span)) in spans.enumerate() {
+        let end = offset + window - 1;
+        let returned = func.call1(py, (PyList::new(py, span),))?;
+        let value: f64 = returned.extract(py).map_err(|_| {
+            PyTypeError::new_err(format!(
+                "rolling_apply callback must return a number, got {} for the window ending at \
+                 index {}",
+                returned.as_ref(py).get_type().name().unwrap_or("?"),
+                end
+            ))
+        })?;
+        if !value.is_finite() {
+            return Err(PyValueError::new_err(format!(
+                "rolling_apply callback returned {} for the window ending at index {}",
+                value, end
+            )));
+        }
+        *result = value;
+    }
+
+    Ok(results)
+}
+
+/// Predict future values as the mean of the last `window` observations.
+///
+/// # Errors
+///
+/// Returns PyValueError if data is empty, horizon is 0, or window is 0 or
+/// exceeds the data length.
+#[pyfunction]
+pub fn predict_moving_average(data: Vec<f64>, horizon: usize, window: usize) -> PyResult<Vec<f64>> {
+    predict_moving_average_impl(&data, horizon, window).map_err(PyValueError::new_err)
+}
+
+/// Predict future values with an exponentially weighted moving average.
+///
+/// Each observation is weighted by `(1 - alpha)^k`, `k` being its lag from
+/// the last one, with `alpha = 2 / (span + 1)` as in pandas. The weights are
+/// normalized over the data, and the forecast is the weighted mean for every
+/// step. `span = 1` gives the last value; as the span grows the weights even
+/// out and the forecast approaches the plain mean of the data.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Number of future values to predict
+/// * `span` - Decay span, at least 1; roughly the number of recent points
+///   that carry most of the weight
+///
+/// # Errors
+///
+/// Returns PyValueError if data is empty, horizon is 0 or span is less than
+/// 1.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.predict_ewma([1.0, 2.0, 4.0], 1, 3.0)
+/// # [3.0]: weights 1/4, 1/2 and 1 on 1.0, 2.0 and 4.0
+/// ```
+#[pyfunction]
+pub fn predict_ewma(data: Vec<f64>, horizon: usize, span: f64) -> PyResult<Vec<f64>> {
+    predict_ewma_impl(&data, horizon, span).map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ses_levels() {
+        let levels = ses_levels_impl(&[10.0, 20.0, 20.0], 0.5).unwrap();
+
+        assert_eq!(levels, vec![10.0, 15.0, 17.5]);
+    }
+
+    #[test]
+    fn test_predict_ses_alpha_one_is_static() {
+        let result = predict_ses_impl(&[1.0, 4.0, 2.0], 3, 1.0).unwrap();
+
+        assert_eq!(result, vec![2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_predict_ses_invalid_alpha() {
+        let result = predict_ses_impl(&[1.0, 2.0], 3, 0.0);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Alpha must be in (0, 1], got 0");
+    }
+
+    /// Per-window summation, the O(n * window) reference implementation.
+    fn naive_rolling_mean(data: &[f64], window: usize) -> Vec<f64> {
+        (0..data.len())
+            .map(|i| {
+                if i + 1 < window {
+                    f64::NAN
+                } else {
+                    data[i + 1 - window..=i].iter().sum::<f64>() / window as f64
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rolling_mean_matches_naive_on_random_data() {
+        let mut rng = crate::rng::Rng::new(42);
+        let data: Vec<f64> = (0..5000).map(|_| rng.uniform() * 200.0 - 100.0).collect();
+
+        for window in [1, 7, 250, 5000] {
+            let fast = rolling_mean_impl(&data, window).unwrap();
+            let naive = naive_rolling_mean(&data, window);
+
+            assert_eq!(fast.len(), naive.len());
+            for (f, n) in fast.iter().zip(&naive) {
+                if n.is_nan() {
+                    assert!(f.is_nan());
+                } else {
+                    assert!((f - n).abs() < 1e-9, "window {}: {} vs {}", window, f, n);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rolling_mean_recovers_after_a_spike() {
+        let data = [1e17, 1.0, 1.0, 1.0, 1.0];
+        assert_eq!(rolling_mean_impl(&data, 1).unwrap(), data.to_vec());
+
+        let means = rolling_mean_impl(&data, 2).unwrap();
+        assert_eq!(&means[2..], &[1.0, 1.0, 1.0]);
+        assert_eq!(predict_moving_average_impl(&data, 1, 1).unwrap(), vec![1.0]);
+        assert_eq!(predict_moving_average_impl(&data, 1, 3).unwrap(), vec![1.0]);
+    }
+
+    #[test]
+    fn test_rolling_mean_non_finite_stays_in_its_windows() {
+        let means = rolling_mean_impl(&[1.0, f64::NAN, 1.0, 1.0, 1.0], 2).unwrap();
+        assert!(means[..3].iter().all(|m| m.is_nan()));
+        assert_eq!(&means[3..], &[1.0, 1.0]);
+
+        let means = rolling_mean_impl(&[2.0, f64::INFINITY, 2.0, 4.0], 2).unwrap();
+        assert_eq!(&means[1..], &[f64::INFINITY, f64::INFINITY, 3.0]);
+
+        // Overflowing windows recover once the large values leave
+        let means = rolling_mean_impl(&[f64::MAX, f64::MAX, 2.0, 4.0], 2).unwrap();
+        assert_eq!(means[1], f64::INFINITY);
+        assert_eq!(&means[2..], &[f64::MAX / 2.0 + 1.0, 3.0]);
+    }
+
+    #[test]
+    fn test_rolling_mean_invalid_window() {
+        assert_eq!(
+            rolling_mean_impl(&[1.0, 2.0], 0).unwrap_err(),
+            "Window must be greater than 0"
+        );
+        assert_eq!(
+            rolling_mean_impl(&[1.0, 2.0], 3).unwrap_err(),
+            "Window (3) cannot exceed data length (2)"
+        );
+    }
+
+    #[test]
+    fn test_predict_moving_average() {
+        let result = predict_moving_average_impl(&[1.0, 2.0, 3.0, 4.0, 8.0], 2, 3).unwrap();
+
+        assert_eq!(result, vec![5.0, 5.0]);
+    }
+
+    #[test]
+    fn test_predict_holt_follows_linear_series() {
+        let result = predict_holt_impl(&[1.0, 3.0, 5.0, 7.0], 3, 0.5, 0.5).unwrap();
+
+        assert_eq!(result, vec![9.0, 11.0, 13.0]);
+    }
+
+    #[test]
+    fn test_predict_holt_invalid_beta() {
+        let result = predict_holt_impl(&[1.0, 2.0, 3.0], 2, 0.5, 1.5);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Beta must be in (0, 1], got 1.5");
+    }
+
+    #[test]
+    fn test_predict_ets_aan_matches_holt() {
+        let data = [12.0, 15.0, 14.0, 18.0, 21.0, 19.0, 24.0];
+        let ets = predict_ets_aan_impl(&data, 4, 0.3, 0.2).unwrap();
+        let holt = predict_holt_impl(&data, 4, 0.3, 0.2).unwrap();
+
+        assert_eq!(ets, holt);
+    }
+
+    #[test]
+    fn test_fit_ses_is_a_local_minimum_of_in_sample_sse() {
+        let mut rng = crate::rng::Rng::new(5);
+        let mut level = 50.0;
+        let data: Vec<f64> = (0..80)
+            .map(|_| {
+                level += 0.5 * rng.normal();
+                level + rng.normal()
+            })
+            .collect();
+        let alpha = fit_ses_impl(&data).unwrap();
+        let sse = |a: f64| {
+            in_sample_sse_impl(&data, "ses", &HashMap::from([("alpha".to_string(), a)])).unwrap()
+        };
+
+        assert!(alpha > 0.0 && alpha < 1.0);
+        for delta in [1e-3, 1e-2, 5e-2] {
+            assert!(sse(alpha) <= sse(alpha - delta));
+            assert!(sse(alpha) <= sse((alpha + delta).min(1.0)));
+        }
+    }
+
+    #[test]
+    fn test_fit_ses_rising_series_prefers_alpha_one() {
+        // Every smoothed level lags a steadily rising series, least so at 1
+        let alpha = fit_ses_impl(&[1.0, 2.0, 4.0, 7.0, 11.0, 16.0]).unwrap();
+
+        assert_eq!(alpha, 1.0);
+    }
+
+    #[test]
+    fn test_adaptive_ses_alpha_spikes_after_level_shift() {
+        let mut rng = crate::rng::Rng::new(9);
+        let data: Vec<f64> = (0..120)
+            .map(|t| if t < 60 { 10.0 } else { 30.0 } + 0.5 * rng.normal())
+            .collect();
+        let (level, alphas) = adaptive_ses_impl(&data).unwrap();
+
+        // alphas[t - 1] is the alpha applied at observation t
+        let before = alphas[40..59].iter().sum::<f64>() / 19.0;
+        let at_shift = alphas[59];
+        let after = alphas[100..].iter().sum::<f64>() / 19.0;
+
+        assert_eq!(alphas.len(), data.len() - 1);
+        assert!(at_shift > 0.9, "alpha at shift {}", at_shift);
+        assert!(before < 0.5 && after < 0.5);
+        assert!((level - 30.0).abs() < 2.0);
+    }
+
+    #[test]
+    fn test_predict_adaptive_ses_constant_series() {
+        let result = predict_adaptive_ses_impl(&[4.0, 4.0, 4.0], 2).unwrap();
+
+        assert_eq!(result, vec![4.0, 4.0]);
+    }
+
+    #[test]
+    fn test_predict_adaptive_ses_empty() {
+        let result = predict_adaptive_ses_impl(&[], 2);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Input data cannot be empty");
+    }
+
+    #[test]
+    fn test_ses_sse_gradient_matches_central_difference() {
+        let mut rng = crate::rng::Rng::new(17);
+        let data: Vec<f64> = (0..60).map(|t| 50.0 + 0.2 * t as f64 + 3.0 * rng.normal()).collect();
+        let sse = |alpha: f64| {
+            in_sample_sse_impl(&data, "ses", &HashMap::from([("alpha".to_string(), alpha)]))
+                .unwrap()
+        };
+
+        let h = 1e-5;
+        for alpha in [0.05, 0.3, 0.5, 0.8, 0.95] {
+            let analytic = ses_sse_gradient_impl(&data, alpha).unwrap();
+            let numeric = (sse(alpha + h) - sse(alpha - h)) / (2.0 * h);
+            assert!((analytic - numeric).abs() < 1e-6 * numeric.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn test_ses_sse_gradient_small_series() {
+        // e = 2 then 3, with l'[1] = 2: gradient -2 * 3 * 2
+        assert_eq!(ses_sse_gradient_impl(&[10.0, 12.0, 14.0], 0.5).unwrap(), -12.0);
+        assert_eq!(ses_sse_gradient_impl(&[4.0], 0.5).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_ses_sse_gradient_invalid_alpha() {
+        let result = ses_sse_gradient_impl(&[1.0, 2.0], 1.5);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Alpha must be in (0, 1], got 1.5");
+    }
+
+    #[test]
+    fn test_predict_ewma_limits() {
+        let data = [4.0, 9.0, 2.0, 7.0, 5.0, 8.0];
+        let mean = data.iter().sum::<f64>() / data.len() as f64;
+
+        let wide = predict_ewma_impl(&data, 2, 1e9).unwrap();
+        assert!(wide.iter().all(|f| (f - mean).abs() < 1e-6));
+        assert_eq!(predict_ewma_impl(&data, 1, 1.0).unwrap(), vec![8.0]);
+        assert!((predict_ewma_impl(&data, 1, 1.001).unwrap()[0] - 8.0).abs() < 1e-2);
+
+        // A moderate span sits between the mean and the last value
+        let middle = predict_ewma_impl(&data, 1, 3.0).unwrap()[0];
+        assert!(mean < middle && middle < 8.0);
+    }
+
+    #[test]
+    fn test_predict_ewma_invalid_span() {
+        let result = predict_ewma_impl(&[1.0, 2.0], 1, 0.5);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Span must be at least 1, got 0.5");
+    }
+}
@@ -0,0 +1,1235 @@
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::fft::periodogram;
+use crate::seasonal::{classical_decomposition, validate_seasons};
+
+/// Fewest observations for which a periodogram is considered meaningful.
+const MIN_SPECTRUM_LENGTH: usize = 8;
+
+/// Maximum number of periods returned by `suggest_period_fft`.
+const MAX_PERIOD_SUGGESTIONS: usize = 5;
+
+/// Unbiased sample variance (denominator `n - 1`) by Welford's online
+/// algorithm, which stays accurate when values have a large common offset.
+/// Returns NaN for fewer than two values.
+///
+/// Sample variances of whole series go through this helper. Running window
+/// statistics, such as those of `AnomalyState`, use the same update
+/// incrementally, and population variances with denominator `n`, as in
+/// `mse_decomposition_impl`, are computed where they are used.
+pub(crate) fn variance_impl(data: &[f64]) -> f64 {
+    if data.len() < 2 {
+        return f64::NAN;
+    }
+
+    let mut mean = 0.0;
+    let mut m2 = 0.0;
+    for (i, value) in data.iter().enumerate() {
+        let delta = value - mean;
+        mean += delta / (i + 1) as f64;
+        m2 += delta * (value - mean);
+    }
+
+    m2 / (data.len() - 1) as f64
+}
+
+/// Empirical quantile of already sorted values by linear interpolation
+/// between order statistics (type 7 in Hyndman & Fan, NumPy's default):
+/// position `q * (n - 1)` in the sorted values. `sorted` must be non-empty
+/// and `q` in `[0, 1]`.
+pub(crate) fn quantile_sorted(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let below = position.floor() as usize;
+    let above = position.ceil() as usize;
+    let fraction = position - below as f64;
+
+    sorted[below] + fraction * (sorted[above] - sorted[below])
+}
+
+/// Check that a confidence level lies strictly between 0 and 1.
+pub(crate) fn validate_confidence(confidence: f64) -> Result<(), String> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(format!("Confidence must be in (0, 1), got {}", confidence));
+    }
+
+    Ok(())
+}
+
+/// Standard normal quantile for a lower-tail probability `p` in `(0, 1)`,
+/// using Acklam's rational approximation (relative error below 1.2e-9).
+pub(crate) fn inverse_normal_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |t: f64| {
+        let q = (-2.0 * t.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < P_LOW {
+        tail(p)
+    } else if p > 1.0 - P_LOW {
+        -tail(1.0 - p)
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Natural log of the gamma function for `x > 0`, by the Lanczos
+/// approximation (g = 7, 9 terms; relative error around 1e-15).
+fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    let x = x - 1.0;
+    let mut series = COEFFICIENTS[0];
+    for (i, c) in COEFFICIENTS.iter().enumerate().skip(1) {
+        series += c / (x + i as f64);
+    }
+    let t = x + G + 0.5;
+
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+/// Continued fraction of the regularized incomplete beta function, by the
+/// modified Lentz method; converges quickly for `x < (a + 1) / (a + b + 2)`.
+fn incomplete_beta_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    const EPSILON: f64 = 1e-15;
+    const MAX_TERMS: usize = 300;
+
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut fraction = d;
+
+    for m in 1..=MAX_TERMS {
+        let m = m as f64;
+        // Even and odd steps of the fraction
+        for numerator in [
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = 1.0 + numerator / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            fraction *= d * c;
+        }
+        if (d * c - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+
+    fraction
+}
+
+/// Regularized incomplete beta function `I_x(a, b)` for `x` in `[0, 1]`.
+fn regularized_incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let ln_front = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln();
+    let front = ln_front.exp();
+
+    // Use the symmetry I_x(a, b) = 1 - I_{1-x}(b, a) where the fraction is slow
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * incomplete_beta_fraction(a, b, x) / a
+    } else {
+        1.0 - front * incomplete_beta_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Two-sided p-value of a Student t statistic with `df > 0` degrees of
+/// freedom: the probability that `|T| >= |t|`.
+pub(crate) fn student_t_two_sided_p(t: f64, df: f64) -> f64 {
+    regularized_incomplete_beta(df / 2.0, 0.5, df / (df + t * t))
+}
+
+/// Core z-score logic: the two-sided standard normal critical value for a
+/// confidence level, e.g. 1.96 for 0.95.
+pub(crate) fn z_score_impl(confidence: f64) -> Result<f64, String> {
+    validate_confidence(confidence)?;
+
+    // The lower tail (1 - confidence) / 2 keeps full precision near 1
+    Ok(-inverse_normal_cdf((1.0 - confidence) / 2.0))
+}
+
+/// Two-sided standard normal critical value for a confidence level.
+///
+/// The value `z` such that a standard normal variable lies in `[-z, z]` with
+/// probability `confidence`, computed with Acklam's inverse normal CDF. The
+/// interval functions in this module use the same implementation.
+///
+/// # Errors
+///
+/// Returns PyValueError if confidence is not in `(0, 1)`.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.z_score(0.95)
+/// # Returns 1.959964...
+/// ```
+#[pyfunction]
+pub fn z_score(confidence: f64) -> PyResult<f64> {
+    z_score_impl(confidence).map_err(PyValueError::new_err)
+}
+
+/// Core growth rate logic: `(y_t - y_{t-1}) / y_{t-1}` for each period.
+pub(crate) fn growth_rates_impl(data: &[f64]) -> Result<Vec<f64>, String> {
+    if data.len() < 2 {
+        return Err("Growth rates require at least two data points".to_string());
+    }
+
+    data.windows(2)
+        .enumerate()
+        .map(|(i, w)| {
+            if w[0] == 0.0 {
+                Err(format!(
+                    "Growth rate undefined at index {}: previous value is zero",
+                    i + 1
+                ))
+            } else {
+                Ok((w[1] - w[0]) / w[0])
+            }
+        })
+        .collect()
+}
+
+/// Period-over-period relative change of a series.
+///
+/// Entry `i` of the result is `(data[i+1] - data[i]) / data[i]`, so the output
+/// has `len(data) - 1` values. Useful for judging whether changes scale with
+/// the level (multiplicative) or stay constant in size (additive).
+///
+/// A zero previous value makes the rate undefined; rather than skipping such
+/// periods (which would misalign the output with the input), an error naming
+/// the offending index is raised.
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * data has fewer than two points
+/// * any value other than the last is zero
+#[pyfunction]
+pub fn growth_rates(data: Vec<f64>) -> PyResult<Vec<f64>> {
+    growth_rates_impl(&data).map_err(PyValueError::new_err)
+}
+
+/// The finite entries of `data`, in order.
+fn finite_values(data: &[f64]) -> Vec<f64> {
+    data.iter().copied().filter(|v| v.is_finite()).collect()
+}
+
+/// Finite entries of `data`, or an error when there are none.
+fn require_finite(data: &[f64]) -> Result<Vec<f64>, String> {
+    let finite = finite_values(data);
+    if finite.is_empty() {
+        return Err("Input data has no finite values".to_string());
+    }
+
+    Ok(finite)
+}
+
+/// Core NaN-aware mean logic: the mean of the finite values.
+pub(crate) fn nanmean_impl(data: &[f64]) -> Result<f64, String> {
+    let finite = require_finite(data)?;
+    Ok(finite.iter().sum::<f64>() / finite.len() as f64)
+}
+
+/// Core NaN-aware standard deviation logic: the sample standard deviation of
+/// the finite values, NaN when only one is finite.
+pub(crate) fn nanstd_impl(data: &[f64]) -> Result<f64, String> {
+    Ok(variance_impl(&require_finite(data)?).sqrt())
+}
+
+/// Mean of the finite values, ignoring NaN and infinite entries.
+///
+/// # Errors
+///
+/// Returns PyValueError if no value is finite (including empty data).
+#[pyfunction]
+pub fn nanmean(data: Vec<f64>) -> PyResult<f64> {
+    nanmean_impl(&data).map_err(PyValueError::new_err)
+}
+
+/// Sample standard deviation (denominator `n - 1`) of the finite values,
+/// ignoring NaN and infinite entries. NaN if exactly one value is finite.
+///
+/// # Errors
+///
+/// Returns PyValueError if no value is finite (including empty data).
+#[pyfunction]
+pub fn nanstd(data: Vec<f64>) -> PyResult<f64> {
+    nanstd_impl(&data).map_err(PyValueError::new_err)
+}
+
+/// Factor making the median absolute deviation estimate the standard
+/// deviation of normal data, `1 / Phi^-1(3/4)`.
+const MAD_SCALE: f64 = 1.4826;
+
+/// Median of values, which must be non-empty and finite.
+pub(crate) fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    quantile_sorted(&sorted, 0.5)
+}
+
+/// Median of non-empty, finite `values` and their median absolute deviation
+/// from it, scaled by `MAD_SCALE`.
+pub(crate) fn median_and_mad(values: &[f64]) -> (f64, f64) {
+    let center = median(values);
+    let deviations: Vec<f64> = values.iter().map(|v| (v - center).abs()).collect();
+    (center, MAD_SCALE * median(&deviations))
+}
+
+/// Core MAD logic: the median absolute deviation from the median, scaled by
+/// `MAD_SCALE`.
+pub(crate) fn mad_impl(data: &[f64]) -> Result<f64, String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    if data.iter().any(|v| !v.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    Ok(median_and_mad(data).1)
+}
+
+/// Robust scale estimate: the median absolute deviation, scaled by 1.4826 so
+/// it approximates the standard deviation of normally distributed data.
+///
+/// Unlike the standard deviation, a minority of outliers barely moves it,
+/// which makes it suited to setting outlier thresholds.
+///
+/// # Errors
+///
+/// Returns PyValueError if data is empty or has a non-finite value.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.mad([1.0, 2.0, 3.0, 4.0, 100.0])
+/// # 1.4826: the median deviation is 1
+/// ```
+#[pyfunction]
+pub fn mad(data: Vec<f64>) -> PyResult<f64> {
+    mad_impl(&data).map_err(PyValueError::new_err)
+}
+
+/// Core effective sample size logic: `n / (1 + 2 * sum_k rho_k)` over the
+/// sample autocorrelations `rho_1, rho_2, ...` up to the first negative one.
+pub(crate) fn effective_sample_size_impl(data: &[f64]) -> Result<f64, String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    if data.iter().any(|v| !v.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    let n = data.len();
+    let mean = data.iter().sum::<f64>() / n as f64;
+    let deviations: Vec<f64> = data.iter().map(|v| v - mean).collect();
+    let total_ss: f64 = deviations.iter().map(|d| d * d).sum();
+    if total_ss == 0.0 {
+        return Err("Effective sample size is undefined for a constant series".to_string());
+    }
+
+    let mut positive_sum = 0.0;
+    for lag in 1..n {
+        let rho = deviations
+            .iter()
+            .zip(&deviations[lag..])
+            .map(|(a, b)| a * b)
+            .sum::<f64>()
+            / total_ss;
+        if rho < 0.0 {
+            break;
+        }
+        positive_sum += rho;
+    }
+
+    Ok(n as f64 / (1.0 + 2.0 * positive_sum))
+}
+
+/// Effective sample size of autocorrelated data.
+///
+/// Positively autocorrelated observations carry less information than
+/// independent ones; the effective sample size is the number of independent
+/// observations giving the same variance of the mean,
+/// `n / (1 + 2 * sum of autocorrelations)`. The sum runs over the sample
+/// autocorrelations from lag 1 and stops at the first negative one, where
+/// the estimates are dominated by noise. Independent data gives about `n`;
+/// the result never exceeds `n`.
+///
+/// # Errors
+///
+/// Returns PyValueError if data is empty, has a non-finite value or is
+/// constant.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.effective_sample_size(ar1_series)
+/// # about n * (1 - phi) / (1 + phi) for an AR(1) with coefficient phi
+/// ```
+#[pyfunction]
+pub fn effective_sample_size(data: Vec<f64>) -> PyResult<f64> {
+    effective_sample_size_impl(&data).map_err(PyValueError::new_err)
+}
+
+/// Core data-quality report logic.
+///
+/// Counts are over all entries; `mean`, `std`, `min` and `max` use only the
+/// finite values and are NaN when there are too few of them.
+pub(crate) fn data_report_impl(data: &[f64]) -> Result<HashMap<String, f64>, String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    let finite = finite_values(data);
+    let count = |pred: fn(&f64) -> bool| data.iter().filter(|v| pred(v)).count() as f64;
+
+    let (mean, min, max) = if finite.is_empty() {
+        (f64::NAN, f64::NAN, f64::NAN)
+    } else {
+        (
+            finite.iter().sum::<f64>() / finite.len() as f64,
+            finite.iter().copied().fold(f64::INFINITY, f64::min),
+            finite.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        )
+    };
+
+    Ok(HashMap::from([
+        ("n".to_string(), data.len() as f64),
+        ("n_finite".to_string(), finite.len() as f64),
+        ("n_nan".to_string(), count(|v| v.is_nan())),
+        ("n_inf".to_string(), count(|v| v.is_infinite())),
+        ("n_zeros".to_string(), count(|v| *v == 0.0)),
+        ("mean".to_string(), mean),
+        ("std".to_string(), variance_impl(&finite).sqrt()),
+        ("min".to_string(), min),
+        ("max".to_string(), max),
+    ]))
+}
+
+/// Summarize the health of a series before forecasting.
+///
+/// NaN and infinite entries are counted rather than propagated: the summary
+/// statistics are computed over the finite values only.
+///
+/// # Returns
+///
+/// A dict with keys `n`, `n_finite`, `n_nan`, `n_inf`, `n_zeros`, `mean`,
+/// `std` (sample standard deviation), `min` and `max`. Statistics that need
+/// more finite values than are available are NaN.
+///
+/// # Errors
+///
+/// Returns PyValueError if data is empty.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// report = sa_native.data_report([1.0, float("nan"), 0.0, 3.0])
+/// # report["n_nan"] == 1.0, report["n_zeros"] == 1.0, report["mean"] == 4/3
+/// ```
+#[pyfunction]
+pub fn data_report(data: Vec<f64>) -> PyResult<HashMap<String, f64>> {
+    data_report_impl(&data).map_err(PyValueError::new_err)
+}
+
+/// Core cross-correlation logic.
+///
+/// Entry `max_lag + k` is the correlation between `x[t]` and `y[t + k]` for
+/// `k` in `-max_lag..=max_lag`, normalized by `n` and the full-series
+/// standard deviations as in the usual sample CCF.
+pub(crate) fn cross_correlation_impl(
+    x: &[f64],
+    y: &[f64],
+    max_lag: usize,
+) -> Result<Vec<f64>, String> {
+    if x.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    if x.len() != y.len() {
+        return Err(format!(
+            "Series lengths must match: x has {} values, y has {}",
+            x.len(),
+            y.len()
+        ));
+    }
+
+    let n = x.len();
+    if max_lag >= n {
+        return Err(format!(
+            "max_lag ({}) must be less than the series length ({})",
+            max_lag, n
+        ));
+    }
+
+    if x.iter().chain(y).any(|v| !v.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    let x_mean = x.iter().sum::<f64>() / n as f64;
+    let y_mean = y.iter().sum::<f64>() / n as f64;
+    let x_ss: f64 = x.iter().map(|v| (v - x_mean).powi(2)).sum();
+    let y_ss: f64 = y.iter().map(|v| (v - y_mean).powi(2)).sum();
+    if x_ss == 0.0 || y_ss == 0.0 {
+        return Err("Cross-correlation is undefined for a constant series".to_string());
+    }
+
+    let scale = (x_ss * y_ss).sqrt();
+    let lag_max = max_lag as isize;
+    Ok((-lag_max..=lag_max)
+        .map(|k| {
+            // Pairs (t, t + k) with both indices inside the series
+            let start = (-k).max(0) as usize;
+            let end = (n as isize - k.max(0)) as usize;
+            (start..end)
+                .map(|t| (x[t] - x_mean) * (y[(t as isize + k) as usize] - y_mean))
+                .sum::<f64>()
+                / scale
+        })
+        .collect())
+}
+
+/// Sample cross-correlation of two series for lags `-max_lag..=max_lag`.
+///
+/// The value at lag `k` correlates `x[t]` with `y[t + k]`, so a peak at a
+/// positive lag means `x` leads `y` by that many steps, and a peak at a
+/// negative lag means `y` leads `x`. As in the standard estimator, sums are
+/// divided by the full-series variances, so magnitudes shrink at long lags.
+///
+/// # Arguments
+///
+/// * `x` - First series
+/// * `y` - Second series, of the same length as `x`
+/// * `max_lag` - Largest lag in either direction
+///
+/// # Returns
+///
+/// `2 * max_lag + 1` correlations; entry `i` is for lag `i - max_lag`
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * the series are empty or their lengths differ
+/// * max_lag is not less than the series length
+/// * either series contains non-finite values
+/// * either series is constant
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// x = [0.0, 1.0, 0.0, -1.0, 0.0, 1.0, 0.0, -1.0]
+/// y = x[-1:] + x[:-1]            # y lags x by one step
+/// ccf = sa_native.cross_correlation(x, y, 2)
+/// # the maximum is at index 3, i.e. lag +1
+/// ```
+#[pyfunction]
+pub fn cross_correlation(x: Vec<f64>, y: Vec<f64>, max_lag: usize) -> PyResult<Vec<f64>> {
+    cross_correlation_impl(&x, &y, max_lag).map_err(PyValueError::new_err)
+}
+
+/// `max(0, 1 - var(remainder) / var(component + remainder))`, the share of
+/// variation explained by a component; 0 when the sum does not vary.
+fn component_strength(component: &[f64], remainder: &[f64]) -> f64 {
+    let combined: Vec<f64> = component.iter().zip(remainder).map(|(c, r)| c + r).collect();
+    let total = variance_impl(&combined);
+    if total.is_nan() || total <= 0.0 {
+        return 0.0;
+    }
+
+    (1.0 - variance_impl(remainder) / total).max(0.0)
+}
+
+/// Core series feature logic: trend and seasonal strength from a classical
+/// additive decomposition.
+///
+/// The trend is a centered moving average over one season, the seasonal
+/// component the zero-mean average of the detrended values per phase, and
+/// the remainder what is left. Points at the edges without a moving average
+/// are excluded.
+pub(crate) fn series_features_impl(data: &[f64], season_length: usize) -> Result<(f64, f64), String> {
+    validate_seasons(data.len(), season_length)?;
+
+    let (trend, seasonal) = classical_decomposition(data, season_length);
+    let inner: Vec<usize> = (0..data.len()).filter(|&t| !trend[t].is_nan()).collect();
+
+    let trend_part: Vec<f64> = inner.iter().map(|&t| trend[t]).collect();
+    let season_part: Vec<f64> = inner.iter().map(|&t| seasonal[t % season_length]).collect();
+    let remainder: Vec<f64> = inner
+        .iter()
+        .map(|&t| data[t] - trend[t] - seasonal[t % season_length])
+        .collect();
+
+    Ok((
+        component_strength(&trend_part, &remainder),
+        component_strength(&season_part, &remainder),
+    ))
+}
+
+/// Strength of trend and seasonality in a series, each in [0, 1].
+///
+/// From a classical additive decomposition `y = T + S + R` (centered moving
+/// average trend, per-phase mean seasonal component), the strengths are
+/// `max(0, 1 - var(R) / var(T + R))` and `max(0, 1 - var(R) / var(S + R))`
+/// as in Wang, Smith & Hyndman (2006). Values near 1 mean the component
+/// dominates the noise; near 0, it is indistinguishable from it.
+///
+/// # Returns
+///
+/// Tuple `(trend_strength, seasonal_strength)`
+///
+/// # Errors
+///
+/// Returns PyValueError if season_length is less than 2 or data holds fewer
+/// than two full seasons.
+#[pyfunction]
+pub fn series_features(data: Vec<f64>, season_length: usize) -> PyResult<(f64, f64)> {
+    series_features_impl(&data, season_length).map_err(PyValueError::new_err)
+}
+
+/// Check that a series is long enough for a meaningful periodogram.
+fn validate_spectrum_length(n: usize) -> Result<(), String> {
+    if n < MIN_SPECTRUM_LENGTH {
+        return Err(format!(
+            "Spectral analysis requires at least {} data points, got {}",
+            MIN_SPECTRUM_LENGTH, n
+        ));
+    }
+
+    Ok(())
+}
+
+/// Core FFT period suggestion logic.
+pub(crate) fn suggest_period_fft_impl(data: &[f64]) -> Result<Vec<usize>, String> {
+    validate_spectrum_length(data.len())?;
+
+    let n = data.len();
+    let power = periodogram(data);
+
+    // Local maxima from k = 2, so each suggested period repeats at least twice
+    let mut peaks: Vec<usize> = (2..power.len())
+        .filter(|&k| {
+            power[k] > power[k - 1]
+                && (k + 1 == power.len() || power[k] >= power[k + 1])
+                && power[k] > 0.0
+        })
+        .collect();
+    peaks.sort_by(|&a, &b| power[b].total_cmp(&power[a]));
+
+    let mut periods = Vec::new();
+    for k in peaks {
+        let period = (n as f64 / k as f64).round() as usize;
+        if period >= 2 && !periods.contains(&period) {
+            periods.push(period);
+        }
+        if periods.len() == MAX_PERIOD_SUGGESTIONS {
+            break;
+        }
+    }
+
+    Ok(periods)
+}
+
+/// Suggest seasonal periods from the peaks of the periodogram.
+///
+/// The periodogram of the demeaned series is computed with an FFT, and the
+/// periods `round(n / k)` of its local peaks are returned strongest first.
+/// The zero frequency is excluded, and so are periods longer than half the
+/// series, which would not repeat at least twice. Unlike the
+/// autocorrelation-based detector, several cycles can be reported at once.
+///
+/// # Returns
+///
+/// Up to 5 distinct periods, sorted by decreasing spectral power
+///
+/// # Errors
+///
+/// Returns PyValueError if data has fewer than 8 points.
+#[pyfunction]
+pub fn suggest_period_fft(data: Vec<f64>) -> PyResult<Vec<usize>> {
+    suggest_period_fft_impl(&data).map_err(PyValueError::new_err)
+}
+
+/// Core forecastability logic: one minus the normalized Shannon entropy of
+/// the periodogram over the non-zero frequencies.
+pub(crate) fn forecastability_impl(data: &[f64]) -> Result<f64, String> {
+    validate_spectrum_length(data.len())?;
+
+    if data.iter().any(|v| !v.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    // Checked on the data: demeaning a constant such as 0.1 can leave
+    // rounding residue with a tiny but non-zero spectrum
+    if data.iter().all(|&v| v == data[0]) {
+        return Err("Forecastability is undefined for a constant series".to_string());
+    }
+
+    let power = periodogram(data);
+    let total: f64 = power[1..].iter().sum();
+
+    let entropy: f64 = power[1..]
+        .iter()
+        .filter(|p| **p > 0.0)
+        .map(|p| {
+            let share = p / total;
+            -share * share.ln()
+        })
+        .sum();
+    let max_entropy = ((power.len() - 1) as f64).ln();
+
+    Ok((1.0 - entropy / max_entropy).clamp(0.0, 1.0))
+}
+
+/// Score in `[0, 1]` of how forecastable a series is, from its spectral
+/// entropy.
+///
+/// The periodogram of the demeaned series is normalized into a distribution
+/// over the non-zero frequencies, and its Shannon entropy is divided by the
+/// largest possible value, that of a flat spectrum. The score is one minus
+/// that ratio: power concentrated in a few frequencies, as in a clean cycle,
+/// scores near 1, while white noise spreads it evenly and scores near 0
+/// (about 0.1 in practice, since sample periodograms are never quite flat).
+///
+/// # Errors
+///
+/// Returns PyValueError if data has fewer than 8 points, a non-finite value,
+/// or is constant.
+///
+/// # Examples
+///
+/// ```python
+/// import math
+/// import sa_native
+/// sa_native.forecastability([math.sin(2 * math.pi * t / 12) for t in range(120)])
+/// # close to 1.0
+/// ```
+#[pyfunction]
+pub fn forecastability(data: Vec<f64>) -> PyResult<f64> {
+    forecastability_impl(&data).map_err(PyValueError::new_err)
+}
+
+/// Core differencing order logic: the `d` in `0..=max_d` whose `d`-times
+/// differenced series has the smallest sample variance.
+pub(crate) fn estimate_diff_order_impl(data: &[f64], max_d: usize) -> Result<usize, String> {
+    // Saturating, as a wrapped bound would accept any data and never end
+    if data.len() < max_d.saturating_add(2) {
+        return Err(format!(
+            "Estimating up to {} differences requires at least {} data points, got {}",
+            max_d,
+            max_d as u128 + 2,
+            data.len()
+        ));
+    }
+
+    if data.iter().any(|v| !v.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    let mut series = data.to_vec();
+    let mut best = (variance_impl(&series), 0);
+    for d in 1..=max_d {
+        series = series.windows(2).map(|w| w[1] - w[0]).collect();
+        let variance = variance_impl(&series);
+        if variance < best.0 {
+            best = (variance, d);
+        }
+    }
+
+    Ok(best.1)
+}
+
+/// Estimate how many times a series should be differenced to be stationary.
+///
+/// Uses the minimum-variance rule: the series is differenced up to `max_d`
+/// times and the order whose result has the smallest sample variance wins.
+/// Differencing a non-stationary (integrated) series removes the wandering
+/// level and shrinks the variance, while differencing an already stationary
+/// one adds noise (white noise doubles its variance), so the minimum sits
+/// at the order of integration. Ties keep the lower order.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `max_d` - Largest differencing order considered
+///
+/// # Errors
+///
+/// Returns PyValueError if data is not finite or has fewer than
+/// `max_d + 2` points.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.estimate_diff_order([1.0, 3.0, 2.0, 5.0, 4.0, 7.0, 6.0, 9.0], 2)
+/// # 1: the fluctuations ride on a steady upward drift
+/// ```
+#[pyfunction]
+pub fn estimate_diff_order(data: Vec<f64>, max_d: usize) -> PyResult<usize> {
+    estimate_diff_order_impl(&data, max_d).map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_growth_rates_constant_growth() {
+        let data: Vec<f64> = (0..6).map(|t| 50.0 * 1.2_f64.powi(t)).collect();
+        let rates = growth_rates_impl(&data).unwrap();
+
+        assert_eq!(rates.len(), data.len() - 1);
+        assert!(rates.iter().all(|r| (r - 0.2).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_growth_rates_zero_denominator() {
+        let result = growth_rates_impl(&[1.0, 0.0, 2.0]);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Growth rate undefined at index 2: previous value is zero"
+        );
+    }
+
+    #[test]
+    fn test_growth_rates_zero_last_value_allowed() {
+        let rates = growth_rates_impl(&[2.0, 0.0]).unwrap();
+
+        assert_eq!(rates, vec![-1.0]);
+    }
+
+    #[test]
+    fn test_growth_rates_single_point() {
+        let result = growth_rates_impl(&[1.0]);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Growth rates require at least two data points");
+    }
+
+    #[test]
+    fn test_variance_known_value() {
+        let variance = variance_impl(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+
+        assert!((variance - 32.0 / 7.0).abs() < 1e-12);
+        assert!(variance_impl(&[1.0]).is_nan());
+    }
+
+    #[test]
+    fn test_variance_large_offset_stays_accurate() {
+        let mut rng = Rng::new(3);
+        let noise: Vec<f64> = (0..1000).map(|_| rng.uniform() - 0.5).collect();
+        let data: Vec<f64> = noise.iter().map(|e| 1e9 + e).collect();
+
+        // Reference: the offset is removed exactly and the small residuals
+        // are summed in two passes, losing no significant digits
+        let centered: Vec<f64> = data.iter().map(|y| y - 1e9).collect();
+        let mean = centered.iter().sum::<f64>() / 1000.0;
+        let reference = centered.iter().map(|e| (e - mean).powi(2)).sum::<f64>() / 999.0;
+
+        // Naive sum-of-squares formula cancels catastrophically at this offset
+        let n = data.len() as f64;
+        let sum: f64 = data.iter().sum();
+        let sum_sq: f64 = data.iter().map(|y| y * y).sum();
+        let naive = (sum_sq - sum * sum / n) / (n - 1.0);
+
+        let welford = variance_impl(&data);
+        assert!((welford - reference).abs() / reference < 1e-6);
+        assert!((naive - reference).abs() / reference > 1e-2);
+    }
+
+    #[test]
+    fn test_suggest_period_fft_two_sinusoids() {
+        use std::f64::consts::PI;
+        let data: Vec<f64> = (0..168)
+            .map(|t| {
+                let t = t as f64;
+                3.0 * (2.0 * PI * t / 12.0).sin() + 2.0 * (2.0 * PI * t / 7.0).cos()
+            })
+            .collect();
+        let periods = suggest_period_fft_impl(&data).unwrap();
+
+        assert_eq!(&periods[..2], &[12, 7]);
+    }
+
+    #[test]
+    fn test_suggest_period_fft_short_series() {
+        let result = suggest_period_fft_impl(&[1.0, 2.0, 3.0]);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Spectral analysis requires at least 8 data points, got 3"
+        );
+    }
+
+    #[test]
+    fn test_forecastability_sinusoid_high_noise_low() {
+        use std::f64::consts::PI;
+        let sinusoid: Vec<f64> = (0..120).map(|t| (2.0 * PI * t as f64 / 12.0).sin()).collect();
+        assert!(forecastability_impl(&sinusoid).unwrap() > 0.9);
+
+        let mut rng = Rng::new(4);
+        let noise: Vec<f64> = (0..120).map(|_| rng.normal()).collect();
+        assert!(forecastability_impl(&noise).unwrap() < 0.25);
+    }
+
+    #[test]
+    fn test_forecastability_invalid_data() {
+        assert_eq!(
+            forecastability_impl(&[1.0; 4]).unwrap_err(),
+            "Spectral analysis requires at least 8 data points, got 4"
+        );
+        assert_eq!(
+            forecastability_impl(&[1.0; 16]).unwrap_err(),
+            "Forecastability is undefined for a constant series"
+        );
+    }
+
+    #[test]
+    fn test_forecastability_constant_with_rounding_mean() {
+        // For some lengths the mean rounds away from the value itself
+        let constants: [&[f64]; 4] = [&[0.1; 10], &[0.1; 13], &[0.3; 9], &[0.3; 10]];
+        for data in constants {
+            assert_eq!(
+                forecastability_impl(data).unwrap_err(),
+                "Forecastability is undefined for a constant series"
+            );
+        }
+    }
+
+    #[test]
+    fn test_data_report_mixed_values() {
+        let data = [
+            2.0,
+            f64::NAN,
+            0.0,
+            f64::INFINITY,
+            4.0,
+            f64::NEG_INFINITY,
+            0.0,
+            f64::NAN,
+            -6.0,
+        ];
+        let report = data_report_impl(&data).unwrap();
+
+        assert_eq!(report["n"], 9.0);
+        assert_eq!(report["n_finite"], 5.0);
+        assert_eq!(report["n_nan"], 2.0);
+        assert_eq!(report["n_inf"], 2.0);
+        assert_eq!(report["n_zeros"], 2.0);
+        assert_eq!(report["mean"], 0.0);
+        assert!((report["std"] - 14.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(report["min"], -6.0);
+        assert_eq!(report["max"], 4.0);
+    }
+
+    #[test]
+    fn test_data_report_no_finite_values() {
+        let report = data_report_impl(&[f64::NAN, f64::INFINITY]).unwrap();
+
+        assert_eq!(report["n_finite"], 0.0);
+        assert!(report["mean"].is_nan());
+        assert!(report["std"].is_nan());
+        assert!(report["min"].is_nan());
+    }
+
+    #[test]
+    fn test_quantile_sorted_interpolates() {
+        let sorted = [1.0, 2.0, 4.0, 8.0];
+
+        assert_eq!(quantile_sorted(&sorted, 0.0), 1.0);
+        assert_eq!(quantile_sorted(&sorted, 1.0), 8.0);
+        assert_eq!(quantile_sorted(&sorted, 0.5), 3.0);
+        assert_eq!(quantile_sorted(&sorted, 0.25), 1.75);
+    }
+
+    #[test]
+    fn test_cross_correlation_peaks_at_known_lag() {
+        let mut rng = crate::rng::Rng::new(3);
+        let x: Vec<f64> = (0..200).map(|_| rng.normal()).collect();
+        // y[t] = x[t - 4]: x leads y by four steps
+        let y: Vec<f64> = (0..200).map(|t| if t >= 4 { x[t - 4] } else { 0.0 }).collect();
+        let ccf = cross_correlation_impl(&x, &y, 6).unwrap();
+        let peak = (0..ccf.len()).max_by(|&i, &j| ccf[i].total_cmp(&ccf[j])).unwrap();
+
+        assert_eq!(ccf.len(), 13);
+        assert_eq!(peak as isize - 6, 4);
+
+        let reversed = cross_correlation_impl(&y, &x, 6).unwrap();
+        let peak = (0..reversed.len())
+            .max_by(|&i, &j| reversed[i].total_cmp(&reversed[j]))
+            .unwrap();
+        assert_eq!(peak as isize - 6, -4);
+    }
+
+    #[test]
+    fn test_cross_correlation_lag_zero_is_pearson() {
+        let x = [1.0, 2.0, 3.0, 4.0];
+        let ccf = cross_correlation_impl(&x, &[2.0, 4.0, 6.0, 8.0], 1).unwrap();
+
+        assert!((ccf[1] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cross_correlation_invalid_max_lag() {
+        let result = cross_correlation_impl(&[1.0, 2.0, 3.0], &[3.0, 1.0, 2.0], 3);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "max_lag (3) must be less than the series length (3)"
+        );
+    }
+
+    #[test]
+    fn test_cross_correlation_rejects_non_finite() {
+        for value in [f64::NAN, f64::INFINITY] {
+            let result = cross_correlation_impl(&[1.0, value, 3.0], &[3.0, 1.0, 2.0], 1);
+            assert_eq!(result.unwrap_err(), "Input data must be finite");
+
+            let result = cross_correlation_impl(&[1.0, 2.0, 3.0], &[3.0, 1.0, value], 1);
+            assert_eq!(result.unwrap_err(), "Input data must be finite");
+        }
+    }
+
+    #[test]
+    fn test_nanmean_nanstd_match_finite_subset() {
+        let data = [f64::NAN, 2.0, 4.0, f64::INFINITY, 4.0, f64::NAN, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let finite = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+
+        assert_eq!(nanmean_impl(&data).unwrap(), 5.0);
+        assert_eq!(nanstd_impl(&data).unwrap(), variance_impl(&finite).sqrt());
+        assert!((nanstd_impl(&data).unwrap() - (32.0_f64 / 7.0).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_nanmean_all_non_finite() {
+        let result = nanmean_impl(&[f64::NAN, f64::NEG_INFINITY]);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Input data has no finite values");
+        assert!(nanstd_impl(&[]).is_err());
+        assert!(nanstd_impl(&[f64::NAN, 3.0]).unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_series_features_separates_trend_and_season() {
+        let mut rng = crate::rng::Rng::new(21);
+        let pattern = [5.0, -3.0, 2.0, -4.0];
+        let seasonal: Vec<f64> = (0..48).map(|t| 20.0 + pattern[t % 4] + 0.3 * rng.normal()).collect();
+        let trending: Vec<f64> = (0..48).map(|t| 0.8 * t as f64 + 0.3 * rng.normal()).collect();
+
+        let (trend, season) = series_features_impl(&seasonal, 4).unwrap();
+        assert!(season > 0.95 && trend < 0.5, "{} {}", trend, season);
+
+        let (trend, season) = series_features_impl(&trending, 4).unwrap();
+        assert!(trend > 0.95 && season < 0.5, "{} {}", trend, season);
+    }
+
+    #[test]
+    fn test_mad_ignores_outliers_unlike_std() {
+        let mut rng = Rng::new(8);
+        let mut data: Vec<f64> = (0..40).map(|_| 10.0 + rng.normal()).collect();
+        data[5] = 60.0;
+        data[22] = -45.0;
+
+        let mad = mad_impl(&data).unwrap();
+        let std = variance_impl(&data).sqrt();
+        assert!(mad > 0.6 && mad < 1.5);
+        assert!(std > 5.0 * mad);
+        assert_eq!(mad_impl(&[1.0, 2.0, 3.0, 4.0, 100.0]).unwrap(), MAD_SCALE);
+    }
+
+    #[test]
+    fn test_mad_invalid_data() {
+        assert_eq!(mad_impl(&[]).unwrap_err(), "Input data cannot be empty");
+        assert_eq!(mad_impl(&[1.0, f64::NAN]).unwrap_err(), "Input data must be finite");
+    }
+
+    #[test]
+    fn test_effective_sample_size_white_noise_and_ar1() {
+        let mut rng = Rng::new(17);
+        let noise: Vec<f64> = (0..500).map(|_| rng.normal()).collect();
+        let ess = effective_sample_size_impl(&noise).unwrap();
+        assert!(ess > 400.0 && ess <= 500.0);
+
+        // AR(1) with coefficient 0.9: ESS near n * 0.1 / 1.9
+        let mut level = 0.0;
+        let ar1: Vec<f64> = (0..500)
+            .map(|_| {
+                level = 0.9 * level + rng.normal();
+                level
+            })
+            .collect();
+        let ess = effective_sample_size_impl(&ar1).unwrap();
+        assert!(ess < 100.0);
+    }
+
+    #[test]
+    fn test_effective_sample_size_invalid_data() {
+        assert_eq!(effective_sample_size_impl(&[]).unwrap_err(), "Input data cannot be empty");
+        assert_eq!(
+            effective_sample_size_impl(&[2.0, 2.0, 2.0]).unwrap_err(),
+            "Effective sample size is undefined for a constant series"
+        );
+    }
+
+    #[test]
+    fn test_z_score_known_values() {
+        assert!((z_score_impl(0.95).unwrap() - 1.959963985).abs() < 1e-8);
+        assert!((z_score_impl(0.90).unwrap() - 1.644853627).abs() < 1e-8);
+        assert!((z_score_impl(0.99).unwrap() - 2.575829304).abs() < 1e-8);
+        assert!((z_score_impl(0.999999).unwrap() - 4.891638476).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_inverse_normal_cdf_is_antisymmetric() {
+        for p in [0.001, 0.01, 0.2, 0.4] {
+            let z = inverse_normal_cdf(p);
+            assert!(z < 0.0);
+            assert!((z + inverse_normal_cdf(1.0 - p)).abs() < 1e-9);
+        }
+        assert_eq!(inverse_normal_cdf(0.5), 0.0);
+    }
+
+    #[test]
+    fn test_student_t_two_sided_p_matches_tables() {
+        // One degree of freedom is the Cauchy distribution: P(|T| >= 1) = 1/2
+        assert!((student_t_two_sided_p(1.0, 1.0) - 0.5).abs() < 1e-12);
+        // Two-sided 5% critical values for 10 and 30 degrees of freedom
+        assert!((student_t_two_sided_p(2.228_138_851_986_273_4, 10.0) - 0.05).abs() < 1e-9);
+        assert!((student_t_two_sided_p(-2.042_272_456_301_238, 30.0) - 0.05).abs() < 1e-9);
+        assert_eq!(student_t_two_sided_p(0.0, 5.0), 1.0);
+        assert!(student_t_two_sided_p(50.0, 20.0) < 1e-20);
+    }
+
+    #[test]
+    fn test_z_score_invalid_confidence() {
+        for confidence in [0.0, 1.0, -0.5, f64::NAN] {
+            assert!(z_score_impl(confidence).is_err());
+        }
+        assert_eq!(z_score_impl(1.0).unwrap_err(), "Confidence must be in (0, 1), got 1");
+    }
+
+    #[test]
+    fn test_estimate_diff_order_doubly_integrated_walk() {
+        let mut rng = Rng::new(31);
+        let mut slope = 0.0;
+        let mut level = 0.0;
+        let data: Vec<f64> = (0..500)
+            .map(|_| {
+                slope += rng.normal();
+                level += slope;
+                level
+            })
+            .collect();
+
+        assert_eq!(estimate_diff_order_impl(&data, 3).unwrap(), 2);
+        assert_eq!(estimate_diff_order_impl(&data, 1).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_estimate_diff_order_stationary_noise() {
+        let mut rng = Rng::new(32);
+        let data: Vec<f64> = (0..500).map(|_| 4.0 + rng.normal()).collect();
+
+        assert_eq!(estimate_diff_order_impl(&data, 3).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_estimate_diff_order_too_short() {
+        let result = estimate_diff_order_impl(&[1.0, 2.0, 3.0], 2);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Estimating up to 2 differences requires at least 4 data points, got 3"
+        );
+    }
+
+    #[test]
+    fn test_estimate_diff_order_huge_max_d() {
+        let result = estimate_diff_order_impl(&[1.0, 2.0, 3.0], usize::MAX);
+
+        assert_eq!(
+            result.unwrap_err(),
+            "Estimating up to 18446744073709551615 differences requires at least \
+             18446744073709551617 data points, got 3"
+        );
+    }
+}
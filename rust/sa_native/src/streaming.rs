@@ -0,0 +1,561 @@
+use std::sync::{Mutex, MutexGuard};
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::PyDict;
+
+use crate::smoothing::validate_smoothing;
+use crate::trend::fit_drift_impl;
+
+/// Mutable state of a streaming simple exponential smoother.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SesState {
+    pub(crate) alpha: f64,
+    pub(crate) level: Option<f64>,
+    pub(crate) count: u64,
+}
+
+impl SesState {
+    pub(crate) fn new(alpha: f64) -> Result<Self, String> {
+        validate_smoothing("Alpha", alpha)?;
+        Ok(SesState { alpha, level: None, count: 0 })
+    }
+
+    /// Rebuild a state from checkpointed parts, checking they are consistent:
+    /// an initialized state has a finite level and at least one observation,
+    /// an uninitialized one has neither.
+    pub(crate) fn from_checkpoint(
+        alpha: f64,
+        level: Option<f64>,
+        initialized: bool,
+        count: u64,
+    ) -> Result<Self, String> {
+        validate_smoothing("Alpha", alpha)?;
+
+        match (initialized, level) {
+            (true, Some(level)) if !level.is_finite() => {
+                return Err(format!("Checkpoint level must be finite, got {}", level))
+            }
+            (true, None) => {
+                return Err("Checkpoint is initialized but has no level".to_string())
+            }
+            (false, Some(_)) => {
+                return Err("Checkpoint is not initialized but has a level".to_string())
+            }
+            _ => {}
+        }
+
+        if initialized != (count > 0) {
+            return Err(format!(
+                "Checkpoint count ({}) is inconsistent with initialized = {}",
+                count, initialized
+            ));
+        }
+
+        Ok(SesState { alpha, level, count })
+    }
+
+    /// Fold one observation into the level; the first one initializes it.
+    pub(crate) fn update(&mut self, value: f64) -> Result<(), String> {
+        if !value.is_finite() {
+            return Err(format!("Observation must be finite, got {}", value));
+        }
+
+        self.level = Some(match self.level {
+            Some(level) => level + self.alpha * (value - level),
+            None => value,
+        });
+        self.count += 1;
+        Ok(())
+    }
+
+    pub(crate) fn forecast(&self, horizon: usize) -> Result<Vec<f64>, String> {
+        let level = self
+            .level
+            .ok_or_else(|| "No observations have been received yet".to_string())?;
+
+        if horizon == 0 {
+            return Err("Horizon must be greater than 0".to_string());
+        }
+
+        Ok(vec![level; horizon])
+    }
+}
+
+/// Simple exponential smoothing updated one observation at a time.
+///
+/// After receiving the observations of a series in order, `forecast` returns
+/// exactly what `predict_ses` would return for the whole series.
+///
+/// Thread safety: the state is guarded by a mutex, so an instance can be
+/// shared between Python threads. Each `update` is applied atomically and
+/// no update is lost. Updates from different threads are applied in the
+/// order they acquire the lock, so when several threads push different
+/// values the final level depends on that interleaving; the observation
+/// `count` does not.
+#[pyclass]
+pub struct StreamingSes {
+    state: Mutex<SesState>,
+}
+
+impl StreamingSes {
+    fn lock(&self) -> MutexGuard<'_, SesState> {
+        // State is only replaced after successful validation, so it is
+        // consistent even if a previous holder panicked
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[pymethods]
+impl StreamingSes {
+    /// Create a smoother with smoothing parameter `alpha` in `(0, 1]`.
+    #[new]
+    fn new(alpha: f64) -> PyResult<Self> {
+        let state = SesState::new(alpha).map_err(PyValueError::new_err)?;
+        Ok(StreamingSes { state: Mutex::new(state) })
+    }
+
+    /// Fold a new observation into the level.
+    fn update(&self, value: f64) -> PyResult<()> {
+        self.lock().update(value).map_err(PyValueError::new_err)
+    }
+
+    /// Forecast `horizon` steps from the current level.
+    fn forecast(&self, horizon: usize) -> PyResult<Vec<f64>> {
+        self.lock().forecast(horizon).map_err(PyValueError::new_err)
+    }
+
+    /// Current level, or None before the first observation.
+    #[getter]
+    fn level(&self) -> Option<f64> {
+        self.lock().level
+    }
+
+    /// Smoothing parameter.
+    #[getter]
+    fn alpha(&self) -> f64 {
+        self.lock().alpha
+    }
+
+    /// Number of observations received.
+    #[getter]
+    fn count(&self) -> u64 {
+        self.lock().count
+    }
+
+    /// Checkpoint the state as a dict with keys `level`, `alpha`,
+    /// `initialized` and `count`, for `from_dict` to resume from.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        let state = self.lock().clone();
+        let dict = PyDict::new(py);
+        dict.set_item("level", state.level)?;
+        dict.set_item("alpha", state.alpha)?;
+        dict.set_item("initialized", state.level.is_some())?;
+        dict.set_item("count", state.count)?;
+        Ok(dict)
+    }
+
+    /// Resume a smoother from a `to_dict` checkpoint.
+    ///
+    /// Raises ValueError if a key is missing, alpha is out of range, or the
+    /// level, `initialized` flag and count disagree.
+    #[staticmethod]
+    fn from_dict(state: &PyDict) -> PyResult<Self> {
+        let item = |key: &str| {
+            state.get_item(key).ok_or_else(|| {
+                PyValueError::new_err(format!("Checkpoint is missing key '{}'", key))
+            })
+        };
+
+        let state = SesState::from_checkpoint(
+            item("alpha")?.extract()?,
+            item("level")?.extract()?,
+            item("initialized")?.extract()?,
+            item("count")?.extract()?,
+        )
+        .map_err(PyValueError::new_err)?;
+        Ok(StreamingSes { state: Mutex::new(state) })
+    }
+}
+
+/// Core state of `BatchModels`: one streaming smoother per series.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct BatchSesState {
+    pub(crate) series: Vec<SesState>,
+}
+
+impl BatchSesState {
+    pub(crate) fn new(n_series: usize, alpha: f64) -> Result<Self, String> {
+        if n_series == 0 {
+            return Err("Number of series must be greater than 0".to_string());
+        }
+
+        Ok(BatchSesState { series: vec![SesState::new(alpha)?; n_series] })
+    }
+
+    /// Apply one optional new point per series; `None` leaves a series as is.
+    ///
+    /// The batch is validated in full before any series is updated.
+    pub(crate) fn append(&mut self, new_points: &[Option<f64>]) -> Result<(), String> {
+        if new_points.len() != self.series.len() {
+            return Err(format!(
+                "Batch has {} points but {} series are stored",
+                new_points.len(),
+                self.series.len()
+            ));
+        }
+
+        if let Some((i, value)) = new_points
+            .iter()
+            .enumerate()
+            .find_map(|(i, p)| p.filter(|v| !v.is_finite()).map(|v| (i, v)))
+        {
+            return Err(format!("Observation for series {} must be finite, got {}", i, value));
+        }
+
+        for (state, point) in self.series.iter_mut().zip(new_points) {
+            if let Some(value) = point {
+                state.update(*value)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn forecast_all(&self, horizon: usize) -> Result<Vec<Vec<f64>>, String> {
+        self.series
+            .iter()
+            .enumerate()
+            .map(|(i, state)| state.forecast(horizon).map_err(|e| format!("Series {}: {}", i, e)))
+            .collect()
+    }
+}
+
+/// Streaming simple exponential smoothing for many series at once.
+///
+/// Each call to `append_batch` carries at most one new point per series, and
+/// `forecast_all` returns what `predict_ses` would give for each series'
+/// history so far.
+#[pyclass]
+pub struct BatchModels {
+    state: BatchSesState,
+}
+
+#[pymethods]
+impl BatchModels {
+    /// Create `n_series` empty smoothers sharing smoothing parameter `alpha`.
+    #[new]
+    fn new(n_series: usize, alpha: f64) -> PyResult<Self> {
+        let state = BatchSesState::new(n_series, alpha).map_err(PyValueError::new_err)?;
+        Ok(BatchModels { state })
+    }
+
+    /// Append one optional point per series (`None` skips that series).
+    ///
+    /// # Errors
+    ///
+    /// Returns PyValueError if the batch length differs from the number of
+    /// series or a point is not finite; no series is updated in that case.
+    fn append_batch(&mut self, new_points: Vec<Option<f64>>) -> PyResult<()> {
+        self.state.append(&new_points).map_err(PyValueError::new_err)
+    }
+
+    /// Forecast `horizon` steps for every series, in series order.
+    ///
+    /// # Errors
+    ///
+    /// Returns PyValueError if horizon is 0 or a series has no observations.
+    fn forecast_all(&self, horizon: usize) -> PyResult<Vec<Vec<f64>>> {
+        self.state.forecast_all(horizon).map_err(PyValueError::new_err)
+    }
+
+    /// Number of series held.
+    #[getter]
+    fn n_series(&self) -> usize {
+        self.state.series.len()
+    }
+}
+
+/// Unbounded forecast along a line from the last observation: step `h` is
+/// `last + h * slope`, so a zero slope carries the last value forward.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LineForecast {
+    pub(crate) last: f64,
+    pub(crate) slope: f64,
+    pub(crate) step: u64,
+}
+
+impl LineForecast {
+    /// Static forecast: the last value, indefinitely.
+    pub(crate) fn carry_forward(data: &[f64]) -> Result<Self, String> {
+        let last = *data.last().ok_or_else(|| "Input data cannot be empty".to_string())?;
+        Ok(LineForecast { last, slope: 0.0, step: 0 })
+    }
+
+    /// Drift forecast: the line through the first and last values, extended.
+    pub(crate) fn drift(data: &[f64]) -> Result<Self, String> {
+        let slope = fit_drift_impl(data)?;
+        Ok(LineForecast { last: data[data.len() - 1], slope, step: 0 })
+    }
+}
+
+impl Iterator for LineForecast {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        // Computed from the step count rather than accumulated, so values
+        // match the finite forecasts exactly and do not drift with rounding
+        self.step += 1;
+        Some(self.last + self.step as f64 * self.slope)
+    }
+}
+
+/// Lazy, unbounded forecast following the Python iterator protocol.
+///
+/// Each `next()` yields the forecast one step further ahead; the iterator
+/// never raises `StopIteration`, so callers must bound their own consumption
+/// (e.g. with `itertools.islice` or `zip` against a finite sequence). A
+/// plain `list(...)` over it never returns.
+#[pyclass]
+pub struct ForecastIterator {
+    state: LineForecast,
+}
+
+#[pymethods]
+impl ForecastIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<f64> {
+        self.state.next()
+    }
+
+    /// Number of values yielded so far.
+    #[getter]
+    fn steps_taken(&self) -> u64 {
+        self.state.step
+    }
+}
+
+/// Lazily forecast the last value, indefinitely.
+///
+/// The iterator yields what `predict_static` returns for any horizon, one
+/// step at a time and without end: take only as many values as needed.
+///
+/// # Errors
+///
+/// Returns PyValueError if data is empty.
+///
+/// # Examples
+///
+/// ```python
+/// import itertools
+/// import sa_native
+/// list(itertools.islice(sa_native.predict_static_iter([1.0, 2.0, 3.0]), 3))
+/// # [3.0, 3.0, 3.0]
+/// ```
+#[pyfunction]
+pub fn predict_static_iter(data: Vec<f64>) -> PyResult<ForecastIterator> {
+    let state = LineForecast::carry_forward(&data).map_err(PyValueError::new_err)?;
+    Ok(ForecastIterator { state })
+}
+
+/// Lazily forecast with the drift method, indefinitely.
+///
+/// The iterator yields the successive points of `predict_drift`: the last
+/// value moved on by the mean one-step change at every step, without end.
+///
+/// # Errors
+///
+/// Returns PyValueError if data has fewer than two points.
+///
+/// # Examples
+///
+/// ```python
+/// import itertools
+/// import sa_native
+/// list(itertools.islice(sa_native.predict_drift_iter([1.0, 3.0]), 3))
+/// # [5.0, 7.0, 9.0]
+/// ```
+#[pyfunction]
+pub fn predict_drift_iter(data: Vec<f64>) -> PyResult<ForecastIterator> {
+    let state = LineForecast::drift(&data).map_err(PyValueError::new_err)?;
+    Ok(ForecastIterator { state })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::predict_static_impl;
+    use crate::smoothing::predict_ses_impl;
+    use crate::trend::predict_drift_impl;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_streaming_ses_matches_batch() {
+        let data = vec![3.0, 5.0, 4.0, 8.0, 6.0];
+        let mut state = SesState::new(0.3).unwrap();
+        for value in &data {
+            state.update(*value).unwrap();
+        }
+
+        assert_eq!(state.forecast(3).unwrap(), predict_ses_impl(&data, 3, 0.3).unwrap());
+        assert_eq!(state.count, 5);
+    }
+
+    #[test]
+    fn test_streaming_ses_checkpoint_resume_matches_uninterrupted() {
+        let data = [3.0, 5.0, 4.0, 8.0, 6.0, 9.0, 7.0];
+        let mut uninterrupted = SesState::new(0.3).unwrap();
+        for value in &data {
+            uninterrupted.update(*value).unwrap();
+        }
+
+        let mut first = SesState::new(0.3).unwrap();
+        for value in &data[..4] {
+            first.update(*value).unwrap();
+        }
+        let mut resumed =
+            SesState::from_checkpoint(first.alpha, first.level, first.level.is_some(), first.count)
+                .unwrap();
+        for value in &data[4..] {
+            resumed.update(*value).unwrap();
+        }
+
+        assert_eq!(resumed, uninterrupted);
+        assert_eq!(resumed.forecast(3).unwrap(), uninterrupted.forecast(3).unwrap());
+    }
+
+    #[test]
+    fn test_streaming_ses_checkpoint_validation() {
+        assert!(SesState::from_checkpoint(0.3, None, false, 0).is_ok());
+        assert_eq!(
+            SesState::from_checkpoint(1.5, Some(1.0), true, 1).unwrap_err(),
+            SesState::new(1.5).unwrap_err()
+        );
+        assert_eq!(
+            SesState::from_checkpoint(0.3, None, true, 2).unwrap_err(),
+            "Checkpoint is initialized but has no level"
+        );
+        assert_eq!(
+            SesState::from_checkpoint(0.3, Some(1.0), false, 0).unwrap_err(),
+            "Checkpoint is not initialized but has a level"
+        );
+        assert_eq!(
+            SesState::from_checkpoint(0.3, Some(1.0), true, 0).unwrap_err(),
+            "Checkpoint count (0) is inconsistent with initialized = true"
+        );
+    }
+
+    #[test]
+    fn test_streaming_ses_concurrent_updates_are_not_lost() {
+        let smoother = Arc::new(StreamingSes {
+            state: Mutex::new(SesState::new(0.4).unwrap()),
+        });
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let smoother = Arc::clone(&smoother);
+                thread::spawn(move || {
+                    for _ in 0..500 {
+                        smoother.lock().update(7.0).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // A constant stream gives the same level in any interleaving
+        let state = smoother.lock();
+        assert_eq!(state.count, 2000);
+        assert_eq!(state.level, Some(7.0));
+    }
+
+    #[test]
+    fn test_streaming_ses_forecast_before_update() {
+        let state = SesState::new(0.5).unwrap();
+        let result = state.forecast(2);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "No observations have been received yet");
+    }
+
+    #[test]
+    fn test_streaming_ses_rejects_non_finite() {
+        let mut state = SesState::new(0.5).unwrap();
+        let result = state.update(f64::NAN);
+
+        assert!(result.is_err());
+        assert_eq!(state.count, 0);
+    }
+
+    #[test]
+    fn test_batch_models_incremental_matches_from_scratch() {
+        let histories = [
+            vec![1.0, 2.0, 3.0, 4.0],
+            vec![10.0, 8.0],
+            vec![5.0, 5.0, 6.0],
+        ];
+        let mut batch = BatchSesState::new(3, 0.5).unwrap();
+        for step in 0..4 {
+            let points: Vec<Option<f64>> = histories.iter().map(|h| h.get(step).copied()).collect();
+            batch.append(&points).unwrap();
+        }
+
+        let forecasts = batch.forecast_all(3).unwrap();
+        for (history, forecast) in histories.iter().zip(&forecasts) {
+            assert_eq!(forecast, &predict_ses_impl(history, 3, 0.5).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_batch_models_length_mismatch_leaves_state_unchanged() {
+        let mut batch = BatchSesState::new(2, 0.5).unwrap();
+        let before = batch.clone();
+        let result = batch.append(&[Some(1.0)]);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Batch has 1 points but 2 series are stored");
+        assert_eq!(batch, before);
+    }
+
+    #[test]
+    fn test_batch_models_forecast_series_without_data() {
+        let mut batch = BatchSesState::new(2, 0.5).unwrap();
+        batch.append(&[Some(1.0), None]).unwrap();
+        let result = batch.forecast_all(1);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Series 1: No observations have been received yet");
+    }
+
+    #[test]
+    fn test_line_forecast_matches_finite_forecasts() {
+        let data = [4.0, 7.0, 5.0, 9.5];
+        let carried: Vec<f64> = LineForecast::carry_forward(&data).unwrap().take(5).collect();
+        let drift: Vec<f64> = LineForecast::drift(&data).unwrap().take(5).collect();
+
+        assert_eq!(carried, predict_static_impl(&data, 5).unwrap());
+        assert_eq!(drift, predict_drift_impl(&data, 5).unwrap());
+    }
+
+    #[test]
+    fn test_line_forecast_continues_past_any_horizon() {
+        let mut iterator = LineForecast::drift(&[0.0, 0.1]).unwrap();
+        let value = iterator.nth(999_999).unwrap();
+
+        assert_eq!(value, 0.1 + 1e6 * 0.1);
+        assert_eq!(iterator.step, 1_000_000);
+    }
+
+    #[test]
+    fn test_line_forecast_empty_data() {
+        let result = LineForecast::carry_forward(&[]);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Input data cannot be empty");
+    }
+}
@@ -0,0 +1,293 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::stats::student_t_two_sided_p;
+use crate::trend::{fit_linear_trend_impl, fit_polynomial_impl};
+
+/// Residual sums of squares below this fraction of the total are treated as
+/// an exact fit by `auto_detrend_impl`.
+const EXACT_FIT_TOLERANCE: f64 = 1e-12;
+
+/// Core conditional detrending logic: the series with its least squares line
+/// removed if the slope is significant at `p_threshold`, and whether it was.
+///
+/// The slope is tested against zero with the usual t statistic
+/// `slope / se(slope)` on `n - 2` degrees of freedom. A series lying exactly
+/// on a line has no residual variance: its slope counts as significant
+/// unless it is zero.
+pub(crate) fn conditional_detrend_impl(
+    data: &[f64],
+    p_threshold: f64,
+) -> Result<(Vec<f64>, bool), String> {
+    if !(p_threshold > 0.0 && p_threshold <= 1.0) {
+        return Err(format!("P-value threshold must be in (0, 1], got {}", p_threshold));
+    }
+
+    if data.len() < 3 {
+        return Err(format!(
+            "At least three data points are required to test a trend, got {}",
+            data.len()
+        ));
+    }
+
+    if data.iter().any(|y| !y.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    let (slope, intercept) = fit_linear_trend_impl(data)?;
+    let residuals: Vec<f64> = data
+        .iter()
+        .enumerate()
+        .map(|(t, y)| y - (intercept + slope * t as f64))
+        .collect();
+
+    // Spread of the time index 0..n about its mean, n (n^2 - 1) / 12
+    let n = data.len() as f64;
+    let sxx = n * (n * n - 1.0) / 12.0;
+    let df = n - 2.0;
+    let sse: f64 = residuals.iter().map(|r| r * r).sum();
+    let standard_error = (sse / df / sxx).sqrt();
+
+    let p_value = if standard_error > 0.0 {
+        student_t_two_sided_p(slope / standard_error, df)
+    } else if slope == 0.0 {
+        1.0
+    } else {
+        0.0
+    };
+
+    if p_value < p_threshold {
+        Ok((residuals, true))
+    } else {
+        Ok((data.to_vec(), false))
+    }
+}
+
+/// Core automatic polynomial detrending logic: the residuals around the
+/// polynomial trend of degree `0..=max_degree` with the lowest BIC, and that
+/// degree.
+///
+/// BIC is `n ln(SSE / n) + (degree + 1) ln(n)`, ties going to the lower
+/// degree. An SSE is floored at `EXACT_FIT_TOLERANCE` times the total sum of
+/// squares: without that, on a noise-free series the rounding residue of
+/// every degree above the true one would still shrink and look like signal.
+/// Degrees too high to fit in floating point are not considered.
+pub(crate) fn auto_detrend_impl(
+    data: &[f64],
+    max_degree: usize,
+) -> Result<(Vec<f64>, usize), String> {
+    if max_degree == 0 {
+        return Err("Maximum degree must be greater than 0".to_string());
+    }
+
+    if data.len() < max_degree + 2 {
+        return Err(format!(
+            "Selecting a degree up to {} requires at least {} data points, got {}",
+            max_degree,
+            max_degree + 2,
+            data.len()
+        ));
+    }
+
+    if data.iter().any(|y| !y.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let total: f64 = data.iter().map(|y| (y - mean).powi(2)).sum();
+    let scale = (data.len() - 1) as f64;
+
+    // A constant series has zero total: every degree then ties at the
+    // smallest positive floor and the mean is kept
+    let floor = (EXACT_FIT_TOLERANCE * total).max(f64::MIN_POSITIVE);
+
+    let mut best = (f64::INFINITY, 0, Vec::new());
+    for degree in 0..=max_degree {
+        let coefficients = if degree == 0 {
+            vec![mean]
+        } else {
+            // The length checks leave only a singular fit, and conditioning
+            // only worsens with the degree, so keep the best so far
+            match fit_polynomial_impl(data, degree) {
+                Ok(coefficients) => coefficients,
+                Err(_) => break,
+            }
+        };
+        let residuals: Vec<f64> = data
+            .iter()
+            .enumerate()
+            .map(|(t, y)| {
+                let u = t as f64 / scale;
+                y - coefficients.iter().rev().fold(0.0, |acc, c| acc * u + c)
+            })
+            .collect();
+
+        let sse: f64 = residuals.iter().map(|r| r * r).sum();
+        let bic = n * (sse.max(floor) / n).ln() + (degree + 1) as f64 * n.ln();
+        if bic < best.0 {
+            best = (bic, degree, residuals);
+        }
+    }
+
+    Ok((best.2, best.1))
+}
+
+/// Remove a linear trend only when the data supports one.
+///
+/// Fits `y = intercept + slope * t` by least squares and tests the slope
+/// against zero with a two-sided t-test. If the p-value is below
+/// `p_threshold`, the fitted line is subtracted, leaving the residuals
+/// (which average zero, as in `scipy.signal.detrend`); otherwise the series
+/// comes back unchanged, since detrending a flat series only adds noise.
+///
+/// # Arguments
+///
+/// * `data` - Historical data
+/// * `p_threshold` - Significance level of the slope test, in `(0, 1]`
+///
+/// # Returns
+///
+/// Tuple `(series, detrended)`: the residuals and `true` if the trend was
+/// significant, otherwise a copy of `data` and `false`.
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * p_threshold is not in `(0, 1]`
+/// * data has fewer than three points or a non-finite value
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// series, detrended = sa_native.conditional_detrend([1.0, 2.1, 2.9, 4.2, 5.0], 0.05)
+/// # detrended is True and series holds the residuals around the fitted line
+/// ```
+#[pyfunction]
+pub fn conditional_detrend(data: Vec<f64>, p_threshold: f64) -> PyResult<(Vec<f64>, bool)> {
+    conditional_detrend_impl(&data, p_threshold).map_err(PyValueError::new_err)
+}
+
+/// Remove a polynomial trend whose degree is chosen from the data.
+///
+/// Polynomials of degree 0 (the mean) up to `max_degree` are fitted by least
+/// squares, and the one with the lowest Bayesian information criterion,
+/// `n ln(SSE / n) + (degree + 1) ln(n)`, is subtracted. The penalty keeps a
+/// higher degree from winning on noise alone, where the raw error would
+/// always favor it. Fits indistinguishable from exact in floating point
+/// count as exact, so a noise-free polynomial gets its true degree. Degrees
+/// too high to fit in floating point end the search.
+///
+/// # Arguments
+///
+/// * `data` - Historical data
+/// * `max_degree` - Highest degree considered, at least 1
+///
+/// # Returns
+///
+/// Tuple `(residuals, degree)`: the data minus the selected trend, and its
+/// degree
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * max_degree is 0
+/// * data has fewer than `max_degree + 2` points or a non-finite value
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// residuals, degree = sa_native.auto_detrend([t * t + 1.0 for t in range(20)], 4)
+/// # degree == 2 and the residuals are all close to zero
+/// ```
+#[pyfunction]
+pub fn auto_detrend(data: Vec<f64>, max_degree: usize) -> PyResult<(Vec<f64>, usize)> {
+    auto_detrend_impl(&data, max_degree).map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_conditional_detrend_removes_clear_trend() {
+        let mut rng = Rng::new(11);
+        let data: Vec<f64> = (0..40).map(|t| 10.0 + 0.5 * t as f64 + rng.normal()).collect();
+        let (series, detrended) = conditional_detrend_impl(&data, 0.05).unwrap();
+
+        assert!(detrended);
+        assert!(series.iter().sum::<f64>().abs() < 1e-9);
+        let (slope, _) = fit_linear_trend_impl(&series).unwrap();
+        assert!(slope.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_conditional_detrend_leaves_flat_noise_alone() {
+        let mut rng = Rng::new(3);
+        let data: Vec<f64> = (0..40).map(|_| 10.0 + rng.normal()).collect();
+        let (series, detrended) = conditional_detrend_impl(&data, 0.05).unwrap();
+
+        assert!(!detrended);
+        assert_eq!(series, data);
+    }
+
+    #[test]
+    fn test_conditional_detrend_invalid_threshold() {
+        let result = conditional_detrend_impl(&[1.0, 2.0, 3.0], 0.0);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "P-value threshold must be in (0, 1], got 0");
+    }
+
+    #[test]
+    fn test_auto_detrend_selects_true_degree() {
+        let mut rng = Rng::new(31);
+        let quadratic: Vec<f64> = (0..60)
+            .map(|t| {
+                let t = t as f64;
+                5.0 + 0.3 * t - 0.02 * t * t + 0.5 * rng.normal()
+            })
+            .collect();
+        let linear: Vec<f64> = (0..60).map(|t| 2.0 + 0.4 * t as f64 + 0.5 * rng.normal()).collect();
+
+        let (residuals, degree) = auto_detrend_impl(&quadratic, 5).unwrap();
+        assert_eq!(degree, 2);
+        assert!(residuals.iter().sum::<f64>().abs() < 1e-9);
+        assert_eq!(auto_detrend_impl(&linear, 5).unwrap().1, 1);
+    }
+
+    #[test]
+    fn test_auto_detrend_noise_free_and_flat_series() {
+        let cubic: Vec<f64> = (0..30).map(|t| (t as f64 - 10.0).powi(3)).collect();
+        let (residuals, degree) = auto_detrend_impl(&cubic, 6).unwrap();
+
+        assert_eq!(degree, 3);
+        assert!(residuals.iter().all(|r| r.abs() < 1e-6));
+        assert_eq!(auto_detrend_impl(&[4.0; 10], 3).unwrap(), (vec![0.0; 10], 0));
+    }
+
+    #[test]
+    fn test_auto_detrend_stops_at_singular_degree() {
+        let mut rng = Rng::new(17);
+        let data: Vec<f64> = (0..40).map(|t| 3.0 + 0.2 * t as f64 + rng.normal()).collect();
+        let (_, low) = auto_detrend_impl(&data, 5).unwrap();
+
+        for max_degree in [10, 12, 15, 20, 30] {
+            assert_eq!(auto_detrend_impl(&data, max_degree).unwrap().1, low);
+        }
+    }
+
+    #[test]
+    fn test_auto_detrend_too_few_points() {
+        let result = auto_detrend_impl(&[1.0, 2.0, 3.0, 4.0], 3);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Selecting a degree up to 3 requires at least 5 data points, got 4"
+        );
+    }
+}
@@ -0,0 +1,993 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::linalg::least_squares;
+
+/// Core weighted least squares fit of `y = intercept + slope * t` over the
+/// time index `t = 0, 1, ..., n - 1`.
+///
+/// Returns `(slope, intercept)`.
+pub(crate) fn fit_weighted_linear_trend_impl(
+    data: &[f64],
+    weights: &[f64],
+) -> Result<(f64, f64), String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    if data.len() < 2 {
+        return Err("At least two data points are required to fit a trend".to_string());
+    }
+
+    if weights.len() != data.len() {
+        return Err(format!(
+            "Weights length ({}) must match data length ({})",
+            weights.len(),
+            data.len()
+        ));
+    }
+
+    if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+        return Err("Weights must be finite and non-negative".to_string());
+    }
+
+    let weight_sum: f64 = weights.iter().sum();
+    if weight_sum == 0.0 {
+        return Err("Weights cannot all be zero".to_string());
+    }
+
+    // Weighted means of the time index and of the data
+    let t_mean = weights
+        .iter()
+        .enumerate()
+        .map(|(t, w)| w * t as f64)
+        .sum::<f64>()
+        / weight_sum;
+    let y_mean = weights
+        .iter()
+        .zip(data)
+        .map(|(w, y)| w * y)
+        .sum::<f64>()
+        / weight_sum;
+
+    let mut sxy = 0.0;
+    let mut sxx = 0.0;
+    for (t, (w, y)) in weights.iter().zip(data).enumerate() {
+        let dt = t as f64 - t_mean;
+        sxy += w * dt * (y - y_mean);
+        sxx += w * dt * dt;
+    }
+
+    if sxx == 0.0 {
+        return Err("Weights must be positive for at least two data points".to_string());
+    }
+
+    let slope = sxy / sxx;
+    let intercept = y_mean - slope * t_mean;
+
+    Ok((slope, intercept))
+}
+
+/// Ordinary (unweighted) least squares trend fit, returning `(slope, intercept)`.
+pub(crate) fn fit_linear_trend_impl(data: &[f64]) -> Result<(f64, f64), String> {
+    fit_weighted_linear_trend_impl(data, &vec![1.0; data.len()])
+}
+
+/// Extend a fitted line `horizon` steps past the end of a series of length `n`.
+pub(crate) fn extrapolate_line(slope: f64, intercept: f64, n: usize, horizon: usize) -> Vec<f64> {
+    (n..n + horizon)
+        .map(|t| intercept + slope * t as f64)
+        .collect()
+}
+
+/// Extend a fitted exponential curve `horizon` steps past the end of a series of
+/// length `n`.
+pub(crate) fn extrapolate_exponential(growth: f64, scale: f64, n: usize, horizon: usize) -> Vec<f64> {
+    (n..n + horizon)
+        .map(|t| scale * growth.powi(t as i32))
+        .collect()
+}
+
+/// Core percentage-change fit: the mean period-over-period relative change.
+pub(crate) fn fit_pct_change_impl(data: &[f64]) -> Result<f64, String> {
+    if data.len() < 2 {
+        return Err("Percentage-change forecast requires at least two data points".to_string());
+    }
+
+    if data.iter().any(|&y| !y.is_finite() || y <= 0.0) {
+        return Err("Percentage-change forecast requires strictly positive data".to_string());
+    }
+
+    let total: f64 = data.windows(2).map(|w| (w[1] - w[0]) / w[0]).sum();
+    Ok(total / (data.len() - 1) as f64)
+}
+
+/// Compound a per-period rate forward from `last` for `horizon` steps.
+pub(crate) fn compound_from(last: f64, rate: f64, horizon: usize) -> Vec<f64> {
+    (1..=horizon)
+        .map(|h| last * (1.0 + rate).powi(h as i32))
+        .collect()
+}
+
+/// Core percentage-change prediction logic.
+pub(crate) fn predict_pct_change_impl(data: &[f64], horizon: usize) -> Result<Vec<f64>, String> {
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let rate = fit_pct_change_impl(data)?;
+    Ok(compound_from(data[data.len() - 1], rate, horizon))
+}
+
+/// Core drift fit: the slope of the line through the first and last values,
+/// the mean one-step change.
+pub(crate) fn fit_drift_impl(data: &[f64]) -> Result<f64, String> {
+    if data.len() < 2 {
+        return Err("Drift forecast requires at least two data points".to_string());
+    }
+
+    Ok((data[data.len() - 1] - data[0]) / (data.len() - 1) as f64)
+}
+
+/// Core drift prediction logic.
+pub(crate) fn predict_drift_impl(data: &[f64], horizon: usize) -> Result<Vec<f64>, String> {
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let slope = fit_drift_impl(data)?;
+    let last = data[data.len() - 1];
+    Ok((1..=horizon).map(|h| last + h as f64 * slope).collect())
+}
+
+/// Core weighted trend prediction logic.
+pub(crate) fn predict_weighted_linear_trend_impl(
+    data: &[f64],
+    weights: &[f64],
+    horizon: usize,
+) -> Result<Vec<f64>, String> {
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let (slope, intercept) = fit_weighted_linear_trend_impl(data, weights)?;
+    Ok(extrapolate_line(slope, intercept, data.len(), horizon))
+}
+
+/// Core ordinary least squares trend prediction logic.
+pub(crate) fn predict_linear_trend_impl(data: &[f64], horizon: usize) -> Result<Vec<f64>, String> {
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let (slope, intercept) = fit_linear_trend_impl(data)?;
+    Ok(extrapolate_line(slope, intercept, data.len(), horizon))
+}
+
+/// Iteration limit and convergence tolerance of the robust trend fit.
+const ROBUST_MAX_ITERATIONS: usize = 100;
+const ROBUST_TOLERANCE: f64 = 1e-10;
+
+/// Core robust trend fit: iteratively reweighted least squares with a Huber
+/// loss. Residuals within `delta` get full weight, larger ones get weight
+/// `delta / |residual|`, so outliers contribute linearly instead of
+/// quadratically.
+///
+/// Returns `(slope, intercept)`.
+pub(crate) fn fit_robust_trend_impl(data: &[f64], delta: f64) -> Result<(f64, f64), String> {
+    if !(delta.is_finite() && delta > 0.0) {
+        return Err("Delta must be a finite value greater than 0".to_string());
+    }
+
+    let (mut slope, mut intercept) = fit_linear_trend_impl(data)?;
+    let mut weights = vec![1.0; data.len()];
+
+    for _ in 0..ROBUST_MAX_ITERATIONS {
+        for (t, (w, y)) in weights.iter_mut().zip(data).enumerate() {
+            let residual = (y - (intercept + slope * t as f64)).abs();
+            *w = if residual <= delta { 1.0 } else { delta / residual };
+        }
+
+        let (new_slope, new_intercept) = fit_weighted_linear_trend_impl(data, &weights)?;
+        let change = (new_slope - slope).abs() + (new_intercept - intercept).abs();
+        slope = new_slope;
+        intercept = new_intercept;
+        if change < ROBUST_TOLERANCE {
+            break;
+        }
+    }
+
+    Ok((slope, intercept))
+}
+
+/// Core robust trend prediction logic.
+pub(crate) fn predict_robust_trend_impl(
+    data: &[f64],
+    delta: f64,
+    horizon: usize,
+) -> Result<Vec<f64>, String> {
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let (slope, intercept) = fit_robust_trend_impl(data, delta)?;
+    Ok(extrapolate_line(slope, intercept, data.len(), horizon))
+}
+
+/// Core exponential trend fit: a least squares line through `ln(data)`.
+///
+/// Returns `(growth_factor, scale)` such that the fitted curve is
+/// `scale * growth_factor^t`, so a series doubling each step has a growth
+/// factor of 2.
+pub(crate) fn fit_exponential_trend_impl(data: &[f64]) -> Result<(f64, f64), String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    if data.iter().any(|&y| !y.is_finite() || y <= 0.0) {
+        return Err("Exponential trend requires strictly positive data".to_string());
+    }
+
+    let log_data: Vec<f64> = data.iter().map(|y| y.ln()).collect();
+    let (slope, intercept) = fit_linear_trend_impl(&log_data)?;
+
+    Ok((slope.exp(), intercept.exp()))
+}
+
+/// Core exponential trend prediction logic.
+pub(crate) fn predict_exponential_trend_impl(
+    data: &[f64],
+    horizon: usize,
+) -> Result<Vec<f64>, String> {
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let (growth, scale) = fit_exponential_trend_impl(data)?;
+    Ok(extrapolate_exponential(growth, scale, data.len(), horizon))
+}
+
+/// Clip each forecast into `clamp_range`, leaving values untouched when no
+/// range is given.
+///
+/// A data-relative choice such as
+/// `(min(data) - k * range, max(data) + k * range)`, with `range` the spread
+/// of the data, bounds extrapolation to `k` data ranges beyond what was seen.
+pub(crate) fn clamp_forecasts(
+    values: Vec<f64>,
+    clamp_range: Option<(f64, f64)>,
+) -> Result<Vec<f64>, String> {
+    let (lower, upper) = match clamp_range {
+        Some(range) => range,
+        None => return Ok(values),
+    };
+
+    if lower.is_nan() || upper.is_nan() || lower > upper {
+        return Err(format!(
+            "Clamp range must satisfy lower <= upper, got ({}, {})",
+            lower, upper
+        ));
+    }
+
+    Ok(values.into_iter().map(|v| v.clamp(lower, upper)).collect())
+}
+
+/// Core least squares polynomial fit over the time index.
+///
+/// The index is rescaled to `u = t / (n - 1)` before building the design
+/// matrix so high powers stay well conditioned. Returns the coefficients of
+/// `c0 + c1 * u + ... + cd * u^d`, lowest degree first.
+pub(crate) fn fit_polynomial_impl(data: &[f64], degree: usize) -> Result<Vec<f64>, String> {
+    if degree == 0 {
+        return Err("Degree must be greater than 0".to_string());
+    }
+
+    if data.len() <= degree {
+        return Err(format!(
+            "A degree {} polynomial requires at least {} data points, got {}",
+            degree,
+            degree + 1,
+            data.len()
+        ));
+    }
+
+    let scale = (data.len() - 1) as f64;
+    let rows: Vec<Vec<f64>> = (0..data.len())
+        .map(|t| {
+            let u = t as f64 / scale;
+            (0..=degree).map(|p| u.powi(p as i32)).collect()
+        })
+        .collect();
+
+    least_squares(&rows, data)
+}
+
+/// Core polynomial trend prediction logic.
+pub(crate) fn predict_polynomial_impl(
+    data: &[f64],
+    horizon: usize,
+    degree: usize,
+    clamp_range: Option<(f64, f64)>,
+) -> Result<Vec<f64>, String> {
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let coefficients = fit_polynomial_impl(data, degree)?;
+    let n = data.len();
+    let scale = (n - 1) as f64;
+
+    // Horner evaluation at the rescaled future index
+    let forecasts = (n..n + horizon)
+        .map(|t| {
+            let u = t as f64 / scale;
+            coefficients.iter().rev().fold(0.0, |acc, c| acc * u + c)
+        })
+        .collect();
+
+    clamp_forecasts(forecasts, clamp_range)
+}
+
+/// Core natural cubic spline fit through the data at `t = 0, 1, ..., n - 1`.
+///
+/// Returns the second derivative at every knot, zero at both ends. With unit
+/// spacing the interior ones solve the tridiagonal system
+/// `m[i - 1] + 4 m[i] + m[i + 1] = 6 (y[i + 1] - 2 y[i] + y[i - 1])`, which
+/// is diagonally dominant, so elimination needs no pivoting.
+fn fit_natural_spline(data: &[f64]) -> Vec<f64> {
+    let n = data.len();
+    let mut curvature = vec![0.0; n];
+    if n < 3 {
+        return curvature;
+    }
+
+    // Forward sweep of the Thomas algorithm over the interior knots
+    let mut upper = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+    for i in 1..n - 1 {
+        let second_difference = 6.0 * (data[i + 1] - 2.0 * data[i] + data[i - 1]);
+        let pivot = 4.0 - upper[i - 1];
+        upper[i] = 1.0 / pivot;
+        rhs[i] = (second_difference - rhs[i - 1]) / pivot;
+    }
+    for i in (1..n - 1).rev() {
+        curvature[i] = rhs[i] - upper[i] * curvature[i + 1];
+    }
+
+    curvature
+}
+
+/// Core spline extrapolation logic: the natural cubic spline through the
+/// data, with its last piece continued past the end.
+pub(crate) fn predict_spline_impl(
+    data: &[f64],
+    horizon: usize,
+    clamp_range: Option<(f64, f64)>,
+) -> Result<Vec<f64>, String> {
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    if data.len() < 3 {
+        return Err(format!(
+            "A cubic spline requires at least 3 data points, got {}",
+            data.len()
+        ));
+    }
+
+    if data.iter().any(|y| !y.is_finite()) {
+        return Err("Input data must be finite".to_string());
+    }
+
+    let n = data.len();
+    let curvature = fit_natural_spline(data);
+
+    // The last piece, on [n - 2, n - 1], as a cubic in s = t - (n - 2)
+    let (y0, y1) = (data[n - 2], data[n - 1]);
+    let (m0, m1) = (curvature[n - 2], curvature[n - 1]);
+    let slope = (y1 - y0) - (2.0 * m0 + m1) / 6.0;
+    let forecasts = (2..horizon + 2)
+        .map(|s| {
+            let s = s as f64;
+            y0 + s * (slope + s * (m0 / 2.0 + s * (m1 - m0) / 6.0))
+        })
+        .collect();
+
+    clamp_forecasts(forecasts, clamp_range)
+}
+
+/// Core logistic growth fit against a known `capacity`.
+///
+/// The curve `capacity / (1 + exp(-(a + b * t)))` becomes the line `a + b * t`
+/// under the logit transform `ln(y / (capacity - y))`, which is fitted by
+/// least squares. Returns `(b, a)`, matching the `(slope, intercept)` order of
+/// the linear fit.
+pub(crate) fn fit_logistic_impl(data: &[f64], capacity: f64) -> Result<(f64, f64), String> {
+    if data.is_empty() {
+        return Err("Input data cannot be empty".to_string());
+    }
+
+    if data.iter().any(|&y| !y.is_finite() || y <= 0.0) {
+        return Err("Logistic growth requires strictly positive data".to_string());
+    }
+
+    let max = data.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    if !capacity.is_finite() || capacity <= max {
+        return Err(format!(
+            "Capacity must be finite and greater than the data maximum ({}), got {}",
+            max, capacity
+        ));
+    }
+
+    let logits: Vec<f64> = data.iter().map(|&y| (y / (capacity - y)).ln()).collect();
+    fit_linear_trend_impl(&logits)
+}
+
+/// Core logistic growth prediction logic.
+pub(crate) fn predict_logistic_impl(
+    data: &[f64],
+    horizon: usize,
+    capacity: f64,
+) -> Result<Vec<f64>, String> {
+    if horizon == 0 {
+        return Err("Horizon must be greater than 0".to_string());
+    }
+
+    let (slope, intercept) = fit_logistic_impl(data, capacity)?;
+    Ok(extrapolate_line(slope, intercept, data.len(), horizon)
+        .into_iter()
+        .map(|z| capacity / (1.0 + (-z).exp()))
+        .collect())
+}
+
+/// Fit a linear trend by weighted least squares.
+///
+/// Each observation contributes to the fit in proportion to its weight, so
+/// giving recent points larger weights makes the trend follow recent behavior.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `weights` - Non-negative weight for each observation
+///
+/// # Returns
+///
+/// Tuple `(slope, intercept)` of the fitted line over the index `0..n`
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * data has fewer than two points
+/// * weights and data lengths differ
+/// * weights are negative, non-finite or all zero
+#[pyfunction]
+pub fn fit_weighted_linear_trend(data: Vec<f64>, weights: Vec<f64>) -> PyResult<(f64, f64)> {
+    fit_weighted_linear_trend_impl(&data, &weights).map_err(PyValueError::new_err)
+}
+
+/// Predict future values by extrapolating a weighted least squares trend.
+///
+/// # Errors
+///
+/// Returns PyValueError under the same conditions as
+/// `fit_weighted_linear_trend`, or if horizon is 0.
+#[pyfunction]
+pub fn predict_weighted_linear_trend(
+    data: Vec<f64>,
+    weights: Vec<f64>,
+    horizon: usize,
+) -> PyResult<Vec<f64>> {
+    predict_weighted_linear_trend_impl(&data, &weights, horizon).map_err(PyValueError::new_err)
+}
+
+/// Predict future values by extrapolating an ordinary least squares trend.
+///
+/// # Errors
+///
+/// Returns PyValueError if data has fewer than two points or horizon is 0.
+#[pyfunction]
+pub fn predict_linear_trend(data: Vec<f64>, horizon: usize) -> PyResult<Vec<f64>> {
+    predict_linear_trend_impl(&data, horizon).map_err(PyValueError::new_err)
+}
+
+/// Fit a linear trend that down-weights outliers (Huber loss).
+///
+/// Iteratively reweighted least squares: residuals smaller than `delta` are
+/// treated as in ordinary least squares, larger ones are down-weighted so a
+/// few spikes cannot drag the line. `delta` is in the units of the data.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `delta` - Huber threshold separating inliers from outliers
+///
+/// # Returns
+///
+/// Tuple `(slope, intercept)` of the fitted line over the index `0..n`
+///
+/// # Errors
+///
+/// Returns PyValueError if data has fewer than two points or delta is not
+/// greater than 0.
+#[pyfunction]
+pub fn fit_robust_trend(data: Vec<f64>, delta: f64) -> PyResult<(f64, f64)> {
+    fit_robust_trend_impl(&data, delta).map_err(PyValueError::new_err)
+}
+
+/// Predict future values by extrapolating a robust (Huber) trend.
+///
+/// # Errors
+///
+/// Returns PyValueError under the same conditions as `fit_robust_trend`, or
+/// if horizon is 0.
+#[pyfunction]
+pub fn predict_robust_trend(data: Vec<f64>, delta: f64, horizon: usize) -> PyResult<Vec<f64>> {
+    predict_robust_trend_impl(&data, delta, horizon).map_err(PyValueError::new_err)
+}
+
+/// Predict future values by extrapolating an exponential (geometric) trend.
+///
+/// A line is fitted to the logarithm of the data and extrapolated, then mapped
+/// back to the original scale with `exp`. Suited to series growing by a
+/// roughly constant percentage per step.
+///
+/// # Arguments
+///
+/// * `data` - Historical data, all values strictly positive
+/// * `horizon` - Number of future values to predict
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * data has fewer than two points
+/// * any value is zero, negative or not finite
+/// * horizon is 0
+#[pyfunction]
+pub fn predict_exponential_trend(data: Vec<f64>, horizon: usize) -> PyResult<Vec<f64>> {
+    predict_exponential_trend_impl(&data, horizon).map_err(PyValueError::new_err)
+}
+
+/// Predict future values by compounding the mean percentage change.
+///
+/// The mean period-over-period change `(y_t - y_{t-1}) / y_{t-1}` of the
+/// history is applied repeatedly starting from the last value.
+///
+/// # Arguments
+///
+/// * `data` - Historical data, all values strictly positive
+/// * `horizon` - Number of future values to predict
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * data has fewer than two points
+/// * any value is zero, negative or not finite
+/// * horizon is 0
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// result = sa_native.predict_pct_change([100.0, 110.0, 121.0], 2)
+/// # Returns [133.1, 146.41] (10% growth per step)
+/// ```
+#[pyfunction]
+pub fn predict_pct_change(data: Vec<f64>, horizon: usize) -> PyResult<Vec<f64>> {
+    predict_pct_change_impl(&data, horizon).map_err(PyValueError::new_err)
+}
+
+/// Predict future values with the drift (random walk with drift) method.
+///
+/// The last value is carried forward along the mean one-step change of the
+/// history, `(y_last - y_first) / (n - 1)`: the line through the first and
+/// last observations, extended past the end.
+///
+/// # Arguments
+///
+/// * `data` - Historical data
+/// * `horizon` - Number of future values to predict
+///
+/// # Errors
+///
+/// Returns PyValueError if data has fewer than two points or horizon is 0.
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// sa_native.predict_drift([10.0, 13.0, 12.0, 16.0], 2)
+/// # [18.0, 20.0]
+/// ```
+#[pyfunction]
+pub fn predict_drift(data: Vec<f64>, horizon: usize) -> PyResult<Vec<f64>> {
+    predict_drift_impl(&data, horizon).map_err(PyValueError::new_err)
+}
+
+/// Predict future values by extrapolating a least squares polynomial trend.
+///
+/// High-degree polynomials can run far outside the range of the data once
+/// extrapolated. Pass `clamp_range=(lower, upper)` to clip every forecast
+/// into that interval; by default no clamping is applied. A reasonable
+/// choice is `(min(data) - k * r, max(data) + k * r)` with `r` the data
+/// range and a small `k` such as 1.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Number of future periods to predict
+/// * `degree` - Polynomial degree (1 is a straight line)
+/// * `clamp_range` - Optional `(lower, upper)` bounds for the forecasts
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * degree is 0 or data has no more points than the degree
+/// * horizon is 0
+/// * clamp_range has lower > upper
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// data = [0.0, 1.0, 8.0, 27.0, 64.0]
+/// result = sa_native.predict_polynomial(data, 3, 3, clamp_range=(0.0, 100.0))
+/// # Returns [100.0, 100.0, 100.0] instead of [125.0, 216.0, 343.0]
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, horizon, degree, clamp_range=None))]
+pub fn predict_polynomial(
+    data: Vec<f64>,
+    horizon: usize,
+    degree: usize,
+    clamp_range: Option<(f64, f64)>,
+) -> PyResult<Vec<f64>> {
+    predict_polynomial_impl(&data, horizon, degree, clamp_range).map_err(PyValueError::new_err)
+}
+
+/// Predict future values by extrapolating a natural cubic spline.
+///
+/// The spline passes through every observation at `t = 0, 1, ..., n - 1`
+/// with zero curvature at both ends, and its last cubic piece is continued
+/// past the data, as `scipy.interpolate.CubicSpline` extrapolates. That
+/// piece bends with the final few points and can run far outside the data,
+/// so `clamp_range=(lower, upper)` clips every forecast into that interval,
+/// as in `predict_polynomial`; by default no clamping is applied.
+///
+/// # Arguments
+///
+/// * `data` - Historical data as a vector of floats
+/// * `horizon` - Number of future periods to predict
+/// * `clamp_range` - Optional `(lower, upper)` bounds for the forecasts
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * data has fewer than 3 points or a non-finite value
+/// * horizon is 0
+/// * clamp_range has lower > upper
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// data = [1.0, 2.0, 3.0, 4.0, 9.0]
+/// result = sa_native.predict_spline(data, 4, clamp_range=(0.0, 20.0))
+/// # About [14.0, 12.57, 0.0, 0.0]: the last piece turns down steeply and
+/// # is clipped at 0.0 instead of reaching -35.3
+/// ```
+#[pyfunction]
+#[pyo3(signature = (data, horizon, clamp_range=None))]
+pub fn predict_spline(
+    data: Vec<f64>,
+    horizon: usize,
+    clamp_range: Option<(f64, f64)>,
+) -> PyResult<Vec<f64>> {
+    predict_spline_impl(&data, horizon, clamp_range).map_err(PyValueError::new_err)
+}
+
+/// Predict future values with a logistic growth curve saturating at a known
+/// capacity.
+///
+/// The logit `ln(y / (capacity - y))` of the data is fitted with a straight
+/// line over time and extrapolated, then mapped back with
+/// `capacity / (1 + exp(-z))`. Forecasts approach the capacity but never
+/// exceed it, which suits adoption curves with a known ceiling.
+///
+/// # Arguments
+///
+/// * `data` - Historical data, all values strictly positive
+/// * `horizon` - Number of future values to predict
+/// * `capacity` - Carrying capacity the curve saturates at
+///
+/// # Errors
+///
+/// Returns PyValueError if:
+/// * data has fewer than two points
+/// * any value is zero, negative or not finite
+/// * capacity is not finite or does not exceed the data maximum
+/// * horizon is 0
+///
+/// # Examples
+///
+/// ```python
+/// import sa_native
+/// result = sa_native.predict_logistic([10.0, 20.0, 33.3, 50.0], 2, 100.0)
+/// # Returns roughly [67.9, 81.5], rising toward 100
+/// ```
+#[pyfunction]
+pub fn predict_logistic(data: Vec<f64>, horizon: usize, capacity: f64) -> PyResult<Vec<f64>> {
+    predict_logistic_impl(&data, horizon, capacity).map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_weighted_linear_trend_uniform_weights_match_ols() {
+        let data = vec![1.0, 3.0, 5.0, 7.0];
+        let (slope, intercept) = fit_weighted_linear_trend_impl(&data, &[2.0; 4]).unwrap();
+
+        assert!((slope - 2.0).abs() < 1e-12);
+        assert!((intercept - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fit_weighted_linear_trend_recent_weights_follow_recent_slope() {
+        // Flat for ten steps, then rising by 3 per step
+        let mut data = vec![10.0; 10];
+        data.extend((1..=10).map(|i| 10.0 + 3.0 * i as f64));
+        let weights: Vec<f64> = (0..data.len()).map(|t| 1.5_f64.powi(t as i32)).collect();
+
+        let (ols_slope, _) = fit_linear_trend_impl(&data).unwrap();
+        let (wls_slope, _) = fit_weighted_linear_trend_impl(&data, &weights).unwrap();
+
+        assert!(wls_slope > ols_slope);
+        assert!((wls_slope - 3.0).abs() < (ols_slope - 3.0).abs());
+    }
+
+    #[test]
+    fn test_fit_weighted_linear_trend_length_mismatch() {
+        let result = fit_weighted_linear_trend_impl(&[1.0, 2.0, 3.0], &[1.0, 1.0]);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Weights length (2) must match data length (3)"
+        );
+    }
+
+    #[test]
+    fn test_fit_weighted_linear_trend_all_zero_weights() {
+        let result = fit_weighted_linear_trend_impl(&[1.0, 2.0, 3.0], &[0.0; 3]);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Weights cannot all be zero");
+    }
+
+    #[test]
+    fn test_predict_weighted_linear_trend_extrapolates() {
+        let data = vec![0.0, 1.0, 2.0];
+        let result = predict_weighted_linear_trend_impl(&data, &[1.0, 2.0, 3.0], 2).unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert!((result[0] - 3.0).abs() < 1e-12);
+        assert!((result[1] - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fit_exponential_trend_doubling() {
+        let data: Vec<f64> = (0..8).map(|t| 3.0 * 2.0_f64.powi(t)).collect();
+        let (growth, scale) = fit_exponential_trend_impl(&data).unwrap();
+
+        assert!((growth - 2.0).abs() < 1e-9);
+        assert!((scale - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_predict_exponential_trend_doubling() {
+        let data = vec![1.0, 2.0, 4.0, 8.0];
+        let result = predict_exponential_trend_impl(&data, 2).unwrap();
+
+        assert!((result[0] - 16.0).abs() < 1e-9);
+        assert!((result[1] - 32.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_predict_exponential_trend_non_positive() {
+        let result = predict_exponential_trend_impl(&[1.0, 0.0, 2.0], 3);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Exponential trend requires strictly positive data"
+        );
+    }
+
+    #[test]
+    fn test_predict_drift_extends_mean_change() {
+        let result = predict_drift_impl(&[10.0, 13.0, 12.0, 16.0], 3).unwrap();
+
+        assert_eq!(result, vec![18.0, 20.0, 22.0]);
+    }
+
+    #[test]
+    fn test_predict_drift_single_point() {
+        let result = predict_drift_impl(&[5.0], 3);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Drift forecast requires at least two data points");
+    }
+
+    #[test]
+    fn test_predict_pct_change_ten_percent_growth() {
+        let data: Vec<f64> = (0..5).map(|t| 100.0 * 1.1_f64.powi(t)).collect();
+        let result = predict_pct_change_impl(&data, 3).unwrap();
+
+        for (h, value) in result.iter().enumerate() {
+            let expected = 100.0 * 1.1_f64.powi(5 + h as i32);
+            assert!((value - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_predict_pct_change_single_point() {
+        let result = predict_pct_change_impl(&[5.0], 3);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Percentage-change forecast requires at least two data points"
+        );
+    }
+
+    #[test]
+    fn test_predict_pct_change_non_positive() {
+        let result = predict_pct_change_impl(&[5.0, -1.0, 2.0], 3);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Percentage-change forecast requires strictly positive data"
+        );
+    }
+
+    #[test]
+    fn test_fit_robust_trend_resists_outlier() {
+        let mut data: Vec<f64> = (0..20).map(|t| 5.0 + 0.5 * t as f64).collect();
+        data[18] += 200.0;
+
+        let (ols_slope, _) = fit_linear_trend_impl(&data).unwrap();
+        let (robust_slope, _) = fit_robust_trend_impl(&data, 1.0).unwrap();
+
+        assert!((robust_slope - 0.5).abs() < 0.05);
+        assert!((ols_slope - 0.5).abs() > 1.0);
+    }
+
+    #[test]
+    fn test_predict_robust_trend_clean_line_matches_ols() {
+        let data: Vec<f64> = (0..10).map(|t| 2.0 * t as f64 - 1.0).collect();
+        let result = predict_robust_trend_impl(&data, 1.0, 2).unwrap();
+
+        assert!((result[0] - 19.0).abs() < 1e-9);
+        assert!((result[1] - 21.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fit_robust_trend_invalid_delta() {
+        let result = fit_robust_trend_impl(&[1.0, 2.0, 3.0], 0.0);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Delta must be a finite value greater than 0");
+    }
+
+    #[test]
+    fn test_predict_polynomial_recovers_cubic() {
+        let data: Vec<f64> = (0..6).map(|t| (t as f64).powi(3) - 2.0 * t as f64).collect();
+        let result = predict_polynomial_impl(&data, 2, 3, None).unwrap();
+
+        assert!((result[0] - 204.0).abs() < 1e-6);
+        assert!((result[1] - 329.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_predict_polynomial_clamps_cubic_extrapolation() {
+        let data = [0.0, 1.0, 8.0, 27.0, 64.0];
+        let unclamped = predict_polynomial_impl(&data, 5, 3, None).unwrap();
+        assert!(unclamped.iter().all(|&v| v > 100.0));
+
+        let clamped = predict_polynomial_impl(&data, 5, 3, Some((0.0, 100.0))).unwrap();
+        assert!(clamped.iter().all(|&v| (0.0..=100.0).contains(&v)));
+        assert_eq!(clamped, vec![100.0; 5]);
+    }
+
+    #[test]
+    fn test_predict_polynomial_invalid_clamp_range() {
+        let result = predict_polynomial_impl(&[1.0, 2.0, 3.0], 1, 1, Some((5.0, 1.0)));
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Clamp range must satisfy lower <= upper, got (5, 1)");
+    }
+
+    #[test]
+    fn test_predict_spline_continues_a_line() {
+        let data: Vec<f64> = (0..8).map(|t| 3.0 - 0.5 * t as f64).collect();
+        let result = predict_spline_impl(&data, 3, None).unwrap();
+
+        for (t, value) in (8..11).zip(&result) {
+            assert!((value - (3.0 - 0.5 * t as f64)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_predict_spline_clamps_cubic_extrapolation() {
+        // The jump at the end bends the last piece, which then turns down
+        let data = [1.0, 2.0, 3.0, 4.0, 9.0];
+        let unclamped = predict_spline_impl(&data, 6, None).unwrap();
+        assert!((unclamped[0] - 14.0).abs() < 1e-9);
+        assert!(unclamped[5] < -100.0);
+
+        let clamped = predict_spline_impl(&data, 6, Some((0.0, 20.0))).unwrap();
+        assert!(clamped.iter().all(|&v| (0.0..=20.0).contains(&v)));
+        assert_eq!(&clamped[..2], &unclamped[..2]);
+        assert_eq!(&clamped[2..], &[0.0; 4]);
+    }
+
+    #[test]
+    fn test_predict_spline_invalid_input() {
+        let result = predict_spline_impl(&[1.0, 2.0], 1, None);
+        assert_eq!(result.unwrap_err(), "A cubic spline requires at least 3 data points, got 2");
+
+        let result = predict_spline_impl(&[1.0, 2.0, 3.0], 1, Some((5.0, 1.0)));
+        assert_eq!(result.unwrap_err(), "Clamp range must satisfy lower <= upper, got (5, 1)");
+    }
+
+    #[test]
+    fn test_fit_polynomial_too_few_points() {
+        let result = fit_polynomial_impl(&[1.0, 2.0], 2);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "A degree 2 polynomial requires at least 3 data points, got 2"
+        );
+    }
+
+    #[test]
+    fn test_predict_logistic_recovers_s_curve() {
+        let capacity = 1000.0;
+        let curve = |t: usize| capacity / (1.0 + (-(-4.0 + 0.5 * t as f64)).exp());
+        let data: Vec<f64> = (0..12).map(curve).collect();
+
+        let (slope, intercept) = fit_logistic_impl(&data, capacity).unwrap();
+        assert!((slope - 0.5).abs() < 1e-9);
+        assert!((intercept + 4.0).abs() < 1e-9);
+
+        let result = predict_logistic_impl(&data, 30, capacity).unwrap();
+        for (h, value) in result.iter().enumerate() {
+            assert!((value - curve(12 + h)).abs() < 1e-6);
+        }
+        assert!(result.windows(2).all(|w| w[0] < w[1]));
+        assert!(result.iter().all(|&v| v <= capacity));
+        assert!(capacity - result[29] < 1e-3);
+    }
+
+    #[test]
+    fn test_predict_logistic_capacity_must_exceed_data() {
+        let result = predict_logistic_impl(&[10.0, 50.0, 90.0], 1, 90.0);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            "Capacity must be finite and greater than the data maximum (90), got 90"
+        );
+    }
+
+    #[test]
+    fn test_predict_logistic_non_positive() {
+        let result = predict_logistic_impl(&[0.0, 5.0], 1, 10.0);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "Logistic growth requires strictly positive data");
+    }
+}